@@ -0,0 +1,460 @@
+//! Core types for the [paracord](https://docs.rs/paracord) string interner.
+//!
+//! This crate has no required dependencies, and does not allocate, so library crates can reference
+//! [`Key`] in their public APIs without pulling in the interner itself.
+//! All items are re-exported by `paracord`.
+#![no_std]
+#![warn(
+    unsafe_op_in_unsafe_fn,
+    clippy::missing_safety_doc,
+    clippy::multiple_unsafe_ops_per_block,
+    clippy::undocumented_unsafe_blocks
+)]
+
+use core::fmt;
+use core::hash::Hash;
+use core::iter::FusedIterator;
+use core::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroUsize};
+use core::ops::Range;
+
+/// Key type returned by `ParaCord`.
+///
+/// [`Key`] implements [`core::cmp::Ord`] for use within collections like `BTreeMap`.
+/// Keys allocated by the same `ParaCord` instance are ordered by allocation: a key allocated earlier
+/// always compares less than a key allocated later. Comparing keys from different instances is not meaningful.
+///
+/// Otherwise, treat [`Key`]s as opaque blobs, with an unstable representation.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Debug, PartialEq, Eq, Hash))
+)]
+#[repr(transparent)]
+pub struct Key(NonZeroU32);
+
+#[cfg(feature = "rkyv")]
+impl ArchivedKey {
+    /// Get the [`Key`] stored in the archive.
+    #[inline]
+    pub fn to_native(&self) -> Key {
+        Key(self.0.to_native())
+    }
+}
+
+impl PartialOrd for Key {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.into_repr().cmp(&other.into_repr())
+    }
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Key").field(&self.into_repr()).finish()
+    }
+}
+
+impl Key {
+    /// Turn the key into a u32.
+    ///
+    /// The only guarantee is that [`Key::try_from_repr`] is the inverse of this function,
+    /// and will always return the same key.
+    ///
+    /// ```
+    /// use paracord_core::Key;
+    /// # let key = Key::try_from_repr(0).unwrap();
+    /// let key2 = Key::try_from_repr(key.into_repr()).unwrap();
+    /// assert_eq!(key, key2);
+    /// ```
+    #[inline]
+    pub fn into_repr(self) -> u32 {
+        self.0.get() ^ u32::MAX
+    }
+
+    /// Recreate the key from a u32.
+    ///
+    /// The only guarantee is that [`Key::into_repr`] is the inverse of this function,
+    /// and will always return the same u32.
+    #[inline]
+    pub fn try_from_repr(x: u32) -> Option<Self> {
+        NonZeroU32::new(x ^ u32::MAX).map(Self)
+    }
+
+    /// Create the key for the given index without checking it.
+    ///
+    /// # Safety
+    ///
+    /// `i` must be less than `u32::MAX`.
+    #[inline]
+    pub(crate) unsafe fn new_unchecked(i: u32) -> Self {
+        // SAFETY: from caller
+        Key(unsafe { NonZeroU32::new_unchecked(i ^ u32::MAX) })
+    }
+
+    /// Get the index of this key in allocation order.
+    ///
+    /// Unlike [`Key::into_repr`], consecutive keys have consecutive indices,
+    /// so keys can be counted and iterated with [`KeyRange`].
+    ///
+    /// ```
+    /// use paracord_core::Key;
+    /// # let key = Key::try_from_repr(0).unwrap();
+    /// let next = key.dense().next().unwrap();
+    /// assert!(key < next.key());
+    /// assert_eq!(next.index() - key.dense().index(), 1);
+    /// ```
+    #[inline]
+    pub fn dense(self) -> DenseKey {
+        DenseKey(self.into_repr())
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn from_index(i: usize) -> Self {
+        if usize::BITS >= 32 {
+            assert!(i < u32::MAX as usize);
+        }
+
+        // SAFETY: checked it is less than u32::MAX.
+        unsafe { Self::new_unchecked(i as u32) }
+    }
+}
+
+/// Key type returned by `ParaCord64`, for interners that need more than `2^32` keys.
+///
+/// Like [`Key`], this has a niche, so `Option<LargeKey>` is also 64 bits.
+/// [`LargeKey`] implements [`core::cmp::Ord`] for use within collections like `BTreeMap`,
+/// but keys are not ordered by allocation.
+///
+/// Otherwise, treat [`LargeKey`]s as opaque blobs, with an unstable representation.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[repr(transparent)]
+pub struct LargeKey(NonZeroU64);
+
+impl PartialOrd for LargeKey {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LargeKey {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.into_repr().cmp(&other.into_repr())
+    }
+}
+
+impl fmt::Debug for LargeKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LargeKey").field(&self.into_repr()).finish()
+    }
+}
+
+impl LargeKey {
+    /// Turn the key into a u64.
+    ///
+    /// The only guarantee is that [`LargeKey::try_from_repr`] is the inverse of this function,
+    /// and will always return the same key.
+    #[inline]
+    pub fn into_repr(self) -> u64 {
+        self.0.get() ^ u64::MAX
+    }
+
+    /// Recreate the key from a u64.
+    ///
+    /// The only guarantee is that [`LargeKey::into_repr`] is the inverse of this function,
+    /// and will always return the same u64.
+    #[inline]
+    pub fn try_from_repr(x: u64) -> Option<Self> {
+        NonZeroU64::new(x ^ u64::MAX).map(Self)
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn from_index(i: u64) -> Self {
+        Self::try_from_repr(i).expect("index must be less than u64::MAX")
+    }
+}
+
+/// A type that can be used as the key of an interner, like `KeyedParaCord`.
+///
+/// Keys map to and from indices in allocation order. Smaller key types pack into structs more
+/// tightly, but limit how many strings can be interned.
+///
+/// This is implemented for [`Key`] and [`LargeKey`], and for the `NonZero` integer types,
+/// which store `index + 1`.
+///
+/// ```
+/// use core::num::NonZeroU16;
+/// use paracord_core::InternKey;
+///
+/// let key = NonZeroU16::try_from_index(0).unwrap();
+/// assert_eq!(key.get(), 1);
+/// assert_eq!(key.into_index(), 0);
+/// assert_eq!(NonZeroU16::try_from_index(65535), None);
+/// ```
+pub trait InternKey: Copy + Eq + Hash + fmt::Debug {
+    /// Get the index of this key in allocation order.
+    fn into_index(self) -> usize;
+
+    /// Create the key for the given index.
+    /// Returns [`None`] if the index does not fit in this key type.
+    fn try_from_index(i: usize) -> Option<Self>;
+}
+
+impl InternKey for Key {
+    #[inline]
+    fn into_index(self) -> usize {
+        self.into_repr() as usize
+    }
+
+    #[inline]
+    fn try_from_index(i: usize) -> Option<Self> {
+        Self::try_from_repr(u32::try_from(i).ok()?)
+    }
+}
+
+impl InternKey for LargeKey {
+    #[inline]
+    fn into_index(self) -> usize {
+        self.into_repr() as usize
+    }
+
+    #[inline]
+    fn try_from_index(i: usize) -> Option<Self> {
+        Self::try_from_repr(u64::try_from(i).ok()?)
+    }
+}
+
+macro_rules! non_zero_key {
+    ($($t:ty: $int:ty),*) => {$(
+        impl InternKey for $t {
+            #[inline]
+            fn into_index(self) -> usize {
+                self.get() as usize - 1
+            }
+
+            #[inline]
+            fn try_from_index(i: usize) -> Option<Self> {
+                <$t>::new(<$int>::try_from(i.checked_add(1)?).ok()?)
+            }
+        }
+    )*};
+}
+
+non_zero_key!(NonZeroU16: u16, NonZeroU32: u32, NonZeroU64: u64, NonZeroUsize: usize);
+
+/// A [`Key`] represented by its index in allocation order. Created with [`Key::dense`].
+///
+/// Dense keys order the same way as keys, and support the arithmetic needed to step between keys.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct DenseKey(u32);
+
+impl DenseKey {
+    /// Get the index of this key.
+    #[inline]
+    pub fn index(self) -> u32 {
+        self.0
+    }
+
+    /// Get the key with this index.
+    #[inline]
+    pub fn key(self) -> Key {
+        // SAFETY: dense keys are only created from valid keys, which are less than `u32::MAX`.
+        unsafe { Key::new_unchecked(self.0) }
+    }
+
+    /// Get the key allocated immediately after this one, if there is one.
+    #[inline]
+    pub fn next(self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    /// Get the key allocated immediately before this one, if there is one.
+    #[inline]
+    pub fn prev(self) -> Option<Self> {
+        self.0.checked_sub(1).map(Self)
+    }
+
+    /// Get the key allocated `n` keys after this one, if there is one.
+    #[inline]
+    pub fn checked_add(self, n: u32) -> Option<Self> {
+        self.0.checked_add(n).filter(|&i| i < u32::MAX).map(Self)
+    }
+}
+
+impl From<Key> for DenseKey {
+    #[inline]
+    fn from(key: Key) -> Self {
+        key.dense()
+    }
+}
+
+impl From<DenseKey> for Key {
+    #[inline]
+    fn from(key: DenseKey) -> Self {
+        key.key()
+    }
+}
+
+/// A half-open range of keys, in allocation order.
+///
+/// This can be used to iterate over every key allocated during some phase, without manual
+/// arithmetic on the key representation.
+///
+/// ```
+/// use paracord_core::{Key, KeyRange};
+///
+/// let start = Key::try_from_repr(3).unwrap();
+/// let end = Key::try_from_repr(6).unwrap();
+///
+/// let range = KeyRange::new(start, end);
+/// assert_eq!(range.len(), 3);
+/// assert!(range.contains(start));
+/// assert!(!range.contains(end));
+///
+/// let keys: Vec<Key> = range.iter().collect();
+/// assert_eq!(keys.first(), Some(&start));
+/// assert_eq!(keys.last().unwrap().dense().next().unwrap().key(), end);
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct KeyRange {
+    start: u32,
+    end: u32,
+}
+
+impl KeyRange {
+    /// The keys from `start` up to, but not including, `end`.
+    ///
+    /// The range is empty if `end` is not greater than `start`.
+    #[inline]
+    pub fn new(start: Key, end: Key) -> Self {
+        let (start, end) = (start.into_repr(), end.into_repr());
+        Self {
+            start,
+            end: end.max(start),
+        }
+    }
+
+    /// The keys from `start` up to, and including, `end`.
+    ///
+    /// The range is empty if `end` is less than `start`.
+    #[inline]
+    pub fn inclusive(start: Key, end: Key) -> Self {
+        let (start, end) = (start.into_repr(), end.into_repr());
+        Self {
+            start,
+            // keys are less than `u32::MAX`, so this cannot overflow.
+            end: (end + 1).max(start),
+        }
+    }
+
+    /// The first key in the range, if it is not empty.
+    #[inline]
+    pub fn first(&self) -> Option<Key> {
+        self.iter().next()
+    }
+
+    /// The last key in the range, if it is not empty.
+    #[inline]
+    pub fn last(&self) -> Option<Key> {
+        self.iter().next_back()
+    }
+
+    /// The number of keys in the range.
+    #[inline]
+    pub fn len(&self) -> usize {
+        (self.end - self.start) as usize
+    }
+
+    /// Determine if the range contains no keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Determine if the key is in the range.
+    #[inline]
+    pub fn contains(&self, key: Key) -> bool {
+        (self.start..self.end).contains(&key.into_repr())
+    }
+
+    /// Iterate over every key in the range, in allocation order.
+    #[inline]
+    pub fn iter(&self) -> KeyRangeIter {
+        KeyRangeIter(self.start..self.end)
+    }
+}
+
+impl From<Range<Key>> for KeyRange {
+    #[inline]
+    fn from(range: Range<Key>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl IntoIterator for KeyRange {
+    type Item = Key;
+    type IntoIter = KeyRangeIter;
+
+    #[inline]
+    fn into_iter(self) -> KeyRangeIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &KeyRange {
+    type Item = Key;
+    type IntoIter = KeyRangeIter;
+
+    #[inline]
+    fn into_iter(self) -> KeyRangeIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the keys in a [`KeyRange`].
+#[derive(Clone, Debug)]
+pub struct KeyRangeIter(Range<u32>);
+
+impl Iterator for KeyRangeIter {
+    type Item = Key;
+
+    #[inline]
+    fn next(&mut self) -> Option<Key> {
+        // SAFETY: the range only contains indices of valid keys, which are less than `u32::MAX`.
+        self.0.next().map(|i| unsafe { Key::new_unchecked(i) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Key> {
+        // SAFETY: the range only contains indices of valid keys, which are less than `u32::MAX`.
+        self.0.nth(n).map(|i| unsafe { Key::new_unchecked(i) })
+    }
+}
+
+impl DoubleEndedIterator for KeyRangeIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Key> {
+        // SAFETY: the range only contains indices of valid keys, which are less than `u32::MAX`.
+        self.0.next_back().map(|i| unsafe { Key::new_unchecked(i) })
+    }
+}
+
+impl ExactSizeIterator for KeyRangeIter {}
+
+impl FusedIterator for KeyRangeIter {}
@@ -0,0 +1,247 @@
+//! Derive macros for the [paracord](https://docs.rs/paracord) string interner.
+//!
+//! These are re-exported by paracord when the `derive` feature is enabled.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, GenericParam, Path,
+    PathArguments, Type,
+};
+
+/// Derive a companion struct where every string field is interned.
+#[proc_macro_derive(InternFields, attributes(intern))]
+pub fn derive_intern_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    intern_fields(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// How a field is converted into the interned struct.
+enum Conversion {
+    /// `String` becomes `Key`.
+    Key,
+    /// `Option<String>` becomes `Option<Key>`.
+    Option,
+    /// `Vec<String>` becomes `Vec<Key>`.
+    Vec,
+    /// Anything else is cloned.
+    Clone,
+}
+
+fn intern_fields(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "InternFields can only be derived for structs",
+        ));
+    };
+
+    let mut derives = vec![];
+    for attr in &input.attrs {
+        if attr.path().is_ident("intern") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("derive") {
+                    meta.parse_nested_meta(|meta| {
+                        derives.push(meta.path);
+                        Ok(())
+                    })
+                } else {
+                    Err(meta.error("unsupported intern attribute, expected `derive(...)`"))
+                }
+            })?;
+        }
+    }
+
+    let mut field_defs = vec![];
+    let mut field_inits = vec![];
+    for (i, field) in data.fields.iter().enumerate() {
+        let mut skip = false;
+        for attr in &field.attrs {
+            if attr.path().is_ident("intern") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("skip") {
+                        skip = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported intern attribute, expected `skip`"))
+                    }
+                })?;
+            }
+        }
+
+        let conversion = if skip {
+            Conversion::Clone
+        } else {
+            classify(&field.ty)
+        };
+
+        let member = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = syn::Index::from(i);
+                quote!(#index)
+            }
+        };
+
+        let vis = &field.vis;
+        let ty = &field.ty;
+        let (ty, init) = match conversion {
+            Conversion::Key => (
+                quote!(::paracord::Key),
+                quote!(paracord.get_or_intern(&self.#member)),
+            ),
+            Conversion::Option => (
+                quote!(::core::option::Option<::paracord::Key>),
+                quote!(self.#member.as_deref().map(|s| paracord.get_or_intern(s))),
+            ),
+            Conversion::Vec => (
+                quote!(::std::vec::Vec<::paracord::Key>),
+                quote!(self.#member.iter().map(|s| paracord.get_or_intern(s)).collect()),
+            ),
+            Conversion::Clone => (
+                quote!(#ty),
+                quote!(::core::clone::Clone::clone(&self.#member)),
+            ),
+        };
+
+        match &field.ident {
+            Some(ident) => {
+                field_defs.push(quote!(#vis #ident: #ty));
+                field_inits.push(quote!(#ident: #init));
+            }
+            None => {
+                field_defs.push(quote!(#vis #ty));
+                field_inits.push(init);
+            }
+        }
+    }
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let interned = format_ident!("Interned{}", ident);
+
+    // string fields no longer borrow, so the interned struct has no lifetimes.
+    let mut interned_generics = input.generics.clone();
+    interned_generics.params = interned_generics
+        .params
+        .into_iter()
+        .filter(|p| !matches!(p, GenericParam::Lifetime(_)))
+        .collect();
+    let (_, interned_ty_generics, _) = interned_generics.split_for_impl();
+    let interned_where = &interned_generics.where_clause;
+    let interned_params = &interned_generics.params;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let doc = format!("Interned version of [`{ident}`], where every string field is a [`Key`](::paracord::Key).\n\nCreated by [`{ident}::intern_into`].");
+    let method_doc = format!("Intern every string field, returning an [`{interned}`].");
+
+    let derive_attr = (!derives.is_empty()).then(|| quote!(#[derive(#(#derives),*)]));
+
+    let body = match &data.fields {
+        Fields::Named(_) => quote!({ #(#field_defs,)* }),
+        Fields::Unnamed(_) => quote!(( #(#field_defs,)* ) #interned_where;),
+        Fields::Unit => quote!(;),
+    };
+    let body = match &data.fields {
+        Fields::Named(_) => quote!(#interned_where #body),
+        _ => body,
+    };
+    let init = match &data.fields {
+        Fields::Named(_) => quote!(#interned { #(#field_inits,)* }),
+        Fields::Unnamed(_) => quote!(#interned ( #(#field_inits,)* )),
+        Fields::Unit => quote!(#interned),
+    };
+
+    Ok(quote! {
+        #[doc = #doc]
+        #derive_attr
+        #vis struct #interned <#interned_params> #body
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc = #method_doc]
+            #vis fn intern_into<S: ::core::hash::BuildHasher>(
+                &self,
+                paracord: &::paracord::ParaCord<S>,
+            ) -> #interned #interned_ty_generics {
+                #init
+            }
+        }
+    })
+}
+
+fn classify(ty: &Type) -> Conversion {
+    if is_string(ty) {
+        return Conversion::Key;
+    }
+    if let Some(inner) = single_arg(ty, "Option") {
+        if is_string(inner) {
+            return Conversion::Option;
+        }
+    }
+    if let Some(inner) = single_arg(ty, "Vec") {
+        if is_string(inner) {
+            return Conversion::Vec;
+        }
+    }
+    Conversion::Clone
+}
+
+/// `String`, `&str`, `Box<str>` or `Cow<str>`.
+fn is_string(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(r) => is_str(&r.elem),
+        Type::Path(p) if p.qself.is_none() => {
+            last_ident_is(&p.path, "String")
+                || single_arg(ty, "Box").is_some_and(is_str)
+                || cow_arg(&p.path).is_some_and(is_str)
+        }
+        Type::Group(g) => is_string(&g.elem),
+        _ => false,
+    }
+}
+
+fn is_str(ty: &Type) -> bool {
+    matches!(ty, Type::Path(p) if p.qself.is_none() && p.path.is_ident("str"))
+}
+
+fn last_ident_is(path: &Path, name: &str) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|s| s.ident == name && s.arguments.is_none())
+}
+
+/// If the type is `Name<T>`, return `T`.
+fn single_arg<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    let Type::Path(p) = ty else { return None };
+    let segment = p.path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(t) if args.args.len() == 1 => Some(t),
+        _ => None,
+    }
+}
+
+/// If the path is `Cow<'a, T>`, return `T`.
+fn cow_arg(path: &Path) -> Option<&Type> {
+    let segment = path.segments.last()?;
+    if segment.ident != "Cow" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|a| match a {
+        GenericArgument::Type(t) => Some(t),
+        _ => None,
+    })
+}
@@ -0,0 +1,126 @@
+//! Zero-copy archives of [`ParaCord`] instances, with [`rkyv`].
+//!
+//! A [`StringTable`] stores every string along with a prebuilt hash table. Once archived, it can be
+//! memory mapped and used to look up and resolve keys directly, without deserializing the strings
+//! or rebuilding the hash table.
+//!
+//! ```
+//! use paracord::archive::{ArchivedStringTable, StringTable};
+//! use paracord::ParaCord;
+//!
+//! let paracord = ParaCord::default();
+//! let foo = paracord.get_or_intern("foo");
+//! let bar = paracord.get_or_intern("bar");
+//!
+//! let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&StringTable::from(&paracord)).unwrap();
+//!
+//! // could be a memory mapped file
+//! let archived = rkyv::access::<ArchivedStringTable, rkyv::rancor::Error>(&bytes).unwrap();
+//! assert_eq!(archived.get("foo"), Some(foo));
+//! assert_eq!(archived.try_resolve(bar), Some("bar"));
+//! assert_eq!(archived.len(), 2);
+//! ```
+//!
+//! [`Key`] also implements the `rkyv` traits, archived as [`ArchivedKey`](crate::ArchivedKey).
+
+use std::collections::HashMap;
+
+use crate::{Key, ParaCord};
+
+/// A snapshot of the strings in a [`ParaCord`], to be archived with [`rkyv`].
+///
+/// Every key has the same value in the archive as it had in the instance it was created from.
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize,
+)]
+pub struct StringTable {
+    /// The string for every key, in key order. Removed keys are `None`.
+    strings: Vec<Option<String>>,
+    /// The index of every string in `strings`.
+    index: HashMap<String, u32>,
+}
+
+impl<S> From<&ParaCord<S>> for StringTable {
+    /// Copy every string out of the instance.
+    ///
+    /// Strings interned concurrently might not be included.
+    fn from(paracord: &ParaCord<S>) -> Self {
+        let strings: Vec<Option<String>> = paracord
+            .inner
+            .slots(0)
+            // Safety: we insert only strings, so it's valid utf8
+            .map(|s| s.map(|b| unsafe { core::str::from_utf8_unchecked(b) }.to_owned()))
+            .collect();
+        let index = strings
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| Some((s.clone()?, i as u32)))
+            .collect();
+        Self { strings, index }
+    }
+}
+
+impl<S: core::hash::BuildHasher + Default> From<&StringTable> for ParaCord<S> {
+    /// Load the strings into a new instance, keeping their keys.
+    fn from(table: &StringTable) -> Self {
+        let mut this = Self::with_hasher(S::default());
+        for s in &table.strings {
+            match s {
+                Some(s) => this.inner.push_mut(s.as_bytes()),
+                None => this.inner.push_tombstone_mut(),
+            };
+        }
+        this
+    }
+}
+
+impl ArchivedStringTable {
+    /// Try and get the [`Key`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        let i = self.index.get(s)?;
+        Some(Key::from_index(i.to_native() as usize))
+    }
+
+    /// Try and resolve the string associated with this [`Key`].
+    ///
+    /// Returns [`None`] if the key was not in the archived instance, or was removed.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&str> {
+        let s = self.strings.get(key.into_repr() as usize)?.as_ref()?;
+        Some(s.as_str())
+    }
+
+    /// Resolve the string associated with this [`Key`].
+    ///
+    /// # Panics
+    ///
+    /// If the key was not in the archived instance, or was removed.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &str {
+        self.try_resolve(key)
+            .expect("key should be allocated by the archived paracord instance")
+    }
+
+    /// Determine how many strings are in the archive.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Determine if the archive contains no strings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Get an iterator over every ([`Key`], `&str`) pair in the archive.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &str)> {
+        self.strings
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| Some((Key::from_index(i), s.as_ref()?.as_str())))
+    }
+}
@@ -0,0 +1,134 @@
+//! Interning of bit-level data from [`bitvec`].
+//!
+//! See [`ParaCord`].
+
+use std::hash::BuildHasher;
+
+use bitvec::order::{BitOrder, Lsb0};
+use bitvec::slice::BitSlice;
+use bitvec::store::BitStore;
+use bitvec::vec::BitVec;
+use bitvec::view::BitView;
+
+use crate::{slice, Key};
+
+/// The number of header bytes that store the length in bits.
+const HEADER: usize = 4;
+
+/// An interner for bit slices, which keeps the exact length in bits.
+///
+/// Bit slices are packed into bytes, with any padding bits cleared, and prefixed with their length
+/// in bits. Two bit slices get the same key if and only if they contain the same sequence of bits,
+/// regardless of their storage type or bit order.
+///
+/// # Examples
+///
+/// ```
+/// use bitvec::prelude::*;
+/// use paracord::bits::ParaCord;
+///
+/// let paracord = ParaCord::default();
+///
+/// let a = paracord.get_or_intern(bits![1, 0, 1]);
+/// let b = paracord.get_or_intern(bits![1, 0, 1, 0]);
+/// assert_ne!(a, b);
+/// assert_eq!(paracord.get(bits![u16, Msb0; 1, 0, 1]), Some(a));
+///
+/// assert_eq!(paracord.resolve(a), bits![1, 0, 1]);
+/// assert_eq!(paracord.resolve(b).len(), 4);
+/// ```
+pub struct ParaCord<S = foldhash::fast::RandomState> {
+    bytes: slice::ParaCord<u8, S>,
+}
+
+impl<S> std::fmt::Debug for ParaCord<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl Default for ParaCord {
+    fn default() -> Self {
+        Self::with_hasher(foldhash::fast::RandomState::default())
+    }
+}
+
+impl<S: BuildHasher> ParaCord<S> {
+    /// Create a new `ParaCord` instance with the given hasher state.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            bytes: slice::ParaCord::with_hasher(hasher),
+        }
+    }
+
+    /// Try and get the [`Key`] associated with the given bits.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get<T: BitStore, O: BitOrder>(&self, bits: &BitSlice<T, O>) -> Option<Key> {
+        self.bytes.get(&pack(bits))
+    }
+
+    /// Try and get the [`Key`] associated with the given bits.
+    /// Allocates a new key if not found.
+    pub fn get_or_intern<T: BitStore, O: BitOrder>(&self, bits: &BitSlice<T, O>) -> Key {
+        self.bytes.get_or_intern(&pack(bits))
+    }
+}
+
+impl<S> ParaCord<S> {
+    /// Try and resolve the bits associated with this [`Key`].
+    ///
+    /// This can only return `None` if given a key that was allocated from
+    /// a different [`ParaCord`] instance, but it might return arbitrary bits as well.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&BitSlice<u8, Lsb0>> {
+        self.bytes.try_resolve(key).map(unpack)
+    }
+
+    /// Resolve the bits associated with this [`Key`].
+    ///
+    /// # Panics
+    /// This can panic if given a key that was allocated from
+    /// a different [`ParaCord`] instance, but it might return arbitrary bits
+    /// as well.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &BitSlice<u8, Lsb0> {
+        unpack(self.bytes.resolve(key))
+    }
+
+    /// Determine how many bit slices have been allocated
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Determine if no bit slices have been allocated
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Get an iterator over every ([`Key`], `&BitSlice`) pair
+    /// that has been allocated in this [`ParaCord`] instance.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &BitSlice<u8, Lsb0>)> {
+        self.bytes.iter().map(|(k, b)| (k, unpack(b)))
+    }
+}
+
+/// Pack the bits into bytes, prefixed with the length in bits.
+fn pack<T: BitStore, O: BitOrder>(bits: &BitSlice<T, O>) -> Vec<u8> {
+    let len = u32::try_from(bits.len()).expect("bit slice lengths must be less than 2^32");
+
+    let mut packed = BitVec::<u8, Lsb0>::with_capacity(HEADER * 8 + bits.len());
+    packed.extend_from_bitslice(len.to_le_bytes().view_bits::<Lsb0>());
+    packed.extend(bits.iter().by_vals());
+    packed.set_uninitialized(false);
+    packed.into_vec()
+}
+
+fn unpack(bytes: &[u8]) -> &BitSlice<u8, Lsb0> {
+    let (len, bits) = bytes.split_at(HEADER);
+    let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+    &bits.view_bits::<Lsb0>()[..len]
+}
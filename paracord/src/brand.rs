@@ -0,0 +1,197 @@
+//! Keys branded with a unique lifetime, so they can only be used with the instance that
+//! allocated them.
+//!
+//! See [`ParaCord::scope`].
+
+use core::fmt;
+use core::marker::PhantomData;
+use std::hash::BuildHasher;
+
+use crate::{Key, ParaCord};
+
+/// An invariant lifetime, which is unique to each call of [`ParaCord::scope`].
+type Brand<'brand> = PhantomData<fn(&'brand ()) -> &'brand ()>;
+
+/// A [`Key`] branded with the lifetime of a [`ParaCord::scope`].
+///
+/// Branded keys cannot outlive the scope, and cannot be used with any other scope,
+/// so they always resolve to the string they were allocated for.
+pub struct BrandedKey<'brand> {
+    key: Key,
+    _brand: Brand<'brand>,
+}
+
+impl<'brand> BrandedKey<'brand> {
+    /// Get the unbranded key.
+    #[inline]
+    #[must_use]
+    pub fn key(self) -> Key {
+        self.key
+    }
+}
+
+impl Clone for BrandedKey<'_> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl Copy for BrandedKey<'_> {}
+
+impl PartialEq for BrandedKey<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for BrandedKey<'_> {}
+
+impl PartialOrd for BrandedKey<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BrandedKey<'_> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl core::hash::Hash for BrandedKey<'_> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+impl fmt::Debug for BrandedKey<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BrandedKey")
+            .field(&self.key.into_repr())
+            .finish()
+    }
+}
+
+/// A handle to a [`ParaCord`] that allocates [`BrandedKey`]s. Created by [`ParaCord::scope`].
+pub struct BrandedParaCord<'a, 'brand, S> {
+    paracord: &'a ParaCord<S>,
+    _brand: Brand<'brand>,
+}
+
+impl<S> Clone for BrandedParaCord<'_, '_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for BrandedParaCord<'_, '_, S> {}
+
+impl<S> fmt::Debug for BrandedParaCord<'_, '_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.paracord.fmt(f)
+    }
+}
+
+impl<S> ParaCord<S> {
+    /// Run `f` with a handle to this instance that allocates keys branded with a unique lifetime.
+    ///
+    /// The branded keys cannot escape `f`, and cannot be mixed up with keys from any other scope,
+    /// even of the same instance, so resolving them can never fail or return the wrong string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let len = paracord.scope(|cord| {
+    ///     let foo = cord.get_or_intern("foo");
+    ///     assert_eq!(cord.get("foo"), Some(foo));
+    ///     cord.resolve(foo).len()
+    /// });
+    /// assert_eq!(len, 3);
+    /// ```
+    ///
+    /// Keys cannot be used with another scope:
+    ///
+    /// ```compile_fail
+    /// use paracord::ParaCord;
+    /// let a = ParaCord::default();
+    /// let b = ParaCord::default();
+    ///
+    /// a.scope(|a| {
+    ///     let foo = a.get_or_intern("foo");
+    ///     b.scope(|b| b.resolve(foo).len())
+    /// });
+    /// ```
+    ///
+    /// Or escape the scope:
+    ///
+    /// ```compile_fail
+    /// use paracord::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let foo = paracord.scope(|cord| cord.get_or_intern("foo"));
+    /// ```
+    pub fn scope<R>(&self, f: impl for<'brand> FnOnce(BrandedParaCord<'_, 'brand, S>) -> R) -> R {
+        f(BrandedParaCord {
+            paracord: self,
+            _brand: PhantomData,
+        })
+    }
+}
+
+impl<'a, 'brand, S> BrandedParaCord<'a, 'brand, S> {
+    fn brand(key: Key) -> BrandedKey<'brand> {
+        BrandedKey {
+            key,
+            _brand: PhantomData,
+        }
+    }
+
+    /// Resolve the string associated with this key.
+    ///
+    /// This cannot fail, since the key was allocated by this instance, which cannot be
+    /// cleared or swept while it is borrowed.
+    #[must_use]
+    pub fn resolve(&self, key: BrandedKey<'brand>) -> &'a str {
+        self.paracord.resolve(key.key)
+    }
+
+    /// Brand a key allocated by this instance outside of the scope.
+    ///
+    /// Returns [`None`] if the key does not resolve in this instance. This can't detect keys that
+    /// were allocated by other instances but happen to resolve, so they must be checked some
+    /// other way, eg with [`SendKey`](crate::SendKey).
+    #[must_use]
+    pub fn brand_key(&self, key: Key) -> Option<BrandedKey<'brand>> {
+        self.paracord.try_resolve(key)?;
+        Some(Self::brand(key))
+    }
+
+    /// Get the underlying instance.
+    #[must_use]
+    pub fn paracord(&self) -> &'a ParaCord<S> {
+        self.paracord
+    }
+
+    /// Get an iterator over every ([`BrandedKey`], `&str`) pair in this instance.
+    pub fn iter(&self) -> impl Iterator<Item = (BrandedKey<'brand>, &'a str)> {
+        self.paracord.iter().map(|(key, s)| (Self::brand(key), s))
+    }
+}
+
+impl<'a, 'brand, S: BuildHasher> BrandedParaCord<'a, 'brand, S> {
+    /// Try and get the [`BrandedKey`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<BrandedKey<'brand>> {
+        self.paracord.get(s).map(Self::brand)
+    }
+
+    /// Try and get the [`BrandedKey`] associated with the given string.
+    /// Allocates a new key if not found.
+    pub fn get_or_intern(&self, s: &str) -> BrandedKey<'brand> {
+        Self::brand(self.paracord.get_or_intern(s))
+    }
+}
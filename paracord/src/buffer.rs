@@ -0,0 +1,35 @@
+//! A reusable thread-local buffer, for joining, formatting or encoding values before they are interned.
+
+use core::cell::Cell;
+
+/// Buffers larger than this are not kept for reuse.
+const MAX_BUFFER: usize = 4096;
+
+thread_local! {
+    static BUFFER: Cell<Vec<u8>> = const { Cell::new(Vec::new()) };
+}
+
+/// Take the buffer of this thread. It is empty if it is already taken, eg by a hasher or
+/// [`Display`](core::fmt::Display) implementation that calls back into the interner.
+pub(crate) fn take() -> Vec<u8> {
+    // the thread-local might already be destroyed during thread exit.
+    BUFFER.try_with(Cell::take).unwrap_or_default()
+}
+
+/// Return a buffer from [`take`], to be reused by this thread.
+pub(crate) fn give(mut buf: Vec<u8>) {
+    if buf.capacity() <= MAX_BUFFER {
+        buf.clear();
+        let _ = BUFFER.try_with(|cache| cache.set(buf));
+    }
+}
+
+/// Like [`take`], as a [`String`].
+pub(crate) fn take_string() -> String {
+    String::from_utf8(take()).unwrap_or_default()
+}
+
+/// Like [`give`], for a [`String`] from [`take_string`].
+pub(crate) fn give_string(buf: String) {
+    give(buf.into_bytes());
+}
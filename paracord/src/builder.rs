@@ -0,0 +1,139 @@
+//! Building strings piece by piece in a reusable buffer, and interning them.
+//!
+//! See [`ParaCord::string_builder`].
+
+use core::fmt;
+use std::hash::BuildHasher;
+
+use foldhash::fast::RandomState;
+
+use crate::{Key, ParaCord};
+
+/// Builds a string in a reusable buffer, then interns it. Created with
+/// [`ParaCord::string_builder`].
+///
+/// The buffer is taken from a thread-local cache when the builder is created, and returned to it
+/// when the builder is dropped, so building many strings doesn't allocate once the buffer has grown
+/// large enough. [`finish`](InternStringBuilder::finish) clears the buffer, so one builder can be
+/// used for many strings.
+pub struct InternStringBuilder<'a, S = RandomState> {
+    paracord: &'a ParaCord<S>,
+    buf: String,
+}
+
+impl<S> fmt::Debug for InternStringBuilder<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("InternStringBuilder")
+            .field(&self.buf)
+            .finish()
+    }
+}
+
+impl<S> Drop for InternStringBuilder<'_, S> {
+    fn drop(&mut self) {
+        crate::buffer::give_string(core::mem::take(&mut self.buf));
+    }
+}
+
+impl<S> ParaCord<S> {
+    /// Get an [`InternStringBuilder`], for building strings piece by piece before interning them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let mut builder = paracord.string_builder();
+    /// let keys: Vec<_> = ["foo", "bar", "foo"]
+    ///     .into_iter()
+    ///     .map(|name| {
+    ///         builder.push_str("user.");
+    ///         builder.push_str(name);
+    ///         builder.finish()
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(paracord.resolve(keys[0]), "user.foo");
+    /// assert_eq!(paracord.resolve(keys[1]), "user.bar");
+    /// assert_eq!(keys[0], keys[2]);
+    /// ```
+    #[must_use]
+    pub fn string_builder(&self) -> InternStringBuilder<'_, S> {
+        InternStringBuilder {
+            paracord: self,
+            buf: crate::buffer::take_string(),
+        }
+    }
+}
+
+impl<'a, S> InternStringBuilder<'a, S> {
+    /// Get the [`ParaCord`] the strings are interned into.
+    #[must_use]
+    pub fn paracord(&self) -> &'a ParaCord<S> {
+        self.paracord
+    }
+
+    /// Append a string slice.
+    pub fn push_str(&mut self, s: &str) {
+        self.buf.push_str(s);
+    }
+
+    /// Append a [`char`].
+    pub fn push(&mut self, c: char) {
+        self.buf.push(c);
+    }
+
+    /// Get the string built so far.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Determine the length of the string built so far, in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Determine if nothing has been appended since the builder was created or last finished.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Discard the string built so far, keeping the buffer.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+}
+
+impl<S: BuildHasher> InternStringBuilder<'_, S> {
+    /// Try and get the [`Key`] associated with the string built so far.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self) -> Option<Key> {
+        self.paracord.get(&self.buf)
+    }
+
+    /// Intern the string built so far, and clear the buffer to build the next string.
+    ///
+    /// If the string is already interned, this only does one lookup and doesn't allocate.
+    pub fn finish(&mut self) -> Key {
+        let key = self.paracord.get_or_intern(&self.buf);
+        self.buf.clear();
+        key
+    }
+}
+
+impl<S> fmt::Write for InternStringBuilder<'_, S> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use core::fmt;
+use core::str::Utf8Error;
+use std::hash::BuildHasher;
+
+use crate::{slice, Key, ParaCord};
+
+/// A read-only view of a [`ParaCord`] as bytes. Created with [`ParaCord::as_bytes`].
+///
+/// The view shares the keys and storage of the instance, so no strings are copied. It can't
+/// intern new byte slices, since they might not be valid UTF-8. To intern bytes, convert the
+/// instance with [`ParaCord::into_bytes`].
+pub struct BytesView<'a, S> {
+    inner: &'a slice::ParaCord<u8, S>,
+}
+
+impl<S> Clone for BytesView<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for BytesView<'_, S> {}
+
+impl<S> fmt::Debug for BytesView<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<'a, S> BytesView<'a, S> {
+    /// Try and resolve the bytes associated with this [`Key`].
+    ///
+    /// See [`ParaCord::try_resolve`].
+    #[must_use]
+    #[track_caller]
+    pub fn try_resolve(&self, key: Key) -> Option<&'a [u8]> {
+        self.inner.try_resolve(key)
+    }
+
+    /// Resolve the bytes associated with this [`Key`].
+    ///
+    /// # Panics
+    /// See [`ParaCord::resolve`].
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &'a [u8] {
+        self.inner.resolve(key)
+    }
+
+    /// Determine how many strings have been allocated.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Determine if no strings have been allocated.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Get an iterator over every ([`Key`], `&[u8]`) pair in the instance.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &'a [u8])> {
+        self.inner.iter()
+    }
+}
+
+impl<S: BuildHasher> BytesView<'_, S> {
+    /// Try and get the [`Key`] associated with the given bytes.
+    /// Returns [`None`] if not found.
+    ///
+    /// Since only valid UTF-8 is interned, this never finds invalid UTF-8.
+    #[must_use]
+    pub fn get(&self, s: &[u8]) -> Option<Key> {
+        self.inner.get(s)
+    }
+}
+
+/// Returned by [`ParaCord::try_from_bytes`] when some of the bytes are not valid UTF-8.
+pub struct FromBytesError<S> {
+    paracord: Box<slice::ParaCord<u8, S>>,
+    key: Key,
+    error: Utf8Error,
+}
+
+impl<S> FromBytesError<S> {
+    /// Get the first key whose bytes are not valid UTF-8.
+    #[must_use]
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Get the error from validating the bytes of [`FromBytesError::key`].
+    #[must_use]
+    pub fn utf8_error(&self) -> Utf8Error {
+        self.error
+    }
+
+    /// Get back the instance that failed to convert.
+    #[must_use]
+    pub fn into_bytes(self) -> slice::ParaCord<u8, S> {
+        *self.paracord
+    }
+}
+
+impl<S> fmt::Debug for FromBytesError<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FromBytesError")
+            .field("key", &self.key)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> fmt::Display for FromBytesError<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bytes for key {:?} are not valid utf8: {}",
+            self.key, self.error
+        )
+    }
+}
+
+impl<S> std::error::Error for FromBytesError<S> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<S> ParaCord<S> {
+    /// View this instance as bytes, sharing the same keys and storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let foo = paracord.get_or_intern("foo");
+    /// let bytes = paracord.as_bytes();
+    /// assert_eq!(bytes.get(b"foo"), Some(foo));
+    /// assert_eq!(bytes.resolve(foo), b"foo");
+    /// ```
+    #[must_use]
+    pub fn as_bytes(&self) -> BytesView<'_, S> {
+        BytesView { inner: &self.inner }
+    }
+
+    /// Convert this instance into a byte slice interner, keeping the same keys and storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::ParaCord;
+    /// let paracord = ParaCord::default();
+    /// let foo = paracord.get_or_intern("foo");
+    ///
+    /// let bytes = paracord.into_bytes();
+    /// assert_eq!(bytes.resolve(foo), b"foo");
+    /// bytes.get_or_intern(b"\xff");
+    /// ```
+    #[must_use]
+    pub fn into_bytes(self) -> slice::ParaCord<u8, S> {
+        self.inner
+    }
+
+    /// Convert a byte slice interner into a string interner, keeping the same keys and storage.
+    ///
+    /// Every byte slice is checked to be valid UTF-8, but nothing is copied.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FromBytesError`] with the first key that is not valid UTF-8, which can
+    /// give back the byte slice interner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::{slice, ParaCord};
+    /// let bytes = slice::ParaCord::default();
+    /// let foo = bytes.get_or_intern(b"foo");
+    ///
+    /// let paracord = ParaCord::try_from_bytes(bytes).unwrap();
+    /// assert_eq!(paracord.resolve(foo), "foo");
+    ///
+    /// let bytes = paracord.into_bytes();
+    /// let invalid = bytes.get_or_intern(b"\xff");
+    /// let err = ParaCord::try_from_bytes(bytes).unwrap_err();
+    /// assert_eq!(err.key(), invalid);
+    /// ```
+    pub fn try_from_bytes(bytes: slice::ParaCord<u8, S>) -> Result<Self, FromBytesError<S>> {
+        let invalid = bytes
+            .iter()
+            .find_map(|(key, s)| Some((key, core::str::from_utf8(s).err()?)));
+        match invalid {
+            None => Ok(Self {
+                inner: bytes,
+                parsed: Default::default(),
+            }),
+            Some((key, error)) => Err(FromBytesError {
+                paracord: Box::new(bytes),
+                key,
+                error,
+            }),
+        }
+    }
+}
+
+impl<S> TryFrom<slice::ParaCord<u8, S>> for ParaCord<S> {
+    type Error = FromBytesError<S>;
+
+    fn try_from(bytes: slice::ParaCord<u8, S>) -> Result<Self, Self::Error> {
+        Self::try_from_bytes(bytes)
+    }
+}
+
+impl<S> From<ParaCord<S>> for slice::ParaCord<u8, S> {
+    fn from(paracord: ParaCord<S>) -> Self {
+        paracord.into_bytes()
+    }
+}
@@ -0,0 +1,114 @@
+//! A stable, specified hashing mode, so implementations in other languages can build the same
+//! dictionary and exchange raw keys with a [`ParaCord`].
+//!
+//! ```
+//! use paracord::canonical::{self, CanonicalParaCord, CanonicalState};
+//!
+//! let paracord = CanonicalParaCord::with_hasher(CanonicalState);
+//! paracord.get_or_intern("foo");
+//! paracord.get_or_intern("bar");
+//!
+//! // computed by another implementation that interned the same strings in the same order.
+//! let expected = {
+//!     let other = CanonicalParaCord::with_hasher(CanonicalState);
+//!     other.get_or_intern("foo");
+//!     other.get_or_intern("bar");
+//!     other.canonical_digest()
+//! };
+//! assert_eq!(paracord.canonical_digest(), expected);
+//! ```
+//!
+//! # Specification
+//!
+//! Keys are allocated densely in the order strings are first interned, starting at `0`, and the
+//! raw key is [`Key::into_repr`](crate::Key::into_repr). The hash and shard routing do not affect
+//! which key a string is given, so any implementation that interns the same strings in the same
+//! order has the same keys.
+//!
+//! The hash of a string `s` is XXH3-64 with seed [`SEED`] over the length of `s` in bytes as
+//! 8 little endian bytes, followed by the bytes of `s`. See [`hash`]. For example, the hash of
+//! `"foo"` is `8445251776033600215`.
+//!
+//! The digest of a dictionary is XXH3-64 with seed [`SEED`] over every key in order, from `0` to
+//! the number of keys, where each key is written as a `0` byte if it was removed, or otherwise as a
+//! `1` byte, then the length of the string as 8 little endian bytes, then the bytes of the string.
+//! See [`ParaCord::canonical_digest`].
+
+use core::hash::{BuildHasher, Hasher};
+
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::ParaCord;
+
+/// The seed used for every canonical hash: the bytes of `"paracord"` as a big endian integer.
+pub const SEED: u64 = u64::from_be_bytes(*b"paracord");
+
+/// A [`ParaCord`] that hashes with [`CanonicalState`].
+pub type CanonicalParaCord = ParaCord<CanonicalState>;
+
+/// Computes the canonical hash of a string. See the [module docs](self#specification).
+#[must_use]
+pub fn hash(s: &[u8]) -> u64 {
+    CanonicalState.hash_one(s)
+}
+
+/// A [`BuildHasher`] that produces the same hashes on every platform and in every process.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CanonicalState;
+
+impl BuildHasher for CanonicalState {
+    type Hasher = CanonicalHasher;
+
+    fn build_hasher(&self) -> CanonicalHasher {
+        CanonicalHasher(Xxh3::with_seed(SEED))
+    }
+}
+
+/// The [`Hasher`] created by [`CanonicalState`].
+///
+/// Lengths are written as 8 little endian bytes, regardless of the platform's pointer width
+/// and endianness.
+#[derive(Clone)]
+pub struct CanonicalHasher(Xxh3);
+
+impl core::fmt::Debug for CanonicalHasher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("CanonicalHasher").finish()
+    }
+}
+
+impl Hasher for CanonicalHasher {
+    fn finish(&self) -> u64 {
+        self.0.digest()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write(&(i as u64).to_le_bytes());
+    }
+}
+
+impl<S> ParaCord<S> {
+    /// Compute a digest of every key and string in this instance, which is stable across
+    /// platforms, processes and hashers. See the [module docs](crate::canonical#specification).
+    ///
+    /// Strings interned concurrently may not be included.
+    #[must_use]
+    pub fn canonical_digest(&self) -> u64 {
+        let mut hasher = Xxh3::with_seed(SEED);
+        for s in self.inner.slots(0) {
+            match s {
+                None => hasher.update(&[0]),
+                Some(s) => {
+                    hasher.update(&[1]);
+                    hasher.update(&(s.len() as u64).to_le_bytes());
+                    hasher.update(s);
+                }
+            }
+        }
+        hasher.digest()
+    }
+}
@@ -0,0 +1,144 @@
+//! Read-through interning over an external source of strings.
+//!
+//! See [`ParaCord::with_fallback`].
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use foldhash::fast::RandomState;
+
+use crate::{Key, ParaCord};
+
+type Flight = Arc<OnceLock<Option<Key>>>;
+
+/// A [`ParaCord`] that populates misses from a loader function.
+///
+/// Created with [`ParaCord::with_fallback`].
+///
+/// When [`ResolverChain::get`] misses the interner, the loader is called with the string.
+/// If the loader returns `Some`, the returned string is interned and its key returned.
+/// Concurrent lookups of the same missing string share a single call to the loader.
+///
+/// Misses from the loader are not cached, so looking up the same missing string again
+/// will call the loader again. Similarly, if the loader returns a different string than
+/// the one looked up (eg a canonical form), only the returned string is interned.
+pub struct ResolverChain<F, S = RandomState> {
+    paracord: ParaCord<S>,
+    loader: F,
+    in_flight: Mutex<HashMap<Box<str>, Flight, RandomState>>,
+}
+
+impl<F, S> std::fmt::Debug for ResolverChain<F, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResolverChain")
+            .field("paracord", &self.paracord)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: BuildHasher> ParaCord<S> {
+    /// Wrap this interner with a loader, which is consulted on [`get`](ResolverChain::get) misses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::ParaCord;
+    ///
+    /// let known = ["foo", "bar"];
+    /// let chain = ParaCord::default().with_fallback(|s: &str| {
+    ///     known.contains(&s).then(|| s.to_owned())
+    /// });
+    ///
+    /// assert!(chain.paracord().get("foo").is_none());
+    ///
+    /// let foo = chain.get("foo").unwrap();
+    /// assert_eq!(chain.paracord().get("foo"), Some(foo));
+    /// assert_eq!(chain.resolve(foo), "foo");
+    ///
+    /// assert!(chain.get("baz").is_none());
+    /// ```
+    pub fn with_fallback<F>(self, loader: F) -> ResolverChain<F, S>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        ResolverChain {
+            paracord: self,
+            loader,
+            in_flight: Mutex::default(),
+        }
+    }
+}
+
+impl<F, S> ResolverChain<F, S> {
+    /// Get the underlying [`ParaCord`].
+    pub fn paracord(&self) -> &ParaCord<S> {
+        &self.paracord
+    }
+
+    /// Remove the loader, returning the underlying [`ParaCord`].
+    pub fn into_inner(self) -> ParaCord<S> {
+        self.paracord
+    }
+
+    /// Resolve the string associated with this key.
+    ///
+    /// # Panics
+    ///
+    /// See [`ParaCord::resolve`].
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &str {
+        self.paracord.resolve(key)
+    }
+}
+
+impl<F, S> ResolverChain<F, S>
+where
+    F: Fn(&str) -> Option<String>,
+    S: BuildHasher,
+{
+    /// Get the key for the string, calling the loader if it has not been interned yet.
+    ///
+    /// Returns [`None`] if the string is not interned and the loader returns [`None`].
+    pub fn get(&self, s: &str) -> Option<Key> {
+        match self.paracord.get(s) {
+            Some(key) => Some(key),
+            None => self.load(s),
+        }
+    }
+
+    /// Get the key for the string, calling the loader if it has not been interned yet.
+    ///
+    /// If the loader returns [`None`], the string is interned as is.
+    pub fn get_or_intern(&self, s: &str) -> Key {
+        match self.get(s) {
+            Some(key) => key,
+            None => self.paracord.get_or_intern(s),
+        }
+    }
+
+    #[cold]
+    fn load(&self, s: &str) -> Option<Key> {
+        let flight = {
+            let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+            in_flight.entry(s.into()).or_default().clone()
+        };
+
+        let key = *flight.get_or_init(|| {
+            // someone else may have finished loading between our miss and joining the flight.
+            if let Some(key) = self.paracord.get(s) {
+                return Some(key);
+            }
+            let loaded = (self.loader)(s)?;
+            Some(self.paracord.get_or_intern(&loaded))
+        });
+
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        if in_flight.get(s).is_some_and(|f| Arc::ptr_eq(f, &flight)) {
+            in_flight.remove(s);
+        }
+
+        key
+    }
+}
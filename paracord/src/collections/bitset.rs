@@ -0,0 +1,231 @@
+use core::ops::{BitAnd, BitOr, Sub};
+
+use crate::{Key, ParaCord};
+
+/// Another name for [`KeyBitSet`].
+pub type KeySet = KeyBitSet;
+
+/// A compact set of [`Key`]s, stored as a bitset indexed by the key.
+///
+/// Since keys are allocated densely, this uses 1 bit of memory per key allocated
+/// by the [`ParaCord`](crate::ParaCord) instance.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::collections::KeyBitSet;
+/// use paracord::ParaCord;
+///
+/// let paracord = ParaCord::default();
+/// let foo = paracord.get_or_intern("foo");
+/// let bar = paracord.get_or_intern("bar");
+///
+/// let mut set = KeyBitSet::new();
+/// assert!(set.insert(foo));
+/// assert!(!set.insert(foo));
+///
+/// assert!(set.contains(foo));
+/// assert!(!set.contains(bar));
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct KeyBitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl core::fmt::Debug for KeyBitSet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[inline]
+fn split(key: Key) -> (usize, u64) {
+    let i = key.into_repr() as usize;
+    (i / 64, 1 << (i % 64))
+}
+
+impl KeyBitSet {
+    /// Create a new empty set.
+    pub const fn new() -> Self {
+        Self {
+            words: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Insert the key into the set. Returns `true` if the key was not already present.
+    pub fn insert(&mut self, key: Key) -> bool {
+        let (word, bit) = split(key);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+
+        let inserted = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        self.len += inserted as usize;
+        inserted
+    }
+
+    /// Remove the key from the set. Returns `true` if the key was present.
+    pub fn remove(&mut self, key: Key) -> bool {
+        let (word, bit) = split(key);
+        let Some(w) = self.words.get_mut(word) else {
+            return false;
+        };
+
+        let removed = *w & bit != 0;
+        *w &= !bit;
+        self.len -= removed as usize;
+        removed
+    }
+
+    /// Determine if the key is in the set.
+    #[must_use]
+    pub fn contains(&self, key: Key) -> bool {
+        let (word, bit) = split(key);
+        self.words.get(word).is_some_and(|w| w & bit != 0)
+    }
+
+    /// Determine how many keys are in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Determine if the set is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all keys from the set.
+    pub fn clear(&mut self) {
+        self.words.clear();
+        self.len = 0;
+    }
+
+    /// Get an iterator over every key in the set, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = Key> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            core::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros();
+                word &= word - 1;
+                Some(Key::from_index(i * 64 + bit as usize))
+            })
+        })
+    }
+}
+
+impl KeyBitSet {
+    fn recount(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+        self.len = self.words.iter().map(|w| w.count_ones() as usize).sum();
+    }
+
+    /// Insert every key in `other` into this set.
+    pub fn union_with(&mut self, other: &Self) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a |= b);
+        self.recount();
+    }
+
+    /// Remove every key that is not in `other` from this set.
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.words.truncate(other.words.len());
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a &= b);
+        self.recount();
+    }
+
+    /// Remove every key in `other` from this set.
+    pub fn difference_with(&mut self, other: &Self) {
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a &= !b);
+        self.recount();
+    }
+
+    /// Determine if every key in this set is also in `other`.
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .enumerate()
+            .all(|(i, a)| a & !other.words.get(i).copied().unwrap_or(0) == 0)
+    }
+
+    /// Get an iterator over every key in the set along with its string, in key order.
+    ///
+    /// Keys that the instance cannot resolve, eg because they were removed, are skipped.
+    pub fn iter_resolved<'a, S>(
+        &'a self,
+        paracord: &'a ParaCord<S>,
+    ) -> impl Iterator<Item = (Key, &'a str)> + 'a {
+        self.iter()
+            .filter_map(|key| Some((key, paracord.try_resolve(key)?)))
+    }
+}
+
+impl BitOr for &KeyBitSet {
+    type Output = KeyBitSet;
+
+    /// The union of the two sets.
+    fn bitor(self, rhs: Self) -> KeyBitSet {
+        let mut set = self.clone();
+        set.union_with(rhs);
+        set
+    }
+}
+
+impl BitAnd for &KeyBitSet {
+    type Output = KeyBitSet;
+
+    /// The intersection of the two sets.
+    fn bitand(self, rhs: Self) -> KeyBitSet {
+        let mut set = self.clone();
+        set.intersect_with(rhs);
+        set
+    }
+}
+
+impl Sub for &KeyBitSet {
+    type Output = KeyBitSet;
+
+    /// The keys in the left set that are not in the right set.
+    fn sub(self, rhs: Self) -> KeyBitSet {
+        let mut set = self.clone();
+        set.difference_with(rhs);
+        set
+    }
+}
+
+impl Extend<Key> for KeyBitSet {
+    fn extend<I: IntoIterator<Item = Key>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|key| {
+            self.insert(key);
+        });
+    }
+}
+
+impl FromIterator<Key> for KeyBitSet {
+    fn from_iter<I: IntoIterator<Item = Key>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
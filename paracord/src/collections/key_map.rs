@@ -0,0 +1,240 @@
+use core::ops::{Index, IndexMut};
+
+use crate::{Key, ParaCord};
+
+/// A map from [`Key`]s to values, stored as a `Vec` indexed by the key.
+///
+/// Since keys are allocated densely, this gives O(1) access to side data for every string
+/// in a [`ParaCord`] instance, without hashing.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::collections::KeyMap;
+/// use paracord::ParaCord;
+///
+/// let paracord = ParaCord::default();
+/// let foo = paracord.get_or_intern("foo");
+/// let bar = paracord.get_or_intern("bar");
+///
+/// let mut counts = KeyMap::for_paracord(&paracord);
+/// *counts.entry(foo).or_insert(0) += 1;
+/// *counts.entry(foo).or_insert(0) += 1;
+///
+/// assert_eq!(counts.get(foo), Some(&2));
+/// assert_eq!(counts.get(bar), None);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyMap<V> {
+    values: Vec<Option<V>>,
+    len: usize,
+}
+
+impl<V> Default for KeyMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: core::fmt::Debug> core::fmt::Debug for KeyMap<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V> KeyMap<V> {
+    /// Create a new empty map.
+    pub const fn new() -> Self {
+        Self {
+            values: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Create a new empty map, with room for every key currently allocated by the instance.
+    pub fn for_paracord<S>(paracord: &ParaCord<S>) -> Self {
+        let mut this = Self::new();
+        this.grow_for(paracord);
+        this
+    }
+
+    /// Grow the map to have room for every key currently allocated by the instance,
+    /// so inserting those keys will not reallocate.
+    pub fn grow_for<S>(&mut self, paracord: &ParaCord<S>) {
+        let len = paracord.inner.next_index();
+        if len > self.values.len() {
+            self.values.resize_with(len, || None);
+        }
+    }
+
+    fn slot(&mut self, key: Key) -> &mut Option<V> {
+        let i = key.into_repr() as usize;
+        if i >= self.values.len() {
+            self.values.resize_with(i + 1, || None);
+        }
+        &mut self.values[i]
+    }
+
+    /// Insert the value for the key, returning the previous value.
+    pub fn insert(&mut self, key: Key, value: V) -> Option<V> {
+        let old = self.slot(key).replace(value);
+        self.len += old.is_none() as usize;
+        old
+    }
+
+    /// Remove the value for the key, returning it.
+    pub fn remove(&mut self, key: Key) -> Option<V> {
+        let old = self.values.get_mut(key.into_repr() as usize)?.take();
+        self.len -= old.is_some() as usize;
+        old
+    }
+
+    /// Get the value for the key.
+    #[must_use]
+    pub fn get(&self, key: Key) -> Option<&V> {
+        self.values.get(key.into_repr() as usize)?.as_ref()
+    }
+
+    /// Get the value for the key, mutably.
+    #[must_use]
+    pub fn get_mut(&mut self, key: Key) -> Option<&mut V> {
+        self.values.get_mut(key.into_repr() as usize)?.as_mut()
+    }
+
+    /// Determine if the key has a value.
+    #[must_use]
+    pub fn contains_key(&self, key: Key) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Get the entry for the key, for in-place manipulation.
+    pub fn entry(&mut self, key: Key) -> Entry<'_, V> {
+        let len = &mut self.len;
+        let i = key.into_repr() as usize;
+        if i >= self.values.len() {
+            self.values.resize_with(i + 1, || None);
+        }
+        Entry {
+            key,
+            slot: &mut self.values[i],
+            len,
+        }
+    }
+
+    /// Determine how many keys have values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Determine if no keys have values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove every value, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.values.iter_mut().for_each(|v| *v = None);
+        self.len = 0;
+    }
+
+    /// Get an iterator over every (key, value) pair, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &V)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| Some((Key::from_index(i), v.as_ref()?)))
+    }
+
+    /// Get an iterator over every (key, value) pair with mutable values, in key order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Key, &mut V)> {
+        self.values
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, v)| Some((Key::from_index(i), v.as_mut()?)))
+    }
+}
+
+/// A view into a single entry of a [`KeyMap`]. Created with [`KeyMap::entry`].
+pub struct Entry<'a, V> {
+    key: Key,
+    slot: &'a mut Option<V>,
+    len: &'a mut usize,
+}
+
+impl<'a, V> Entry<'a, V> {
+    /// The key of this entry.
+    #[must_use]
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Get the value of this entry, if there is one.
+    #[must_use]
+    pub fn get(&self) -> Option<&V> {
+        self.slot.as_ref()
+    }
+
+    /// Insert the value if the entry is empty, and return a mutable reference to the value.
+    pub fn or_insert(self, value: V) -> &'a mut V {
+        self.or_insert_with(|| value)
+    }
+
+    /// Insert the result of `f` if the entry is empty, and return a mutable reference to the value.
+    pub fn or_insert_with(self, f: impl FnOnce() -> V) -> &'a mut V {
+        if self.slot.is_none() {
+            *self.len += 1;
+        }
+        self.slot.get_or_insert_with(f)
+    }
+
+    /// Insert the default value if the entry is empty, and return a mutable reference to the value.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Modify the value in place if the entry is not empty.
+    pub fn and_modify(self, f: impl FnOnce(&mut V)) -> Self {
+        if let Some(v) = &mut *self.slot {
+            f(v);
+        }
+        self
+    }
+}
+
+impl<V> Index<Key> for KeyMap<V> {
+    type Output = V;
+
+    #[track_caller]
+    fn index(&self, key: Key) -> &V {
+        self.get(key).expect("key should have a value in this map")
+    }
+}
+
+impl<V> IndexMut<Key> for KeyMap<V> {
+    #[track_caller]
+    fn index_mut(&mut self, key: Key) -> &mut V {
+        self.get_mut(key)
+            .expect("key should have a value in this map")
+    }
+}
+
+impl<V> Extend<(Key, V)> for KeyMap<V> {
+    fn extend<I: IntoIterator<Item = (Key, V)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(key, value)| {
+            self.insert(key, value);
+        });
+    }
+}
+
+impl<V> FromIterator<(Key, V)> for KeyMap<V> {
+    fn from_iter<I: IntoIterator<Item = (Key, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
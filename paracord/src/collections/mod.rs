@@ -0,0 +1,17 @@
+//! Collections that exploit the dense key space of a [`ParaCord`](crate::ParaCord) instance.
+
+mod bitset;
+mod key_map;
+mod multimap;
+mod once_map;
+mod option_key;
+mod remap;
+mod set;
+
+pub use bitset::{KeyBitSet, KeySet};
+pub use key_map::{Entry, KeyMap};
+pub use multimap::InternedMultiMap;
+pub use once_map::KeyOnceMap;
+pub use option_key::OptionKeySlice;
+pub use remap::RemapTable;
+pub use set::InternedSet;
@@ -0,0 +1,103 @@
+use std::hash::BuildHasher;
+
+use foldhash::fast::RandomState;
+
+use super::KeyOnceMap;
+use crate::{Key, ParaCord};
+
+/// A concurrent multimap from strings to values, that interns the strings.
+///
+/// Pushing a value interns the string and appends the value to that key's list, with a single hash
+/// of the string, so it fits the common pattern of grouping events by a string field.
+/// Values are never moved, so references to them are valid for the lifetime of the map.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::collections::InternedMultiMap;
+///
+/// let events = InternedMultiMap::default();
+///
+/// let foo = events.push("foo", 1);
+/// events.push("bar", 2);
+/// events.push("foo", 3);
+///
+/// assert_eq!(events.values(foo).copied().collect::<Vec<_>>(), [1, 3]);
+/// assert_eq!(events.get("bar").copied().collect::<Vec<_>>(), [2]);
+/// assert_eq!(events.paracord().resolve(foo), "foo");
+/// ```
+pub struct InternedMultiMap<V, S = RandomState> {
+    paracord: ParaCord<S>,
+    values: KeyOnceMap<boxcar::Vec<V>>,
+}
+
+impl<V> Default for InternedMultiMap<V> {
+    fn default() -> Self {
+        Self::with_hasher(RandomState::default())
+    }
+}
+
+impl<V: core::fmt::Debug, S> core::fmt::Debug for InternedMultiMap<V, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map()
+            .entries(self.iter().map(|(_, s, v)| (s, v.collect::<Vec<_>>())))
+            .finish()
+    }
+}
+
+impl<V, S: BuildHasher> InternedMultiMap<V, S> {
+    /// Create a new empty map with the given hasher state.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            paracord: ParaCord::with_hasher(hasher),
+            values: KeyOnceMap::new(),
+        }
+    }
+
+    /// Intern the string, and append the value to its list. Returns the key of the string.
+    pub fn push(&self, s: &str, value: V) -> Key {
+        let key = self.paracord.get_or_intern(s);
+        self.push_key(key, value);
+        key
+    }
+
+    /// Get an iterator over the values pushed for the string, in the order they were pushed.
+    pub fn get(&self, s: &str) -> impl Iterator<Item = &V> {
+        let key = self.paracord.get(s);
+        key.into_iter().flat_map(|key| self.values(key))
+    }
+}
+
+impl<V, S> InternedMultiMap<V, S> {
+    /// Append the value to the list of an already interned key.
+    ///
+    /// The key should be allocated by [`InternedMultiMap::paracord`].
+    pub fn push_key(&self, key: Key, value: V) {
+        self.values.get_or_init(key, boxcar::Vec::new).push(value);
+    }
+
+    /// Get an iterator over the values pushed for the key, in the order they were pushed.
+    ///
+    /// Values pushed concurrently might not be included.
+    pub fn values(&self, key: Key) -> impl Iterator<Item = &V> {
+        self.values
+            .get(key)
+            .into_iter()
+            .flat_map(|values| values.iter().map(|(_, v)| v))
+    }
+
+    /// Get an iterator over every key with values, in key order,
+    /// along with its string and an iterator over its values.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &str, impl Iterator<Item = &V>)> {
+        self.values.iter().map(|(key, values)| {
+            let values = values.iter().map(|(_, v)| v);
+            (key, self.paracord.resolve(key), values)
+        })
+    }
+
+    /// Get the interner that holds the strings.
+    #[must_use]
+    pub fn paracord(&self) -> &ParaCord<S> {
+        &self.paracord
+    }
+}
@@ -0,0 +1,178 @@
+use core::marker::PhantomData;
+use core::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::OnceLock;
+
+use crate::Key;
+
+/// The first bucket holds this many entries, and every following bucket is twice as large as the previous.
+const FIRST_BUCKET_BITS: u32 = 5;
+/// Enough buckets to hold every possible key.
+const BUCKETS: usize = (u32::BITS + 1 - FIRST_BUCKET_BITS) as usize;
+
+/// A concurrent map from [`Key`] to `V`, where each value is initialized at most once.
+///
+/// Since keys are allocated densely, this is stored as an append-only array indexed by the key,
+/// allocated in exponentially growing buckets. Values are never moved, so
+/// references to them are valid for the lifetime of the map.
+///
+/// This is useful for caching data derived from the interned strings, eg parsed representations.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::collections::KeyOnceMap;
+/// use paracord::ParaCord;
+///
+/// let paracord = ParaCord::default();
+/// let lengths = KeyOnceMap::new();
+///
+/// let foo = paracord.get_or_intern("foo");
+/// assert_eq!(lengths.get(foo), None);
+///
+/// let len = lengths.get_or_init(foo, || paracord.resolve(foo).len());
+/// assert_eq!(*len, 3);
+///
+/// // only initialized once
+/// let len = lengths.get_or_init(foo, || unreachable!());
+/// assert_eq!(*len, 3);
+/// ```
+pub struct KeyOnceMap<V> {
+    buckets: [AtomicPtr<OnceLock<V>>; BUCKETS],
+    _marker: PhantomData<OnceLock<V>>,
+}
+
+impl<V> Default for KeyOnceMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: core::fmt::Debug> core::fmt::Debug for KeyOnceMap<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Find the bucket and the offset within the bucket for the key.
+#[inline]
+fn location(key: Key) -> (usize, usize) {
+    let i = key.into_repr() as u64 + (1 << FIRST_BUCKET_BITS);
+    let bucket = u64::BITS - 1 - i.leading_zeros() - FIRST_BUCKET_BITS;
+    let offset = i - (1 << (bucket + FIRST_BUCKET_BITS));
+    (bucket as usize, offset as usize)
+}
+
+#[inline]
+fn bucket_len(bucket: usize) -> usize {
+    1 << (bucket as u32 + FIRST_BUCKET_BITS)
+}
+
+impl<V> KeyOnceMap<V> {
+    /// Create a new empty map.
+    pub fn new() -> Self {
+        Self {
+            buckets: [(); BUCKETS].map(|_| AtomicPtr::new(ptr::null_mut())),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a new empty map, with space for the first `capacity` keys preallocated.
+    ///
+    /// Use [`ParaCord::len`](crate::ParaCord::len) to size the map for the strings
+    /// that are already interned.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let this = Self::new();
+        if let Some(last) = capacity.checked_sub(1) {
+            let last = Key::from_index(last.min(u32::MAX as usize - 1));
+            for bucket in 0..=location(last).0 {
+                this.get_or_alloc_bucket(bucket);
+            }
+        }
+        this
+    }
+
+    fn get_bucket(&self, bucket: usize) -> Option<&[OnceLock<V>]> {
+        let ptr = self.buckets[bucket].load(Ordering::Acquire);
+        if ptr.is_null() {
+            return None;
+        }
+        // Safety: non-null buckets were allocated by `get_or_alloc_bucket` with this length,
+        // and are only freed on drop.
+        Some(unsafe { &*ptr::slice_from_raw_parts(ptr, bucket_len(bucket)) })
+    }
+
+    #[cold]
+    fn alloc_bucket(&self, bucket: usize) -> &[OnceLock<V>] {
+        let new: Box<[OnceLock<V>]> = (0..bucket_len(bucket)).map(|_| OnceLock::new()).collect();
+        let new = Box::into_raw(new).cast::<OnceLock<V>>();
+
+        let ptr = match self.buckets[bucket].compare_exchange(
+            ptr::null_mut(),
+            new,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => new,
+            Err(existing) => {
+                // Safety: we just allocated `new` with this length, and it was never shared.
+                drop(unsafe {
+                    Box::from_raw(ptr::slice_from_raw_parts_mut(new, bucket_len(bucket)))
+                });
+                existing
+            }
+        };
+
+        // Safety: the bucket was allocated with this length, and is only freed on drop.
+        unsafe { &*ptr::slice_from_raw_parts(ptr, bucket_len(bucket)) }
+    }
+
+    fn get_or_alloc_bucket(&self, bucket: usize) -> &[OnceLock<V>] {
+        match self.get_bucket(bucket) {
+            Some(b) => b,
+            None => self.alloc_bucket(bucket),
+        }
+    }
+
+    /// Get the value for the key, if it has been initialized.
+    #[must_use]
+    pub fn get(&self, key: Key) -> Option<&V> {
+        let (bucket, offset) = location(key);
+        self.get_bucket(bucket)?[offset].get()
+    }
+
+    /// Get the value for the key, initializing it with `f` if it has not been initialized.
+    ///
+    /// Many threads may call this concurrently with the same key, but `f` will only be called once.
+    pub fn get_or_init(&self, key: Key, f: impl FnOnce() -> V) -> &V {
+        let (bucket, offset) = location(key);
+        self.get_or_alloc_bucket(bucket)[offset].get_or_init(f)
+    }
+
+    /// Get an iterator over every initialized ([`Key`], `&V`) pair, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &V)> {
+        (0..BUCKETS)
+            .filter_map(|bucket| Some((bucket, self.get_bucket(bucket)?)))
+            .flat_map(|(bucket, values)| {
+                let start = bucket_len(bucket) - (1 << FIRST_BUCKET_BITS);
+                values
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(i, v)| Some((Key::from_index(start + i), v.get()?)))
+            })
+    }
+}
+
+impl<V> Drop for KeyOnceMap<V> {
+    fn drop(&mut self) {
+        for (bucket, ptr) in self.buckets.iter_mut().enumerate() {
+            let ptr = *ptr.get_mut();
+            if !ptr.is_null() {
+                // Safety: non-null buckets were allocated by `alloc_bucket` with this length.
+                drop(unsafe {
+                    Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, bucket_len(bucket)))
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::Key;
+
+/// A slice of [`Option<Key>`], with utilities for columnar storage.
+///
+/// `Option<Key>` is guaranteed to have the same layout as a `u32`, where `0` represents [`None`].
+/// This allows nullable columns of keys to be converted to and from raw `u32` arrays without copying,
+/// and for bulk operations to avoid branching on every element.
+///
+/// The non-zero raw values are not the same as [`Key::into_repr`], and should be treated as opaque.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::collections::OptionKeySlice;
+/// use paracord::ParaCord;
+///
+/// let paracord = ParaCord::default();
+/// let foo = paracord.get_or_intern("foo");
+///
+/// let column = [Some(foo), None, Some(foo)];
+/// let column = OptionKeySlice::new(&column);
+/// assert_eq!(column.count_some(), 2);
+/// assert_eq!(column.is_some_bitmap(), [0b101]);
+///
+/// let raw = column.as_raw();
+/// assert_eq!(raw[1], 0);
+/// assert_eq!(&OptionKeySlice::from_raw(raw)[..], &[Some(foo), None, Some(foo)]);
+/// ```
+#[repr(transparent)]
+pub struct OptionKeySlice([Option<Key>]);
+
+impl OptionKeySlice {
+    /// Wrap a slice of optional keys.
+    pub fn new(keys: &[Option<Key>]) -> &Self {
+        // Safety: `OptionKeySlice` is `repr(transparent)` over `[Option<Key>]`.
+        unsafe { &*(keys as *const [Option<Key>] as *const Self) }
+    }
+
+    /// Wrap a mutable slice of optional keys.
+    pub fn new_mut(keys: &mut [Option<Key>]) -> &mut Self {
+        // Safety: `OptionKeySlice` is `repr(transparent)` over `[Option<Key>]`.
+        unsafe { &mut *(keys as *mut [Option<Key>] as *mut Self) }
+    }
+
+    /// Reinterpret raw `u32` values as optional keys, where `0` is [`None`].
+    pub fn from_raw(raw: &[u32]) -> &Self {
+        // Safety: `Option<Key>` has the same layout as `u32`, thanks to the `NonZeroU32` niche,
+        // and every `u32` value is a valid `Option<Key>`.
+        Self::new(unsafe { &*(raw as *const [u32] as *const [Option<Key>]) })
+    }
+
+    /// Reinterpret mutable raw `u32` values as optional keys, where `0` is [`None`].
+    pub fn from_raw_mut(raw: &mut [u32]) -> &mut Self {
+        // Safety: `Option<Key>` has the same layout as `u32`, thanks to the `NonZeroU32` niche,
+        // and every `u32` value is a valid `Option<Key>`.
+        Self::new_mut(unsafe { &mut *(raw as *mut [u32] as *mut [Option<Key>]) })
+    }
+
+    /// View the optional keys as raw `u32` values, where `0` is [`None`].
+    pub fn as_raw(&self) -> &[u32] {
+        // Safety: `Option<Key>` has the same layout as `u32`, and has no padding.
+        unsafe { &*(&self.0 as *const [Option<Key>] as *const [u32]) }
+    }
+
+    /// Count how many keys are [`Some`].
+    #[must_use]
+    pub fn count_some(&self) -> usize {
+        self.as_raw().iter().map(|&k| (k != 0) as usize).sum()
+    }
+
+    /// Build a bitmap where bit `i` is set if key `i` is [`Some`].
+    ///
+    /// Bits are packed into `u64` words, least significant bit first.
+    pub fn is_some_bitmap(&self) -> Vec<u64> {
+        self.as_raw()
+            .chunks(64)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0, |bits, (i, &k)| bits | ((k != 0) as u64) << i)
+            })
+            .collect()
+    }
+
+    /// Iterate over the index and key of every key that is [`Some`].
+    pub fn iter_some(&self) -> impl Iterator<Item = (usize, Key)> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, k)| Some((i, (*k)?)))
+    }
+}
+
+impl core::ops::Deref for OptionKeySlice {
+    type Target = [Option<Key>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl core::ops::DerefMut for OptionKeySlice {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl core::fmt::Debug for OptionKeySlice {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
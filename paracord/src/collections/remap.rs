@@ -0,0 +1,48 @@
+use crate::Key;
+
+/// A mapping from old [`Key`]s to new [`Key`]s, returned by operations
+/// that renumber the keys of a [`ParaCord`](crate::ParaCord) instance.
+///
+/// Any side tables keyed by the old keys can be migrated using [`RemapTable::get`].
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct RemapTable {
+    table: Vec<Option<Key>>,
+}
+
+impl core::fmt::Debug for RemapTable {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl RemapTable {
+    pub(crate) fn with_capacity(n: usize) -> Self {
+        Self {
+            table: Vec::with_capacity(n),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, old: Key, new: Key) {
+        let i = old.into_repr() as usize;
+        if i >= self.table.len() {
+            self.table.resize(i + 1, None);
+        }
+        self.table[i] = Some(new);
+    }
+
+    /// Get the new key for the given old key.
+    ///
+    /// Returns [`None`] if the old key no longer has an associated entry.
+    #[must_use]
+    pub fn get(&self, old: Key) -> Option<Key> {
+        self.table.get(old.into_repr() as usize).copied().flatten()
+    }
+
+    /// Get an iterator over every (old, new) key pair, ordered by the old key.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, Key)> + '_ {
+        self.table
+            .iter()
+            .enumerate()
+            .filter_map(|(i, new)| Some((Key::from_index(i), (*new)?)))
+    }
+}
@@ -0,0 +1,121 @@
+use std::hash::BuildHasher;
+
+use foldhash::fast::RandomState;
+
+use crate::ParaCord;
+
+/// A concurrent set of strings, that interns the strings.
+///
+/// This is a drop-in replacement for a concurrent `HashSet<String>`, for workloads that only need
+/// to deduplicate strings. Every string is stored once, in the arenas of a [`ParaCord`], and
+/// keys never need to be handled. The keys are still available through [`InternedSet::paracord`].
+///
+/// Strings can't be removed through a shared reference.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::collections::InternedSet;
+///
+/// let seen = InternedSet::default();
+///
+/// assert!(seen.insert("foo"));
+/// assert!(seen.insert("bar"));
+/// assert!(!seen.insert("foo"));
+///
+/// assert!(seen.contains("foo"));
+/// assert!(!seen.contains("baz"));
+/// assert_eq!(seen.len(), 2);
+/// assert_eq!(seen.iter().collect::<Vec<_>>(), ["foo", "bar"]);
+/// ```
+pub struct InternedSet<S = RandomState> {
+    paracord: ParaCord<S>,
+}
+
+impl Default for InternedSet {
+    fn default() -> Self {
+        Self::with_hasher(RandomState::default())
+    }
+}
+
+impl<S> core::fmt::Debug for InternedSet<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<S: BuildHasher> InternedSet<S> {
+    /// Create a new empty set with the given hasher state.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            paracord: ParaCord::with_hasher(hasher),
+        }
+    }
+
+    /// Add the string to the set.
+    ///
+    /// Returns `true` if the string was not already in the set. If several threads insert the
+    /// same string concurrently, exactly one of them gets `true`.
+    pub fn insert(&self, s: &str) -> bool {
+        self.paracord.inner.insert(s.as_bytes()).1
+    }
+
+    /// Determine if the string is in the set.
+    #[must_use]
+    pub fn contains(&self, s: &str) -> bool {
+        self.paracord.get(s).is_some()
+    }
+}
+
+impl<S> InternedSet<S> {
+    /// Determine how many strings are in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.paracord.len()
+    }
+
+    /// Determine if the set has no strings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paracord.is_empty()
+    }
+
+    /// Get an iterator over every string in the set, in the order they were first inserted.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.paracord.iter().map(|(_, s)| s)
+    }
+
+    /// Get the interner that holds the strings.
+    #[must_use]
+    pub fn paracord(&self) -> &ParaCord<S> {
+        &self.paracord
+    }
+
+    /// Get back the interner that holds the strings.
+    #[must_use]
+    pub fn into_paracord(self) -> ParaCord<S> {
+        self.paracord
+    }
+}
+
+impl<S> From<ParaCord<S>> for InternedSet<S> {
+    fn from(paracord: ParaCord<S>) -> Self {
+        Self { paracord }
+    }
+}
+
+impl<T: AsRef<str>> FromIterator<T> for InternedSet {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: AsRef<str>, S: BuildHasher> Extend<T> for InternedSet<S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for s in iter {
+            self.insert(s.as_ref());
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Clearing a shared [`ParaCord`] without exclusive access.
+//!
+//! [`ParaCord::clear`] needs `&mut self`, which is not available for an instance shared behind
+//! an `Arc`. An [`EpochParaCord`] holds a sequence of generations instead. Readers take an
+//! [`EpochGuard`] of the current generation, and [`EpochParaCord::clear`] starts a new generation
+//! for new guards. The strings of an old generation are freed once its last guard is dropped.
+//!
+//! ```
+//! use paracord::epoch::EpochParaCord;
+//! use std::sync::Arc;
+//!
+//! let paracord = Arc::new(EpochParaCord::default());
+//!
+//! let guard = paracord.guard();
+//! let foo = guard.get_or_intern("foo");
+//!
+//! paracord.clear();
+//!
+//! // the guard keeps its generation alive.
+//! assert_eq!(guard.resolve(foo), "foo");
+//! // new guards start from an empty generation.
+//! assert_eq!(paracord.guard().get("foo"), None);
+//! ```
+
+use std::hash::BuildHasher;
+use std::ops::Deref;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use foldhash::fast::RandomState;
+
+use crate::ParaCord;
+
+struct Generation<S> {
+    paracord: ParaCord<S>,
+    epoch: u64,
+}
+
+/// A [`ParaCord`] that can be cleared through a shared reference.
+///
+/// See the [module docs](self).
+///
+/// Keys are only meaningful within the generation that allocated them. As with
+/// [`ParaCord::clear`], keys of a new generation continue on from the keys the previous one had
+/// allocated by the time of the clear, unless the
+/// [`StaleKeyPolicy`](crate::slice::StaleKeyPolicy) is `Reuse`, so those stale keys can be
+/// detected.
+///
+/// Guards of an old generation can still intern new strings after the clear, and their keys
+/// come from the same range as the keys of the new generation. These keys alias keys of the new
+/// generation, and cannot be detected by the stale key policy. Use [`SendKey`](crate::SendKey)s
+/// to tell the generations apart in that case, since each generation is a separate instance.
+pub struct EpochParaCord<S = RandomState> {
+    current: RwLock<Arc<Generation<S>>>,
+}
+
+impl<S> std::fmt::Debug for EpochParaCord<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let current = self.read();
+        f.debug_struct("EpochParaCord")
+            .field("epoch", &current.epoch)
+            .field("paracord", &current.paracord)
+            .finish()
+    }
+}
+
+impl Default for EpochParaCord {
+    fn default() -> Self {
+        Self::new(ParaCord::default())
+    }
+}
+
+impl<S> EpochParaCord<S> {
+    /// Use the given instance as the first generation.
+    pub fn new(paracord: ParaCord<S>) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(Generation { paracord, epoch: 0 })),
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Arc<Generation<S>>> {
+        // the lock only protects an `Arc`, which is always valid, even after a panic.
+        self.current.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Arc<Generation<S>>> {
+        self.current.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Get a guard of the current generation.
+    ///
+    /// The guard can intern and resolve strings in its generation, and keeps that generation
+    /// alive until it is dropped, even if this instance is cleared.
+    #[must_use]
+    pub fn guard(&self) -> EpochGuard<S> {
+        EpochGuard {
+            generation: Arc::clone(&self.read()),
+        }
+    }
+
+    /// Get the number of the current generation. This starts at 0, and increases by one
+    /// with every [`EpochParaCord::clear`].
+    #[must_use]
+    pub fn epoch(&self) -> u64 {
+        self.read().epoch
+    }
+}
+
+impl<S: BuildHasher + Clone> EpochParaCord<S> {
+    /// Start a new, empty, generation.
+    ///
+    /// Guards taken before the clear keep resolving the strings of their generation, but keys
+    /// they allocate after the clear alias keys of the new generation. The new
+    /// generation uses the same hasher and [`StaleKeyPolicy`](crate::slice::StaleKeyPolicy) as
+    /// the previous one, but not its soft limits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::epoch::EpochParaCord;
+    /// use paracord::slice::StaleKeyPolicy;
+    /// use paracord::ParaCord;
+    ///
+    /// let paracord = EpochParaCord::new(
+    ///     ParaCord::default().with_stale_key_policy(StaleKeyPolicy::ReturnNone),
+    /// );
+    /// let foo = paracord.guard().get_or_intern("foo");
+    ///
+    /// paracord.clear();
+    /// assert_eq!(paracord.epoch(), 1);
+    ///
+    /// let guard = paracord.guard();
+    /// assert_eq!(guard.try_resolve(foo), None);
+    /// assert_ne!(guard.get_or_intern("foo"), foo);
+    /// ```
+    pub fn clear(&self) {
+        let mut current = self.write();
+        let paracord = ParaCord {
+            inner: current
+                .paracord
+                .inner
+                .successor(current.paracord.hasher().clone()),
+            parsed: Default::default(),
+        };
+        let epoch = current.epoch + 1;
+        let old = std::mem::replace(&mut *current, Arc::new(Generation { paracord, epoch }));
+        // if there are no other guards, free the old generation outside of the lock.
+        drop(current);
+        drop(old);
+    }
+}
+
+/// A generation of an [`EpochParaCord`], which derefs to its [`ParaCord`].
+///
+/// Created with [`EpochParaCord::guard`].
+pub struct EpochGuard<S = RandomState> {
+    generation: Arc<Generation<S>>,
+}
+
+impl<S> Clone for EpochGuard<S> {
+    fn clone(&self) -> Self {
+        Self {
+            generation: Arc::clone(&self.generation),
+        }
+    }
+}
+
+impl<S> std::fmt::Debug for EpochGuard<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EpochGuard")
+            .field("epoch", &self.generation.epoch)
+            .field("paracord", &self.generation.paracord)
+            .finish()
+    }
+}
+
+impl<S> EpochGuard<S> {
+    /// Get the number of the generation this guard belongs to.
+    #[must_use]
+    pub fn epoch(&self) -> u64 {
+        self.generation.epoch
+    }
+}
+
+impl<S> Deref for EpochGuard<S> {
+    type Target = ParaCord<S>;
+
+    fn deref(&self) -> &ParaCord<S> {
+        &self.generation.paracord
+    }
+}
@@ -0,0 +1,260 @@
+//! A fixed-capacity interner that never allocates.
+//!
+//! See [`ParaCord`].
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::hash::BuildHasher;
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+
+use crate::Key;
+
+/// Error returned when a fixed-capacity [`ParaCord`] is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FixedError {
+    /// Every entry is in use.
+    TooManyEntries,
+    /// The arena does not have enough bytes left for the string.
+    ArenaFull,
+}
+
+impl fmt::Display for FixedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixedError::TooManyEntries => f.write_str("interner has no entries left"),
+            FixedError::ArenaFull => f.write_str("interner has no arena bytes left"),
+        }
+    }
+}
+
+impl std::error::Error for FixedError {}
+
+/// A string interner that stores up to `MAX_ENTRIES` strings, using at most `ARENA_BYTES` bytes
+/// of string data, all inline.
+///
+/// Nothing is allocated, so this can be placed in a `static`, or in memory provided by the caller,
+/// for contexts where dynamic allocation after init is forbidden. Interning returns an error once
+/// the interner is full.
+///
+/// Lookups are lock-free. Inserts are serialized by a spin lock, which is only held while copying
+/// the new string into the arena. Lookups are fastest while fewer than ~80% of the entries are used.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::fixed::{FixedError, ParaCord};
+///
+/// let paracord = ParaCord::<4, 16>::default();
+///
+/// let foo = paracord.try_get_or_intern("foo").unwrap();
+/// let bar = paracord.try_get_or_intern("bar").unwrap();
+/// assert_eq!(paracord.try_get_or_intern("foo"), Ok(foo));
+/// assert_eq!(paracord.resolve(bar), "bar");
+///
+/// assert_eq!(paracord.try_get_or_intern("too long for the arena"), Err(FixedError::ArenaFull));
+/// ```
+pub struct ParaCord<
+    const MAX_ENTRIES: usize,
+    const ARENA_BYTES: usize,
+    S = foldhash::fast::RandomState,
+> {
+    arena: UnsafeCell<[u8; ARENA_BYTES]>,
+    /// The `offset << 32 | len` of every entry in the arena.
+    entries: [AtomicU64; MAX_ENTRIES],
+    /// Open addressed hash table of `index + 1`, or 0 if empty.
+    table: [AtomicU32; MAX_ENTRIES],
+    /// The number of published entries.
+    len: AtomicUsize,
+    /// The number of arena bytes in use. Only accessed while locked.
+    arena_len: AtomicUsize,
+    lock: AtomicBool,
+    hasher: S,
+}
+
+// Safety: the arena is only written while locked, past the end of the published strings,
+// and is only read through published strings, so it is never accessed mutably while shared.
+unsafe impl<const N: usize, const B: usize, S: Sync> Sync for ParaCord<N, B, S> {}
+
+impl<const N: usize, const B: usize, S> fmt::Debug for ParaCord<N, B, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<const N: usize, const B: usize> Default for ParaCord<N, B> {
+    fn default() -> Self {
+        Self::with_hasher(foldhash::fast::RandomState::default())
+    }
+}
+
+/// Releases the insert lock on drop.
+struct Locked<'a>(&'a AtomicBool);
+
+impl Drop for Locked<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl<const N: usize, const B: usize, S> ParaCord<N, B, S> {
+    // these are only used to initialize the arrays, since inline const needs a newer MSRV.
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY_ENTRY: AtomicU64 = AtomicU64::new(0);
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY_SLOT: AtomicU32 = AtomicU32::new(0);
+
+    /// Create a new empty `ParaCord` instance with the given hasher state.
+    ///
+    /// This is a `const fn`, so it can initialize a `static` with a hasher
+    /// that can be created in a const context.
+    pub const fn with_hasher(hasher: S) -> Self {
+        Self {
+            arena: UnsafeCell::new([0; B]),
+            entries: [Self::EMPTY_ENTRY; N],
+            table: [Self::EMPTY_SLOT; N],
+            len: AtomicUsize::new(0),
+            arena_len: AtomicUsize::new(0),
+            lock: AtomicBool::new(false),
+            hasher,
+        }
+    }
+
+    fn lock(&self) -> Locked<'_> {
+        while self
+            .lock
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        Locked(&self.lock)
+    }
+
+    /// Get a pointer into the arena.
+    fn arena_ptr(&self, offset: usize) -> *mut u8 {
+        self.arena.get().cast::<u8>().wrapping_add(offset)
+    }
+
+    /// Get the string of a published entry.
+    fn entry(&self, i: usize) -> &str {
+        let entry = self.entries[i].load(Ordering::Relaxed);
+        let (offset, len) = ((entry >> 32) as usize, entry as u32 as usize);
+        // Safety: the entry is published, so these bytes were written before the release store
+        // that published it, and will never be written again.
+        let bytes = unsafe { core::slice::from_raw_parts(self.arena_ptr(offset), len) };
+        // Safety: we insert only strings, so it's valid utf8
+        unsafe { core::str::from_utf8_unchecked(bytes) }
+    }
+
+    /// Try and resolve the string associated with this [`Key`].
+    ///
+    /// Returns [`None`] if the key was not allocated by this instance.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&str> {
+        let i = key.into_repr() as usize;
+        (i < self.len.load(Ordering::Acquire)).then(|| self.entry(i))
+    }
+
+    /// Resolve the string associated with this [`Key`].
+    ///
+    /// # Panics
+    ///
+    /// If the key was not allocated by this instance.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &str {
+        self.try_resolve(key)
+            .expect("key should be allocated by this paracord instance")
+    }
+
+    /// Determine how many strings have been allocated
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    /// Determine if no strings have been allocated
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determine how many bytes of the arena are in use.
+    #[must_use]
+    pub fn arena_len(&self) -> usize {
+        let _locked = self.lock();
+        self.arena_len.load(Ordering::Relaxed)
+    }
+
+    /// Get an iterator over every ([`Key`], `&str`) pair
+    /// that has been allocated in this [`ParaCord`] instance.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &str)> {
+        (0..self.len()).map(|i| (Key::from_index(i), self.entry(i)))
+    }
+}
+
+impl<const N: usize, const B: usize, S: BuildHasher> ParaCord<N, B, S> {
+    /// Find the entry for the string, or the empty slot it would be inserted into.
+    fn find(&self, s: &str, hash: u64) -> Result<Key, Option<usize>> {
+        if N == 0 {
+            return Err(None);
+        }
+        let start = hash as usize % N;
+        for slot in (start..N).chain(0..start) {
+            match self.table[slot].load(Ordering::Acquire) {
+                0 => return Err(Some(slot)),
+                i if self.entry(i as usize - 1) == s => return Ok(Key::from_index(i as usize - 1)),
+                _ => {}
+            }
+        }
+        Err(None)
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        self.find(s, self.hasher.hash_one(s)).ok()
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Allocates a new key if not found, or returns an error if the interner is full.
+    pub fn try_get_or_intern(&self, s: &str) -> Result<Key, FixedError> {
+        let hash = self.hasher.hash_one(s);
+        if let Ok(key) = self.find(s, hash) {
+            return Ok(key);
+        }
+
+        let _locked = self.lock();
+        // another thread might have inserted it while we waited.
+        let slot = match self.find(s, hash) {
+            Ok(key) => return Ok(key),
+            Err(None) => return Err(FixedError::TooManyEntries),
+            Err(Some(slot)) => slot,
+        };
+
+        let i = self.len.load(Ordering::Relaxed);
+        if i >= N {
+            return Err(FixedError::TooManyEntries);
+        }
+        let offset = self.arena_len.load(Ordering::Relaxed);
+        if B - offset < s.len() {
+            return Err(FixedError::ArenaFull);
+        }
+
+        // Safety: we hold the lock, and bytes past `arena_len` are not part of any published
+        // string, so nothing else accesses them. We checked they are in bounds.
+        unsafe {
+            core::ptr::copy_nonoverlapping(s.as_ptr(), self.arena_ptr(offset), s.len());
+        }
+        self.arena_len.store(offset + s.len(), Ordering::Relaxed);
+        self.entries[i].store(((offset as u64) << 32) | s.len() as u64, Ordering::Relaxed);
+
+        let key = Key::from_index(i);
+        // publish the entry to `find` and `try_resolve`.
+        self.table[slot].store(i as u32 + 1, Ordering::Release);
+        self.len.store(i + 1, Ordering::Release);
+        Ok(key)
+    }
+}
@@ -0,0 +1,152 @@
+//! Copy-on-write children of a shared [`ParaCord`].
+//!
+//! See [`ParaCord::fork`].
+
+use std::hash::BuildHasher;
+use std::sync::Arc;
+
+use foldhash::fast::RandomState;
+
+use crate::{Key, ParaCord};
+
+/// A child interner that shares the strings of a parent [`ParaCord`].
+///
+/// Created with [`ParaCord::fork`].
+///
+/// Strings that were interned in the parent when the fork was created resolve to the parent's keys.
+/// New strings are interned into the child's own storage, with keys that start after the parent's
+/// watermark, so keys from the parent are valid in the child but not the other way around.
+///
+/// Strings interned into the parent after the fork are not visible to the child.
+pub struct ForkedParaCord<S = RandomState> {
+    parent: Arc<ParaCord<S>>,
+    watermark: usize,
+    parent_len: usize,
+    own: ParaCord<S>,
+}
+
+impl<S> std::fmt::Debug for ForkedParaCord<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<S: BuildHasher + Clone> ParaCord<S> {
+    /// Create a child interner that shares this instance's strings without copying them.
+    ///
+    /// See [`ForkedParaCord`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use paracord::ParaCord;
+    ///
+    /// let base = Arc::new(ParaCord::default());
+    /// let foo = base.get_or_intern("foo");
+    ///
+    /// let child = base.fork();
+    /// assert_eq!(child.get_or_intern("foo"), foo);
+    ///
+    /// let bar = child.get_or_intern("bar");
+    /// assert_eq!(child.resolve(bar), "bar");
+    /// assert_eq!(base.get("bar"), None);
+    /// ```
+    pub fn fork(self: &Arc<Self>) -> ForkedParaCord<S> {
+        let watermark = self.inner.next_index();
+        ForkedParaCord {
+            parent: Arc::clone(self),
+            watermark,
+            parent_len: watermark - self.inner.first_index() - self.inner.removed(),
+            own: ParaCord::with_hasher(self.inner.hasher().clone()),
+        }
+    }
+}
+
+impl<S> ForkedParaCord<S> {
+    /// Get the parent [`ParaCord`] of this fork.
+    #[must_use]
+    pub fn parent(&self) -> &Arc<ParaCord<S>> {
+        &self.parent
+    }
+
+    fn is_parent_key(&self, key: Key) -> bool {
+        (key.into_repr() as usize) < self.watermark
+    }
+
+    fn own_to_child(&self, key: Key) -> Key {
+        Key::from_index(self.watermark + key.into_repr() as usize)
+    }
+
+    fn child_to_own(&self, key: Key) -> Key {
+        Key::from_index(key.into_repr() as usize - self.watermark)
+    }
+
+    /// Try resolve the string associated with this key.
+    ///
+    /// Returns [`None`] if the key was not allocated by this fork or its parent before the fork.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&str> {
+        if self.is_parent_key(key) {
+            self.parent.try_resolve(key)
+        } else {
+            self.own.try_resolve(self.child_to_own(key))
+        }
+    }
+
+    /// Resolve the string associated with this key.
+    ///
+    /// # Panics
+    ///
+    /// If the key was not allocated by this fork or its parent before the fork.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &str {
+        if self.is_parent_key(key) {
+            self.parent.resolve(key)
+        } else {
+            self.own.resolve(self.child_to_own(key))
+        }
+    }
+
+    /// Determine how many strings are visible in this fork, including the parent's.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.parent_len + self.own.len()
+    }
+
+    /// Determine if no strings are visible in this fork.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get an iterator over every ([`Key`], `&str`) pair visible in this fork,
+    /// starting with the parent's.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &str)> {
+        let parent = self.parent.keys_in_range(..Key::from_index(self.watermark));
+        let own = self.own.iter().map(|(k, s)| (self.own_to_child(k), s));
+        parent.chain(own)
+    }
+}
+
+impl<S: BuildHasher> ForkedParaCord<S> {
+    /// Try and get the [`Key`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        match self.parent.get(s) {
+            Some(key) if self.is_parent_key(key) => Some(key),
+            _ => self.own.get(s).map(|k| self.own_to_child(k)),
+        }
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Allocates a new key in this fork if not found.
+    pub fn get_or_intern(&self, s: &str) -> Key {
+        match self.parent.get(s) {
+            Some(key) if self.is_parent_key(key) => key,
+            _ => self.own_to_child(self.own.get_or_intern(s)),
+        }
+    }
+}
@@ -0,0 +1,339 @@
+//! Interners generated at build time, which live in static memory.
+//!
+//! A fixed vocabulary can be turned into Rust source with [`codegen`], typically from a build
+//! script, and included in the crate as a `static` [`FrozenParaCord`]. It costs nothing at
+//! startup, and strings outside the vocabulary can be handled by a [`Layered`] interner.
+//!
+//! ```
+//! // in build.rs
+//! let source = paracord::frozen::codegen("KEYWORDS", ["fn", "let", "match"]);
+//! assert!(source.starts_with("static KEYWORDS: ::paracord::frozen::FrozenParaCord"));
+//! // std::fs::write(out_dir.join("keywords.rs"), source).unwrap();
+//!
+//! // in the crate
+//! // include!(concat!(env!("OUT_DIR"), "/keywords.rs"));
+//! ```
+
+use core::fmt;
+use core::fmt::Write;
+use std::hash::BuildHasher;
+
+use foldhash::fast::RandomState;
+
+use crate::{Key, ParaCord};
+
+/// The hash used by the generated table. This must never change, since tables are generated
+/// ahead of time.
+fn hash(s: &[u8]) -> u64 {
+    // FNV-1a
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &b in s {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// A read-only interner whose strings and hash table are stored in static memory.
+///
+/// These are generated with [`codegen`]. The strings have the keys `0..len`, in the order they
+/// were given to [`codegen`].
+#[derive(Clone, Copy)]
+pub struct FrozenParaCord {
+    /// Every string, concatenated.
+    data: &'static str,
+    /// The start of each string in `data`, followed by the end of the last string.
+    offsets: &'static [u32],
+    /// Open addressed hash table of `index + 1`, or 0 if empty. The length is a power of two.
+    table: &'static [u32],
+}
+
+impl fmt::Debug for FrozenParaCord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl FrozenParaCord {
+    /// Create an instance from the parts generated by [`codegen`].
+    ///
+    /// Parts that were not generated by [`codegen`] won't cause undefined behaviour, but lookups
+    /// might return the wrong results, or panic.
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn from_parts(
+        data: &'static str,
+        offsets: &'static [u32],
+        table: &'static [u32],
+    ) -> Self {
+        Self {
+            data,
+            offsets,
+            table,
+        }
+    }
+
+    /// Determine how many strings are in this instance.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Determine if this instance has no strings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Try and resolve the string associated with this [`Key`].
+    ///
+    /// Returns [`None`] if the key is not in this instance.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&'static str> {
+        let i = key.into_repr() as usize;
+        let start = *self.offsets.get(i)? as usize;
+        let end = *self.offsets.get(i + 1)? as usize;
+        self.data.get(start..end)
+    }
+
+    /// Resolve the string associated with this [`Key`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not in this instance.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &'static str {
+        self.try_resolve(key)
+            .expect("key should be in this frozen paracord")
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        let mask = self.table.len().checked_sub(1)?;
+        let mut i = hash(s.as_bytes()) as usize & mask;
+        loop {
+            let index = self.table[i].checked_sub(1)?;
+            let key = Key::from_index(index as usize);
+            if self.try_resolve(key) == Some(s) {
+                return Some(key);
+            }
+            i = (i + 1) & mask;
+        }
+    }
+
+    /// Get an iterator over every ([`Key`], `&str`) pair, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &'static str)> + '_ {
+        (0..self.len()).map(|i| {
+            let key = Key::from_index(i);
+            (key, self.resolve(key))
+        })
+    }
+
+    /// Use a runtime [`ParaCord`] for strings that are not in this instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::frozen::FrozenParaCord;
+    ///
+    /// # static KEYWORDS: FrozenParaCord = FrozenParaCord::from_parts("", &[0], &[0]);
+    /// let layered = KEYWORDS.layered();
+    /// let foo = layered.get_or_intern("foo");
+    /// assert_eq!(layered.resolve(foo), "foo");
+    /// ```
+    #[must_use]
+    pub fn layered(&'static self) -> Layered {
+        Layered {
+            frozen: self,
+            runtime: ParaCord::default(),
+        }
+    }
+}
+
+/// Generate the Rust source for a `static` [`FrozenParaCord`] called `name`, with the given
+/// strings.
+///
+/// Duplicate strings are only included once, so the keys are the indices of the first
+/// occurrence of each string, among the unique strings.
+///
+/// # Panics
+///
+/// Panics if the strings are larger than `u32::MAX` bytes in total.
+#[must_use]
+pub fn codegen<I>(name: &str, strings: I) -> String
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let (data, offsets, table) = build(strings);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "static {name}: ::paracord::frozen::FrozenParaCord = \
+         ::paracord::frozen::FrozenParaCord::from_parts("
+    );
+    let _ = writeln!(out, "    {data:?},");
+    let _ = writeln!(out, "    &{offsets:?},");
+    let _ = writeln!(out, "    &{table:?},");
+    let _ = writeln!(out, ");");
+    out
+}
+
+/// Build the parts of a [`FrozenParaCord`].
+fn build<I>(strings: I) -> (String, Vec<u32>, Vec<u32>)
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let unique = ParaCord::default();
+    for s in strings {
+        unique.get_or_intern(s.as_ref());
+    }
+    build_unique(unique.iter().map(|(_, s)| s))
+}
+
+/// Build the parts of a [`FrozenParaCord`] from strings that are already unique.
+fn build_unique<'a>(strings: impl IntoIterator<Item = &'a str>) -> (String, Vec<u32>, Vec<u32>) {
+    let mut data = String::new();
+    let mut offsets = vec![0];
+    for s in strings {
+        data.push_str(s);
+        offsets.push(u32::try_from(data.len()).expect("frozen strings must fit in u32::MAX bytes"));
+    }
+
+    let len = offsets.len() - 1;
+    let mut table = vec![0u32; (len * 2).next_power_of_two()];
+    let mask = table.len() - 1;
+    for (index, w) in offsets.windows(2).enumerate() {
+        let s = &data.as_bytes()[w[0] as usize..w[1] as usize];
+        let mut i = hash(s) as usize & mask;
+        while table[i] != 0 {
+            i = (i + 1) & mask;
+        }
+        table[i] = index as u32 + 1;
+    }
+
+    (data, offsets, table)
+}
+
+/// Build a [`FrozenParaCord`] at runtime from strings that are already unique, leaking its
+/// memory.
+pub(crate) fn leak_unique<'a>(strings: impl IntoIterator<Item = &'a str>) -> FrozenParaCord {
+    let (data, offsets, table) = build_unique(strings);
+    FrozenParaCord::from_parts(data.leak(), offsets.leak(), table.leak())
+}
+
+/// A [`FrozenParaCord`] with a runtime [`ParaCord`] for every other string.
+/// Created with [`FrozenParaCord::layered`].
+///
+/// Strings in the frozen instance keep their keys. Other strings are interned at runtime, and
+/// get keys after the frozen keys.
+pub struct Layered<S = RandomState> {
+    frozen: &'static FrozenParaCord,
+    runtime: ParaCord<S>,
+}
+
+impl<S> fmt::Debug for Layered<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Layered")
+            .field("frozen", &self.frozen)
+            .field("runtime", &self.runtime)
+            .finish()
+    }
+}
+
+impl<S> Layered<S> {
+    /// Use the given runtime [`ParaCord`] for strings that are not in the frozen instance.
+    ///
+    /// The runtime instance should not be shared with anything else, since its keys are
+    /// offset by the number of frozen strings.
+    #[must_use]
+    pub fn with_runtime(frozen: &'static FrozenParaCord, runtime: ParaCord<S>) -> Self {
+        Self { frozen, runtime }
+    }
+
+    /// Get the frozen instance.
+    #[must_use]
+    pub fn frozen(&self) -> &'static FrozenParaCord {
+        self.frozen
+    }
+
+    fn runtime_key(&self, key: Key) -> Option<Key> {
+        let index = (key.into_repr() as usize).checked_sub(self.frozen.len())?;
+        Some(Key::from_index(index))
+    }
+
+    fn layered_key(&self, key: Key) -> Key {
+        Key::from_index(self.frozen.len() + key.into_repr() as usize)
+    }
+
+    /// Determine how many strings are in the frozen and runtime instances.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frozen.len() + self.runtime.len()
+    }
+
+    /// Determine if both the frozen and runtime instances are empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Try and resolve the string associated with this [`Key`].
+    ///
+    /// Returns [`None`] if the key is in neither instance.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&str> {
+        match self.runtime_key(key) {
+            None => self.frozen.try_resolve(key),
+            Some(key) => self.runtime.try_resolve(key),
+        }
+    }
+
+    /// Resolve the string associated with this [`Key`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is in neither instance.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &str {
+        self.try_resolve(key)
+            .expect("key should be allocated by this paracord instance")
+    }
+}
+
+impl<S: BuildHasher> Layered<S> {
+    /// Try and get the [`Key`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        if let Some(key) = self.frozen.get(s) {
+            return Some(key);
+        }
+        Some(self.layered_key(self.runtime.get(s)?))
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Interns the string at runtime if it's not in the frozen instance.
+    pub fn get_or_intern(&self, s: &str) -> Key {
+        if let Some(key) = self.frozen.get(s) {
+            return key;
+        }
+        self.layered_key(self.runtime.get_or_intern(s))
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn leak(strings: &[&str]) -> &'static FrozenParaCord {
+    let (data, offsets, table) = build(strings);
+    Box::leak(Box::new(FrozenParaCord::from_parts(
+        data.leak(),
+        offsets.leak(),
+        table.leak(),
+    )))
+}
@@ -0,0 +1,162 @@
+//! Introspection of the global interners created by [`custom_key`](crate::custom_key).
+//!
+//! Keys opt in to the registry with the `register` option:
+//!
+//! ```
+//! paracord::custom_key!(
+//!     pub struct NameKey;
+//!
+//!     register;
+//! );
+//!
+//! NameKey::new("foo");
+//!
+//! let entry = paracord::global::registry()
+//!     .find(|entry| entry.name.ends_with("NameKey"))
+//!     .unwrap();
+//! assert_eq!(entry.len, 1);
+//! ```
+
+use core::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Mutex, OnceLock};
+
+use crate::ParaCord;
+
+/// Information about a global interner, returned by [`registry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GlobalInterner {
+    /// The type name of the key, as given by [`core::any::type_name`].
+    pub name: &'static str,
+    /// How many strings have been allocated.
+    pub len: usize,
+    /// Approximately how many bytes of memory are used.
+    pub memory_usage: usize,
+}
+
+/// A key type backed by a global interner, as created by [`custom_key`](crate::custom_key).
+///
+/// This is implemented automatically by the macro, and lets code such as [`Interned`](crate::Interned)
+/// be generic over the global interner used.
+pub trait GlobalKey: Copy + Eq + Send + Sync + 'static {
+    /// Create a new key associated with the given string.
+    /// Returns the same key if called repeatedly.
+    fn new(s: &str) -> Self;
+
+    /// Try and get the key associated with the given string.
+    /// Returns [`None`] if not found.
+    fn try_new_existing(s: &str) -> Option<Self>;
+
+    /// Resolve the string associated with this key.
+    fn as_str(&self) -> &'static str;
+}
+
+/// Passed to the hasher init expression of a [`custom_key`](crate::custom_key),
+/// when the global interner is first used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Config {
+    /// The type name of the key, as given by [`core::any::type_name`].
+    pub name: &'static str,
+}
+
+impl Config {
+    #[doc(hidden)]
+    pub fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+
+    /// Get a seed for the hasher.
+    ///
+    /// This is the seed given to [`set_seed`], or a new random seed from OS entropy if
+    /// there isn't one.
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        match *SEED.lock().unwrap_or_else(|e| e.into_inner()) {
+            Some(seed) => seed,
+            None => {
+                let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+                hasher.write(self.name.as_bytes());
+                hasher.finish()
+            }
+        }
+    }
+}
+
+static SEED: Mutex<Option<u64>> = Mutex::new(None);
+
+/// Set the seed returned by [`Config::seed`], or [`None`] to use OS entropy.
+///
+/// This only affects global interners that are initialized afterwards.
+pub fn set_seed(seed: Option<u64>) {
+    *SEED.lock().unwrap_or_else(|e| e.into_inner()) = seed;
+}
+
+/// Returned by `init_with` on a [`custom_key`](crate::custom_key) when the global interner
+/// was already initialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AlreadyInitialized;
+
+impl fmt::Display for AlreadyInitialized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("global interner is already initialized")
+    }
+}
+
+impl std::error::Error for AlreadyInitialized {}
+
+#[doc(hidden)]
+pub fn init<T>(cell: &OnceLock<T>, f: impl FnOnce() -> T) -> Result<(), AlreadyInitialized> {
+    let mut f = Some(f);
+    cell.get_or_init(|| f.take().expect("init should only be called once")());
+    match f {
+        None => Ok(()),
+        Some(_) => Err(AlreadyInitialized),
+    }
+}
+
+#[doc(hidden)]
+pub trait Registered: Sync {
+    fn count(&self) -> usize;
+    fn memory_usage(&self) -> usize;
+}
+
+impl<S: Sync> Registered for ParaCord<S> {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.inner.memory_usage()
+    }
+}
+
+type Entry = (&'static str, fn() -> Option<&'static dyn Registered>);
+
+static REGISTRY: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+#[doc(hidden)]
+pub fn register(name: &'static str, get: fn() -> Option<&'static dyn Registered>) {
+    REGISTRY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push((name, get));
+}
+
+/// Get information about every global interner that has been registered.
+///
+/// Global interners are created lazily, so a key type only appears once it has been used.
+pub fn registry() -> impl Iterator<Item = GlobalInterner> {
+    let entries = REGISTRY.lock().unwrap_or_else(|e| e.into_inner()).clone();
+
+    entries.into_iter().filter_map(|(name, get)| {
+        let paracord = get()?;
+        Some(GlobalInterner {
+            name,
+            len: paracord.count(),
+            memory_usage: paracord.memory_usage(),
+        })
+    })
+}
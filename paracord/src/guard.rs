@@ -0,0 +1,96 @@
+//! Amortized reads of a [`ParaCord`].
+//!
+//! See [`ParaCord::guard`].
+
+use core::fmt;
+use std::hash::BuildHasher;
+
+use foldhash::fast::RandomState;
+
+use crate::{slice, Key, ParaCord};
+
+/// A guard for doing many reads of a [`ParaCord`], which keeps every shard lock it takes.
+/// Created with [`ParaCord::guard`].
+///
+/// [`ParaCord::get`] takes a shard read lock for every call. [`ReadGuard::get`] only takes each
+/// shard's read lock the first time that shard is needed, so tight loops of lookups don't pay
+/// for synchronization on every call.
+///
+/// While the guard is alive, strings cannot be interned into any shard it has locked. Interning
+/// into the same instance from the thread holding the guard may never return, so drop the guard
+/// first.
+pub struct ReadGuard<'a, S = RandomState> {
+    paracord: &'a ParaCord<S>,
+    inner: slice::ReadGuard<'a, u8, S>,
+}
+
+impl<S> fmt::Debug for ReadGuard<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.paracord.fmt(f)
+    }
+}
+
+impl<S> ParaCord<S> {
+    /// Get a [`ReadGuard`], for doing many lookups without taking a lock for every one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::ParaCord;
+    /// let paracord = ParaCord::default();
+    /// let foo = paracord.get_or_intern("foo");
+    ///
+    /// let guard = paracord.guard();
+    /// for _ in 0..100 {
+    ///     assert_eq!(guard.get("foo"), Some(foo));
+    ///     assert_eq!(guard.resolve(foo), "foo");
+    /// }
+    /// drop(guard);
+    ///
+    /// paracord.get_or_intern("bar");
+    /// ```
+    #[must_use]
+    pub fn guard(&self) -> ReadGuard<'_, S> {
+        ReadGuard {
+            paracord: self,
+            inner: self.inner.guard(),
+        }
+    }
+}
+
+impl<'a, S> ReadGuard<'a, S> {
+    /// Get the [`ParaCord`] this guard reads from.
+    #[must_use]
+    pub fn paracord(&self) -> &'a ParaCord<S> {
+        self.paracord
+    }
+
+    /// Try and resolve the string associated with this [`Key`].
+    ///
+    /// See [`ParaCord::try_resolve`].
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&'a str> {
+        self.paracord.try_resolve(key)
+    }
+
+    /// Resolve the string associated with this [`Key`].
+    ///
+    /// # Panics
+    /// See [`ParaCord::resolve`].
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &'a str {
+        self.paracord.resolve(key)
+    }
+}
+
+impl<S: BuildHasher> ReadGuard<'_, S> {
+    /// Try and get the [`Key`] associated with the given string.
+    /// Returns [`None`] if not found.
+    ///
+    /// See [`ParaCord::get`].
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        self.inner.get(s.as_bytes())
+    }
+}
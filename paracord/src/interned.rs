@@ -0,0 +1,186 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::global::GlobalKey;
+use crate::DefaultKey;
+
+/// A cheap, copyable handle to a string in a global interner.
+///
+/// `Interned` wraps a key created by [`custom_key`](crate::custom_key) (by default [`DefaultKey`]),
+/// but behaves like a `&'static str`: it derefs to `str`, implements [`Borrow<str>`], and hashes
+/// and orders by the string contents, so it can be used as a drop-in replacement for
+/// strings in maps and sorted collections. Equality is still a single key comparison.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use paracord::Interned;
+///
+/// let foo: Interned = Interned::new("foo");
+/// assert_eq!(foo, "foo");
+/// assert_eq!(foo.len(), 3);
+///
+/// let set = HashSet::from([foo, Interned::new("bar")]);
+/// assert!(set.contains("foo"));
+///
+/// let mut sorted: Vec<Interned> = vec![Interned::new("b"), Interned::new("a")];
+/// sorted.sort();
+/// assert_eq!(sorted, ["a", "b"]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Interned<K = DefaultKey>(K);
+
+impl<K: GlobalKey> Interned<K> {
+    /// Intern the string, and return a handle to it.
+    #[inline]
+    pub fn new(s: &str) -> Self {
+        Self(K::new(s))
+    }
+
+    /// Get a handle to the string, if it has already been interned.
+    #[inline]
+    pub fn try_new_existing(s: &str) -> Option<Self> {
+        K::try_new_existing(s).map(Self)
+    }
+
+    /// Get the interned string.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        self.0.as_str()
+    }
+}
+
+impl<K> Interned<K> {
+    /// Get the underlying key.
+    #[inline]
+    pub fn key(self) -> K {
+        self.0
+    }
+}
+
+impl<K: GlobalKey> From<K> for Interned<K> {
+    #[inline]
+    fn from(key: K) -> Self {
+        Self(key)
+    }
+}
+
+impl<K: GlobalKey> From<&str> for Interned<K> {
+    #[inline]
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl<K: GlobalKey> Deref for Interned<K> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<K: GlobalKey> AsRef<str> for Interned<K> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<K: GlobalKey> Borrow<str> for Interned<K> {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Hashes the string contents, to be consistent with [`Borrow<str>`].
+impl<K: GlobalKey> Hash for Interned<K> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+/// Orders by the string contents, not by allocation order.
+impl<K: GlobalKey> Ord for Interned<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.0 == other.0 {
+            Ordering::Equal
+        } else {
+            self.as_str().cmp(other.as_str())
+        }
+    }
+}
+
+impl<K: GlobalKey> PartialOrd for Interned<K> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: GlobalKey> PartialEq<str> for Interned<K> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<K: GlobalKey> PartialEq<&str> for Interned<K> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<K: GlobalKey> fmt::Debug for Interned<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl<K: GlobalKey> fmt::Display for Interned<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Serialize the handle as the interned string
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<K: GlobalKey> serde::Serialize for Interned<K> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Deserializes and interns a string
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, K: GlobalKey> serde::Deserialize<'de> for Interned<K> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct InternedVisitor<K>(core::marker::PhantomData<K>);
+
+        impl<K: GlobalKey> serde::de::Visitor<'_> for InternedVisitor<K> {
+            type Value = Interned<K>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string value")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(Interned::new(v))
+            }
+        }
+
+        deserializer.deserialize_str(InternedVisitor(core::marker::PhantomData))
+    }
+}
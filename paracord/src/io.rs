@@ -0,0 +1,85 @@
+//! Interning strings read from IO.
+
+use std::hash::BuildHasher;
+use std::io::{self, BufRead};
+
+use crate::{Key, ParaCord};
+
+/// Intern every line of `reader`, returning an iterator of their keys.
+///
+/// Line endings (`\n` or `\r\n`) are removed before interning. Lines are read into a reused buffer,
+/// so no allocations are made for lines that are already interned.
+///
+/// The iterator returns an error if reading fails, or if a line is not valid UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::ParaCord;
+///
+/// let paracord = ParaCord::default();
+/// let words = "foo\n  bar \r\nfoo\n";
+///
+/// let keys: Vec<_> = paracord::io::intern_lines(&paracord, words.as_bytes())
+///     .trim(true)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+///
+/// assert_eq!(keys.len(), 3);
+/// assert_eq!(keys[0], keys[2]);
+/// assert_eq!(paracord.resolve(keys[1]), "bar");
+/// assert_eq!(paracord.len(), 2);
+/// ```
+pub fn intern_lines<R: BufRead, S: BuildHasher>(
+    paracord: &ParaCord<S>,
+    reader: R,
+) -> InternLines<'_, R, S> {
+    InternLines {
+        paracord,
+        reader,
+        buf: String::new(),
+        trim: false,
+    }
+}
+
+/// Iterator returned by [`intern_lines`].
+pub struct InternLines<'a, R, S> {
+    paracord: &'a ParaCord<S>,
+    reader: R,
+    buf: String,
+    trim: bool,
+}
+
+impl<R, S> std::fmt::Debug for InternLines<'_, R, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InternLines")
+            .field("trim", &self.trim)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R, S> InternLines<'_, R, S> {
+    /// Whether to remove leading and trailing whitespace from each line before interning.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+}
+
+impl<R: BufRead, S: BuildHasher> Iterator for InternLines<'_, R, S> {
+    type Item = io::Result<Key>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buf.clear();
+        match self.reader.read_line(&mut self.buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                let line = self.buf.strip_suffix('\n').unwrap_or(&self.buf);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                let line = if self.trim { line.trim() } else { line };
+                Some(Ok(self.paracord.get_or_intern(line)))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
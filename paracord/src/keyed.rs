@@ -0,0 +1,294 @@
+//! Interners with a custom key type.
+//!
+//! See [`KeyedParaCord`] and [`TypedParaCord`].
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::marker::PhantomData;
+
+use foldhash::fast::RandomState;
+
+use crate::{InternKey, Key, ParaCord};
+
+/// Returned when a string cannot be interned because its key would not fit in the key type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct KeySpaceExhausted;
+
+impl fmt::Display for KeySpaceExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("no keys left in the key type")
+    }
+}
+
+impl std::error::Error for KeySpaceExhausted {}
+
+/// A [`ParaCord`] that returns keys of type `K`.
+///
+/// This lets you pick a key type that fits the expected number of strings, eg [`NonZeroU16`](std::num::NonZeroU16)
+/// to pack keys into a struct more tightly. Keys are still allocated in order, so `K` limits how
+/// many strings can be interned.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroU16;
+/// use paracord::keyed::KeyedParaCord;
+///
+/// let paracord = KeyedParaCord::<NonZeroU16>::default();
+///
+/// let foo: NonZeroU16 = paracord.get_or_intern("foo");
+/// let bar = paracord.get_or_intern("bar");
+///
+/// assert_eq!(paracord.get("foo"), Some(foo));
+/// assert_eq!(paracord.resolve(bar), "bar");
+/// assert_eq!(std::mem::size_of::<Option<NonZeroU16>>(), 2);
+/// ```
+pub struct KeyedParaCord<K, S = RandomState> {
+    inner: ParaCord<S>,
+    _key: PhantomData<fn() -> K>,
+}
+
+impl<K: InternKey, S> fmt::Debug for KeyedParaCord<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K> Default for KeyedParaCord<K> {
+    fn default() -> Self {
+        Self::with_hasher(RandomState::default())
+    }
+}
+
+impl<K, S: BuildHasher> KeyedParaCord<K, S> {
+    /// Create a new empty `KeyedParaCord` instance with the given hasher state.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            inner: ParaCord::with_hasher(hasher),
+            _key: PhantomData,
+        }
+    }
+}
+
+impl<K, S> KeyedParaCord<K, S> {
+    /// Get the interner that holds the strings, which uses [`Key`]s.
+    #[must_use]
+    pub fn paracord(&self) -> &ParaCord<S> {
+        &self.inner
+    }
+
+    /// Determine how many strings have been allocated
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Determine if no strings have been allocated
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<K: InternKey, S> KeyedParaCord<K, S> {
+    fn to_key(key: K) -> Option<Key> {
+        Key::try_from_index(key.into_index())
+    }
+
+    fn from_key(key: Key) -> Option<K> {
+        K::try_from_index(key.into_index())
+    }
+
+    /// Try and resolve the string associated with this key.
+    ///
+    /// This can only return `None` if given a key that was allocated from
+    /// a different instance, but it might return an arbitrary string as well.
+    #[must_use]
+    pub fn try_resolve(&self, key: K) -> Option<&str> {
+        self.inner.try_resolve(Self::to_key(key)?)
+    }
+
+    /// Resolve the string associated with this key.
+    ///
+    /// # Panics
+    /// This can panic if given a key that was allocated from
+    /// a different instance, but it might return an arbitrary string
+    /// as well.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: K) -> &str {
+        self.try_resolve(key)
+            .expect("key should be allocated by this paracord instance")
+    }
+
+    /// Get an iterator over every (`K`, `&str`) pair
+    /// that has been allocated in this instance.
+    pub fn iter(&self) -> impl Iterator<Item = (K, &str)> {
+        self.inner
+            .iter()
+            .filter_map(|(key, s)| Some((Self::from_key(key)?, s)))
+    }
+}
+
+impl<K: InternKey, S: BuildHasher> KeyedParaCord<K, S> {
+    /// Try and get the key associated with the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<K> {
+        Self::from_key(self.inner.get(s)?)
+    }
+
+    /// Try and get the key associated with the given string.
+    /// Allocates a new key if not found.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KeySpaceExhausted`] if the string is new and its key would not fit in `K`.
+    /// If other threads are interning concurrently, the string might still be interned.
+    pub fn try_get_or_intern(&self, s: &str) -> Result<K, KeySpaceExhausted> {
+        if let Some(key) = self.inner.get(s) {
+            return Self::from_key(key).ok_or(KeySpaceExhausted);
+        }
+        if K::try_from_index(self.inner.inner.next_index()).is_none() {
+            return Err(KeySpaceExhausted);
+        }
+        Self::from_key(self.inner.get_or_intern(s)).ok_or(KeySpaceExhausted)
+    }
+
+    /// Try and get the key associated with the given string.
+    /// Allocates a new key if not found.
+    ///
+    /// # Panics
+    ///
+    /// If the string is new and its key would not fit in `K`.
+    #[track_caller]
+    pub fn get_or_intern(&self, s: &str) -> K {
+        self.try_get_or_intern(s)
+            .expect("key space of the key type should not be exhausted")
+    }
+}
+
+/// A [`Key`] tagged with a marker type `M`, so that keys from different instances
+/// cannot be mixed up.
+///
+/// Returned by [`TypedParaCord`]. Like [`Key`], `Option<TypedKey<M>>` is 32 bits.
+pub struct TypedKey<M> {
+    key: Key,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<M> TypedKey<M> {
+    /// Tag the key with the marker type.
+    ///
+    /// This does not check that the key was allocated by an instance with this marker type.
+    #[inline]
+    #[must_use]
+    pub fn new(key: Key) -> Self {
+        Self {
+            key,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get the untagged key.
+    #[inline]
+    #[must_use]
+    pub fn key(self) -> Key {
+        self.key
+    }
+}
+
+impl<M> Clone for TypedKey<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for TypedKey<M> {}
+
+impl<M> PartialEq for TypedKey<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<M> Eq for TypedKey<M> {}
+
+impl<M> PartialOrd for TypedKey<M> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<M> Ord for TypedKey<M> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl<M> Hash for TypedKey<M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+impl<M> fmt::Debug for TypedKey<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedKey")
+            .field(&self.key.into_repr())
+            .finish()
+    }
+}
+
+impl<M> InternKey for TypedKey<M> {
+    #[inline]
+    fn into_index(self) -> usize {
+        self.key.into_index()
+    }
+
+    #[inline]
+    fn try_from_index(i: usize) -> Option<Self> {
+        Key::try_from_index(i).map(Self::new)
+    }
+}
+
+/// A [`ParaCord`] that returns [`TypedKey`]s tagged with the marker type `M`.
+///
+/// Giving each instance its own marker type means its keys cannot be resolved against another
+/// instance by accident, without needing a global interner like [`custom_key`](crate::custom_key).
+///
+/// # Examples
+///
+/// ```
+/// use paracord::keyed::{TypedKey, TypedParaCord};
+///
+/// struct Users;
+/// struct Groups;
+///
+/// let users = TypedParaCord::<Users>::default();
+/// let groups = TypedParaCord::<Groups>::default();
+///
+/// let alice: TypedKey<Users> = users.get_or_intern("alice");
+/// let admins: TypedKey<Groups> = groups.get_or_intern("admins");
+///
+/// assert_eq!(users.resolve(alice), "alice");
+/// assert_eq!(groups.resolve(admins), "admins");
+/// ```
+///
+/// Mixing up the keys is a compile error:
+///
+/// ```compile_fail
+/// use paracord::keyed::TypedParaCord;
+///
+/// struct Users;
+/// struct Groups;
+///
+/// let users = TypedParaCord::<Users>::default();
+/// let groups = TypedParaCord::<Groups>::default();
+///
+/// let alice = users.get_or_intern("alice");
+/// groups.resolve(alice);
+/// ```
+pub type TypedParaCord<M, S = RandomState> = KeyedParaCord<TypedKey<M>, S>;
@@ -0,0 +1,172 @@
+//! An interner for more than `2^32` strings.
+//!
+//! See [`ParaCord64`].
+
+use std::hash::BuildHasher;
+use std::sync::OnceLock;
+
+use foldhash::fast::RandomState;
+
+use crate::{slice, Key, LargeKey};
+
+/// The number of segments. Each segment has its own 32 bit key space.
+const SEGMENTS: usize = 256;
+
+/// A variant of [`ParaCord`](crate::ParaCord) that returns 64 bit [`LargeKey`]s, for workloads
+/// that intern more than `2^32` unique strings.
+///
+/// Strings are partitioned by hash into 256 segments, each with its own 32 bit key space,
+/// which are created on first use. A [`LargeKey`] holds the segment in its upper bits,
+/// so keys are not ordered by allocation.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::large::ParaCord64;
+///
+/// let paracord = ParaCord64::default();
+///
+/// let foo = paracord.get_or_intern("foo");
+/// let bar = paracord.get_or_intern("bar");
+///
+/// assert_ne!(foo, bar);
+/// assert_eq!(paracord.get("foo"), Some(foo));
+/// assert_eq!(paracord.resolve(foo), "foo");
+/// assert_eq!(std::mem::size_of::<Option<paracord::LargeKey>>(), 8);
+/// ```
+pub struct ParaCord64<S = RandomState> {
+    segments: Box<[OnceLock<slice::ParaCord<u8, S>>]>,
+    hasher: S,
+}
+
+impl<S> std::fmt::Debug for ParaCord64<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl Default for ParaCord64 {
+    fn default() -> Self {
+        Self::with_hasher(RandomState::default())
+    }
+}
+
+impl<S> ParaCord64<S> {
+    /// Create a new `ParaCord64` instance with the given hasher state.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            segments: (0..SEGMENTS).map(|_| OnceLock::new()).collect(),
+            hasher,
+        }
+    }
+
+    /// Determine which segment a hash belongs to.
+    ///
+    /// This uses the middle bits of the hash, which are not used to pick a bucket or a shard
+    /// within the segment.
+    #[inline]
+    fn segment_for_hash(hash: u64) -> usize {
+        (hash >> 32) as usize % SEGMENTS
+    }
+
+    #[inline]
+    fn make_key(segment: usize, key: Key) -> LargeKey {
+        LargeKey::from_index(((segment as u64) << 32) | key.into_repr() as u64)
+    }
+
+    #[inline]
+    fn split_key(key: LargeKey) -> (usize, Option<Key>) {
+        let i = key.into_repr();
+        ((i >> 32) as usize, Key::try_from_repr(i as u32))
+    }
+
+    fn segment(&self, i: usize) -> Option<&slice::ParaCord<u8, S>> {
+        self.segments.get(i)?.get()
+    }
+
+    /// Try and resolve the string associated with this [`LargeKey`].
+    ///
+    /// This can only return `None` if given a key that was allocated from
+    /// a different [`ParaCord64`] instance, but it might return an arbitrary string as well.
+    #[must_use]
+    pub fn try_resolve(&self, key: LargeKey) -> Option<&str> {
+        let (segment, key) = Self::split_key(key);
+        let b = self.segment(segment)?.try_resolve(key?)?;
+        // Safety: we insert only strings, so it's valid utf8
+        Some(unsafe { core::str::from_utf8_unchecked(b) })
+    }
+
+    /// Resolve the string associated with this [`LargeKey`].
+    ///
+    /// # Panics
+    /// This can panic if given a key that was allocated from
+    /// a different [`ParaCord64`] instance, but it might return an arbitrary string
+    /// as well.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: LargeKey) -> &str {
+        self.try_resolve(key)
+            .expect("key should be allocated by this paracord instance")
+    }
+
+    /// Determine how many strings have been allocated
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.segments
+            .iter()
+            .filter_map(OnceLock::get)
+            .map(slice::ParaCord::len)
+            .sum()
+    }
+
+    /// Determine if no strings have been allocated
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.segments
+            .iter()
+            .filter_map(OnceLock::get)
+            .all(slice::ParaCord::is_empty)
+    }
+
+    /// Get an iterator over every ([`LargeKey`], `&str`) pair
+    /// that has been allocated in this [`ParaCord64`] instance.
+    ///
+    /// Strings are grouped by segment, so they are not in allocation order.
+    pub fn iter(&self) -> impl Iterator<Item = (LargeKey, &str)> {
+        self.segments
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| Some((i, s.get()?)))
+            .flat_map(|(i, segment)| {
+                segment.iter().map(move |(key, b)| {
+                    // Safety: we insert only strings, so it's valid utf8
+                    let s = unsafe { core::str::from_utf8_unchecked(b) };
+                    (Self::make_key(i, key), s)
+                })
+            })
+    }
+}
+
+impl<S: BuildHasher> ParaCord64<S> {
+    /// Try and get the [`LargeKey`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<LargeKey> {
+        let hash = self.hasher.hash_one(s.as_bytes());
+        let i = Self::segment_for_hash(hash);
+        let key = self.segment(i)?.get_hashed(s.as_bytes(), hash)?;
+        Some(Self::make_key(i, key))
+    }
+}
+
+impl<S: BuildHasher + Clone> ParaCord64<S> {
+    /// Try and get the [`LargeKey`] associated with the given string.
+    /// Allocates a new key if not found.
+    pub fn get_or_intern(&self, s: &str) -> LargeKey {
+        let hash = self.hasher.hash_one(s.as_bytes());
+        let i = Self::segment_for_hash(hash);
+        let segment =
+            self.segments[i].get_or_init(|| slice::ParaCord::with_hasher(self.hasher.clone()));
+        Self::make_key(i, segment.get_or_intern_hashed(s.as_bytes(), hash))
+    }
+}
@@ -0,0 +1,249 @@
+//! A single-threaded interner, which is merged into a shared [`ParaCord`] in bulk.
+//!
+//! It can also build a [`ParaCord`] or [`FrozenParaCord`] from scratch, without synchronizing on
+//! every string.
+//!
+//! See [`LocalInterner`].
+
+use core::fmt;
+use std::hash::BuildHasher;
+
+use foldhash::fast::RandomState;
+use hashbrown::HashTable;
+
+use crate::collections::RemapTable;
+use crate::frozen::{self, FrozenParaCord};
+use crate::{Key, ParaCord};
+
+/// An interner owned by a single thread, which needs no synchronization.
+///
+/// Threads that work independently for a long time can intern into their own [`LocalInterner`],
+/// and only [`publish`](LocalInterner::publish) the strings into the shared [`ParaCord`] when they
+/// need global keys, eg at batch boundaries.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::local::LocalInterner;
+/// use paracord::ParaCord;
+///
+/// let shared = ParaCord::default();
+///
+/// let remaps: Vec<_> = std::thread::scope(|s| {
+///     let workers: Vec<_> = (0..4)
+///         .map(|i| {
+///             let shared = &shared;
+///             s.spawn(move || {
+///                 let mut local = LocalInterner::new();
+///                 let key = local.get_or_intern(&format!("worker {i}"));
+///                 (key, local.publish(shared))
+///             })
+///         })
+///         .collect();
+///     workers.into_iter().map(|w| w.join().unwrap()).collect()
+/// });
+///
+/// for (i, (key, remap)) in remaps.into_iter().enumerate() {
+///     let global = remap.get(key).unwrap();
+///     assert_eq!(shared.resolve(global), format!("worker {i}"));
+/// }
+/// ```
+pub struct LocalInterner<S = RandomState> {
+    /// Every string, concatenated.
+    buf: String,
+    /// The (start, end) of each string in `buf`, indexed by key.
+    spans: Vec<(usize, usize)>,
+    /// Indices into `spans`.
+    table: HashTable<u32>,
+    hasher: S,
+}
+
+impl<S> fmt::Debug for LocalInterner<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<S: Default> Default for LocalInterner<S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl LocalInterner {
+    /// Create a new empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> LocalInterner<S> {
+    /// Create a new empty interner, with the given hasher.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            buf: String::new(),
+            spans: Vec::new(),
+            table: HashTable::new(),
+            hasher,
+        }
+    }
+
+    fn str(&self, i: usize) -> &str {
+        let (start, end) = self.spans[i];
+        &self.buf[start..end]
+    }
+
+    /// Determine how many strings have been interned.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Determine if no strings have been interned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Try and resolve the string associated with this local [`Key`].
+    ///
+    /// Returns [`None`] if the key was not allocated by this interner.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&str> {
+        let i = key.into_repr() as usize;
+        (i < self.spans.len()).then(|| self.str(i))
+    }
+
+    /// Resolve the string associated with this local [`Key`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key was not allocated by this interner.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &str {
+        self.try_resolve(key)
+            .expect("key should be allocated by this interner")
+    }
+
+    /// Get an iterator over every (local [`Key`], `&str`) pair, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &str)> {
+        (0..self.spans.len()).map(|i| (Key::from_index(i), self.str(i)))
+    }
+
+    /// Remove every string, keeping the allocated memory.
+    ///
+    /// Keys allocated before the clear are reused for different strings.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.spans.clear();
+        self.table.clear();
+    }
+
+    /// Build a [`FrozenParaCord`] with every string, keeping their keys.
+    ///
+    /// This suits loading a large read-only vocabulary once at startup, without any
+    /// synchronization while it is being built.
+    ///
+    /// The strings and hash table are leaked, so this should only be called once per vocabulary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the strings are larger than `u32::MAX` bytes in total.
+    #[must_use]
+    pub fn finish_frozen(&self) -> FrozenParaCord {
+        frozen::leak_unique(self.iter().map(|(_, s)| s))
+    }
+}
+
+impl<S: BuildHasher> LocalInterner<S> {
+    /// Try and get the local [`Key`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        let hash = self.hasher.hash_one(s);
+        let i = self.table.find(hash, |&i| self.str(i as usize) == s)?;
+        Some(Key::from_index(*i as usize))
+    }
+
+    /// Try and get the local [`Key`] associated with the given string.
+    /// Allocates a new key if not found.
+    pub fn get_or_intern(&mut self, s: &str) -> Key {
+        let Self {
+            buf,
+            spans,
+            table,
+            hasher,
+        } = self;
+
+        let hash = hasher.hash_one(s);
+        let entry = table.entry(
+            hash,
+            |&i| {
+                let (start, end) = spans[i as usize];
+                &buf[start..end] == s
+            },
+            |&i| {
+                let (start, end) = spans[i as usize];
+                hasher.hash_one(&buf[start..end])
+            },
+        );
+        let i = *entry
+            .or_insert_with(|| {
+                let i = u32::try_from(spans.len()).expect("too many strings were interned");
+                spans.push((buf.len(), buf.len() + s.len()));
+                buf.push_str(s);
+                i
+            })
+            .get();
+        Key::from_index(i as usize)
+    }
+
+    /// Intern every string into the shared instance.
+    ///
+    /// The returned [`RemapTable`] maps each local key to its key in the shared instance.
+    /// This interner is left unchanged, so it can keep being used, or be [`clear`](Self::clear)ed
+    /// to start the next batch.
+    pub fn publish<S2: BuildHasher>(&self, paracord: &ParaCord<S2>) -> RemapTable {
+        let mut remap = RemapTable::with_capacity(self.len());
+        for (local, s) in self.iter() {
+            remap.insert(local, paracord.get_or_intern(s));
+        }
+        remap
+    }
+
+    /// Convert into a shared [`ParaCord`], keeping every key.
+    ///
+    /// Building a large dictionary in a `LocalInterner` on a loader thread avoids all the
+    /// synchronization of interning into a shared instance, and the result can then be served
+    /// concurrently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::local::LocalInterner;
+    ///
+    /// let loader = std::thread::spawn(|| {
+    ///     let mut builder = LocalInterner::new();
+    ///     for word in ["foo", "bar", "foo"] {
+    ///         builder.get_or_intern(word);
+    ///     }
+    ///     builder
+    /// });
+    ///
+    /// let builder = loader.join().unwrap();
+    /// let bar = builder.get("bar").unwrap();
+    ///
+    /// let paracord = builder.finish();
+    /// assert_eq!(paracord.len(), 2);
+    /// assert_eq!(paracord.get("bar"), Some(bar));
+    /// ```
+    #[must_use]
+    pub fn finish(self) -> ParaCord<S> {
+        let mut paracord = ParaCord::with_capacity_and_hasher(self.len(), self.hasher);
+        for (start, end) in self.spans {
+            paracord.inner.push_mut(&self.buf.as_bytes()[start..end]);
+        }
+        paracord
+    }
+}
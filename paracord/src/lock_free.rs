@@ -0,0 +1,113 @@
+use std::hash::BuildHasher;
+
+use crate::{slice, Key};
+
+/// A variant of [`ParaCord`](crate::ParaCord) that never takes a lock.
+///
+/// Lookups never wait for inserts, which suits read-heavy workloads with many threads.
+/// See [`slice::LockFreeParaCord`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::LockFreeParaCord;
+///
+/// let paracord = LockFreeParaCord::default();
+///
+/// let foo = paracord.get_or_intern("foo");
+/// let bar = paracord.get_or_intern("bar");
+///
+/// assert_ne!(foo, bar);
+/// assert_eq!(paracord.get("foo"), Some(foo));
+/// assert_eq!(paracord.resolve(foo), "foo");
+/// assert_eq!(paracord.resolve(bar), "bar");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "lock-free")))]
+pub struct LockFreeParaCord<S = foldhash::fast::RandomState> {
+    inner: slice::LockFreeParaCord<u8, S>,
+}
+
+impl<S> std::fmt::Debug for LockFreeParaCord<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl Default for LockFreeParaCord {
+    fn default() -> Self {
+        Self {
+            inner: slice::LockFreeParaCord::default(),
+        }
+    }
+}
+
+impl<S> LockFreeParaCord<S> {
+    /// Create a new `LockFreeParaCord` instance with the given hasher state.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            inner: slice::LockFreeParaCord::with_hasher(hasher),
+        }
+    }
+
+    /// Try and resolve the string associated with this [`Key`].
+    ///
+    /// This can return [`None`] if the key was not allocated by this instance.
+    #[inline]
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&str> {
+        // Safety: we only insert strings, so it's definitely valid utf8
+        self.inner
+            .try_resolve(key)
+            .map(|s| unsafe { core::str::from_utf8_unchecked(s) })
+    }
+
+    /// Resolve the string associated with this [`Key`].
+    ///
+    /// # Panics
+    /// This can panic if the key was not allocated by this instance.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &str {
+        // Safety: we only insert strings, so it's definitely valid utf8
+        unsafe { core::str::from_utf8_unchecked(self.inner.resolve(key)) }
+    }
+
+    /// Determine how many strings have been allocated
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Determine if no strings have been allocated
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Get an iterator over every ([`Key`], `&str`) pair
+    /// that has been allocated in this instance, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &str)> {
+        self.inner.iter().map(|(k, s)| {
+            // Safety: we only insert strings, so it's definitely valid utf8
+            (k, unsafe { core::str::from_utf8_unchecked(s) })
+        })
+    }
+}
+
+impl<S: BuildHasher> LockFreeParaCord<S> {
+    /// Try and get the [`Key`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        self.inner.get(s.as_bytes())
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Allocates a new key if not found.
+    #[inline]
+    pub fn get_or_intern(&self, s: &str) -> Key {
+        self.inner.get_or_intern(s.as_bytes())
+    }
+}
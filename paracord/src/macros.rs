@@ -1,3 +1,7 @@
+use core::fmt;
+
+use crate::{Key, ParaCord, SendKey};
+
 /// Create a new custom key, with a static-backed allocator.
 ///
 /// See [`DefaultKey`](crate::DefaultKey) for docs on what this macro generates.
@@ -42,25 +46,152 @@
 ///     let hasher: FixedState = FixedState::with_seed(1);
 /// );
 /// ```
+///
+/// ## Create a custom key with a hasher seeded at runtime
+///
+/// The init expression can take a [`global::Config`](crate::global::Config), which gives a seed
+/// from [`global::set_seed`](crate::global::set_seed) or from OS entropy. It runs when the key is
+/// first used.
+///
+/// ```
+/// use foldhash::quality::FixedState;
+///
+/// paracord::custom_key!(
+///     /// My custom key
+///     pub struct MyKey;
+///
+///     let hasher: FixedState = |config| FixedState::with_seed(config.seed());
+/// );
+/// ```
+///
+/// The hasher can also be chosen by the application with `init_with`, before any keys are created.
+///
+/// ```
+/// use foldhash::quality::FixedState;
+///
+/// paracord::custom_key!(
+///     /// My custom key
+///     pub struct MyKey;
+///
+///     let hasher: FixedState;
+/// );
+///
+/// MyKey::init_with(FixedState::with_seed(42)).unwrap();
+/// MyKey::new("foo");
+/// assert!(MyKey::init_with(FixedState::with_seed(43)).is_err());
+/// ```
+///
+/// ## Register the key in the global registry
+///
+/// Registered keys are listed by [`global::registry`](crate::global::registry).
+///
+/// ```
+/// paracord::custom_key!(
+///     /// My custom key
+///     pub struct MyKey;
+///
+///     register;
+/// );
+/// ```
+///
+/// ## Generate additional trait impls
+///
+/// The following traits can be implemented for the key:
+/// * `FromStr` and `TryFrom`, which intern the string and never fail.
+/// * `JsonSchema`, which describes the key as a string. Requires the `schemars` feature.
+/// * `Arbitrary`, which interns an arbitrary string. Requires the `arbitrary` feature.
+///
+/// ```
+/// paracord::custom_key!(
+///     /// My custom key
+///     pub struct MyKey;
+///
+///     impls: [FromStr, TryFrom];
+/// );
+///
+/// let key: MyKey = "foo".parse().unwrap();
+/// assert_eq!(key, MyKey::try_from("foo").unwrap());
+/// ```
 #[macro_export]
 macro_rules! custom_key {
-    ($(#[$($meta:meta)*])* $vis:vis struct $key:ident $(;)?) => {
+    (@parse $head:tt $hasher:tt $register:tt $impls:tt) => {
+        $crate::custom_key!(@impl $head $hasher $register $impls);
+    };
+    (@parse $head:tt $hasher:tt $register:tt $impls:tt let hasher: $s:ty = |$config:pat_param| $init:expr $(; $($rest:tt)*)?) => {
+        $crate::custom_key!(@parse $head [$s = |$config| $init] $register $impls $($($rest)*)?);
+    };
+    (@parse $head:tt $hasher:tt $register:tt $impls:tt let hasher: $s:ty = $init:expr $(; $($rest:tt)*)?) => {
+        $crate::custom_key!(@parse $head [$s = |_| $init] $register $impls $($($rest)*)?);
+    };
+    (@parse $head:tt $hasher:tt $register:tt $impls:tt let hasher: $s:ty $(; $($rest:tt)*)?) => {
         $crate::custom_key!(
-            $(#[$($meta)*])*
-            $vis struct $key;
-
-            let hasher: $crate::__private::RandomState;
+            @parse $head [$s = |_| <$s as ::core::default::Default>::default()] $register $impls $($($rest)*)?
         );
     };
-    ($(#[$($meta:meta)*])* $vis:vis struct $key:ident; let hasher: $s:ty $(;)?) => {
-        $crate::custom_key!(
-            $(#[$($meta)*])*
-            $vis struct $key;
+    (@parse $head:tt $hasher:tt $register:tt $impls:tt register $(; $($rest:tt)*)?) => {
+        $crate::custom_key!(@parse $head $hasher [register] $impls $($($rest)*)?);
+    };
+    (@parse $head:tt $hasher:tt $register:tt $impls:tt impls: [$($impl:ident),* $(,)?] $(; $($rest:tt)*)?) => {
+        $crate::custom_key!(@parse $head $hasher $register [$($impl)*] $($($rest)*)?);
+    };
+    (@register $key:ident []) => {};
+    (@register $key:ident [register]) => {
+        $crate::__private::register(::core::any::type_name::<$key>(), || {
+            $key::cell().get().map(|p| p as &'static dyn $crate::__private::Registered)
+        });
+    };
+    (@derive $key:ident FromStr) => {
+        impl ::core::str::FromStr for $key {
+            type Err = ::core::convert::Infallible;
 
-            let hasher: $s = <$s as ::core::default::Default>::default();
-        );
+            #[inline]
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                ::core::result::Result::Ok(Self::new(s))
+            }
+        }
+    };
+    (@derive $key:ident TryFrom) => {
+        #[allow(clippy::infallible_try_from)]
+        impl ::core::convert::TryFrom<&str> for $key {
+            type Error = ::core::convert::Infallible;
+
+            #[inline]
+            fn try_from(s: &str) -> ::core::result::Result<Self, Self::Error> {
+                ::core::result::Result::Ok(Self::new(s))
+            }
+        }
     };
-    ($(#[$($meta:meta)*])* $vis:vis struct $key:ident; let hasher: $s:ty = $init:expr $(;)?) => {
+    (@derive $key:ident JsonSchema) => {
+        impl $crate::__private::schemars::JsonSchema for $key {
+            fn inline_schema() -> bool {
+                true
+            }
+
+            fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed("String")
+            }
+
+            fn json_schema(
+                generator: &mut $crate::__private::schemars::SchemaGenerator,
+            ) -> $crate::__private::schemars::Schema {
+                <::std::string::String as $crate::__private::schemars::JsonSchema>::json_schema(generator)
+            }
+        }
+    };
+    (@derive $key:ident Arbitrary) => {
+        impl<'a> $crate::__private::arbitrary::Arbitrary<'a> for $key {
+            fn arbitrary(
+                u: &mut $crate::__private::arbitrary::Unstructured<'a>,
+            ) -> $crate::__private::arbitrary::Result<Self> {
+                <&str as $crate::__private::arbitrary::Arbitrary<'a>>::arbitrary(u).map(Self::new)
+            }
+
+            fn size_hint(depth: usize) -> (usize, ::core::option::Option<usize>) {
+                <&str as $crate::__private::arbitrary::Arbitrary<'a>>::size_hint(depth)
+            }
+        }
+    };
+    (@impl [$(#[$($meta:meta)*])* $vis:vis struct $key:ident] [$s:ty = |$config:pat_param| $init:expr] $register:tt [$($impl:ident)*]) => {
         $(#[$($meta)*])*
         #[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone, Copy)]
         #[repr(transparent)]
@@ -68,9 +199,38 @@ macro_rules! custom_key {
 
         impl $key {
             #[inline]
-            fn paracord() -> &'static $crate::ParaCord<$s> {
+            fn cell() -> &'static ::std::sync::OnceLock<$crate::ParaCord<$s>> {
                 static S: ::std::sync::OnceLock<$crate::ParaCord<$s>> = ::std::sync::OnceLock::new();
-                S.get_or_init(|| $crate::ParaCord::with_hasher($init))
+                &S
+            }
+
+            #[inline]
+            fn paracord() -> &'static $crate::ParaCord<$s> {
+                Self::cell().get_or_init(|| Self::create(::core::option::Option::None))
+            }
+
+            fn create(hasher: ::core::option::Option<$s>) -> $crate::ParaCord<$s> {
+                $crate::custom_key!(@register $key $register);
+                let hasher = hasher.unwrap_or_else(|| {
+                    let $config: &$crate::global::Config =
+                        &$crate::global::Config::new(::core::any::type_name::<$key>());
+                    $init
+                });
+                $crate::ParaCord::with_hasher(hasher)
+            }
+
+            /// Initialize the global interner with the given hasher.
+            ///
+            /// By default, the interner is initialized on first use, with the hasher from the
+            /// macro definition. This must be called before any keys are created.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`AlreadyInitialized`]($crate::global::AlreadyInitialized) if the interner
+            /// was already initialized, by a previous call or by creating a key.
+            #[allow(dead_code)]
+            pub fn init_with(hasher: $s) -> ::core::result::Result<(), $crate::global::AlreadyInitialized> {
+                $crate::__private::init(Self::cell(), || Self::create(::core::option::Option::Some(hasher)))
             }
 
             /// Try and get the key associated with the given string.
@@ -89,9 +249,10 @@ macro_rules! custom_key {
 
             /// Resolve the string associated with this key.
             #[inline]
+            #[must_use]
             pub fn as_str(&self) -> &'static str {
                 // Safety: The key can only be constructed from the static paracord,
-                // and the paracord will never be reset.
+                // and the paracord will never be cleared.
                 unsafe { Self::paracord().resolve_unchecked(self.0) }
             }
 
@@ -110,6 +271,23 @@ macro_rules! custom_key {
             }
         }
 
+        impl $crate::global::GlobalKey for $key {
+            #[inline]
+            fn new(s: &str) -> Self {
+                Self::new(s)
+            }
+
+            #[inline]
+            fn try_new_existing(s: &str) -> Option<Self> {
+                Self::try_new_existing(s)
+            }
+
+            #[inline]
+            fn as_str(&self) -> &'static str {
+                Self::as_str(self)
+            }
+        }
+
         /// Displays the string that this key represents.
         impl ::core::fmt::Display for $key {
             fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
@@ -133,9 +311,115 @@ macro_rules! custom_key {
         }
 
         $crate::__private::serde::custom_key_serde!($key);
+
+        $($crate::custom_key!(@derive $key $impl);)*
+    };
+    ($(#[$($meta:meta)*])* $vis:vis struct $key:ident $(; $($rest:tt)*)?) => {
+        $crate::custom_key!(
+            @parse
+            [$(#[$($meta)*])* $vis struct $key]
+            [$crate::__private::RandomState = |_| <$crate::__private::RandomState as ::core::default::Default>::default()]
+            []
+            []
+            $($($rest)*)?
+        );
+    };
+}
+
+/// Assert that a [`Key`](crate::Key) resolves to the expected string in a [`ParaCord`](crate::ParaCord).
+///
+/// Unlike `assert_eq!(paracord.resolve(key), expected)`, a key from another instance that
+/// happens to be out of bounds is reported as such, rather than as a panic from inside `resolve`.
+/// A [`SendKey`](crate::SendKey) is also checked to belong to the instance.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::{assert_resolves, ParaCord};
+/// let paracord = ParaCord::default();
+///
+/// let foo = paracord.get_or_intern("foo");
+/// assert_resolves!(paracord, foo, "foo");
+/// assert_resolves!(paracord, paracord.send_key(foo), "foo", "with a {}", "message");
+/// ```
+///
+/// ```should_panic
+/// use paracord::{assert_resolves, ParaCord};
+/// let a = ParaCord::default();
+/// let b = ParaCord::default();
+///
+/// let foo = a.send_key(a.get_or_intern("foo"));
+/// b.get_or_intern("foo");
+/// assert_resolves!(b, foo, "foo");
+/// ```
+#[macro_export]
+macro_rules! assert_resolves {
+    ($paracord:expr, $key:expr, $expected:expr $(,)?) => {
+        $crate::__private::assert_resolves(&$paracord, $key, &$expected, ::core::option::Option::None)
+    };
+    ($paracord:expr, $key:expr, $expected:expr, $($arg:tt)+) => {
+        $crate::__private::assert_resolves(
+            &$paracord,
+            $key,
+            &$expected,
+            ::core::option::Option::Some(::core::format_args!($($arg)+)),
+        )
     };
 }
 
+/// A key that can be checked by [`assert_resolves`].
+#[doc(hidden)]
+pub trait ResolveKey: fmt::Debug + Copy {
+    fn check<S>(self, paracord: &ParaCord<S>) -> Result<&str, &'static str>;
+}
+
+impl ResolveKey for Key {
+    #[track_caller]
+    fn check<S>(self, paracord: &ParaCord<S>) -> Result<&str, &'static str> {
+        paracord
+            .try_resolve(self)
+            .ok_or("key is out of bounds or was removed")
+    }
+}
+
+impl ResolveKey for SendKey {
+    #[track_caller]
+    fn check<S>(self, paracord: &ParaCord<S>) -> Result<&str, &'static str> {
+        let key = self
+            .into_key(paracord)
+            .map_err(|_| "key was allocated by a different instance, or a previous generation")?;
+        key.check(paracord)
+    }
+}
+
+/// Shared by [`ParaCord::debug_validate_key`] and [`SendKey::debug_validate`].
+#[track_caller]
+pub(crate) fn debug_validate<S, K: ResolveKey>(paracord: &ParaCord<S>, key: K) {
+    if let Err(err) = key.check(paracord) {
+        panic!("{err}: {key:?}");
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_resolves<S, K: ResolveKey>(
+    paracord: &ParaCord<S>,
+    key: K,
+    expected: &dyn AsRef<str>,
+    msg: Option<fmt::Arguments<'_>>,
+) {
+    let expected = expected.as_ref();
+    match (key.check(paracord), msg) {
+        (Ok(s), None) => assert_eq!(s, expected, "key {key:?} resolved to the wrong string"),
+        (Ok(s), Some(msg)) => assert_eq!(
+            s, expected,
+            "key {key:?} resolved to the wrong string: {msg}"
+        ),
+        (Err(err), None) => panic!("{err}: {key:?}"),
+        (Err(err), Some(msg)) => panic!("{err}: {key:?}: {msg}"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -151,4 +435,125 @@ mod tests {
         let keys: Vec<_> = Foo::iter().collect();
         assert_eq!(keys, [(foo, "foo")]);
     }
+
+    #[test]
+    #[allow(dead_code)]
+    fn options() {
+        use foldhash::quality::FixedState;
+
+        custom_key!(
+            pub struct Hasher;
+
+            let hasher: FixedState = FixedState::with_seed(1)
+        );
+        custom_key!(
+            pub struct HasherDefault;
+
+            let hasher: FixedState
+        );
+        custom_key!(
+            pub struct Registered;
+
+            register;
+        );
+        custom_key!(
+            pub struct Both;
+
+            register;
+            let hasher: FixedState;
+        );
+
+        assert_eq!(Hasher::new("foo").as_str(), "foo");
+        assert_eq!(HasherDefault::new("foo").as_str(), "foo");
+        Registered::new("foo");
+        Both::new("foo");
+        Both::new("bar");
+
+        let registry: Vec<_> = crate::global::registry().collect();
+        let registered = registry
+            .iter()
+            .find(|e| e.name.ends_with("::Registered"))
+            .unwrap();
+        assert_eq!(registered.len, 1);
+        assert!(registered.memory_usage > 0);
+        let both = registry
+            .iter()
+            .find(|e| e.name.ends_with("::Both"))
+            .unwrap();
+        assert_eq!(both.len, 2);
+        assert!(!registry.iter().any(|e| e.name.ends_with("::Hasher")));
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn init() {
+        use foldhash::quality::FixedState;
+
+        custom_key!(
+            pub struct Seeded;
+
+            let hasher: FixedState = |config| FixedState::with_seed(config.seed());
+            register;
+        );
+        custom_key!(
+            pub struct Explicit;
+
+            let hasher: FixedState;
+            register;
+        );
+
+        crate::global::set_seed(Some(1));
+        assert_eq!(Seeded::new("foo").as_str(), "foo");
+        crate::global::set_seed(None);
+
+        assert_eq!(Explicit::init_with(FixedState::with_seed(2)), Ok(()));
+        assert_eq!(
+            Explicit::init_with(FixedState::with_seed(3)),
+            Err(crate::global::AlreadyInitialized)
+        );
+        assert_eq!(
+            Seeded::init_with(FixedState::with_seed(3)),
+            Err(crate::global::AlreadyInitialized)
+        );
+        assert_eq!(Explicit::new("foo").as_str(), "foo");
+
+        let registry: Vec<_> = crate::global::registry().collect();
+        assert!(registry.iter().any(|e| e.name.ends_with("::Seeded")));
+        assert!(registry.iter().any(|e| e.name.ends_with("::Explicit")));
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn impls() {
+        custom_key!(
+            pub struct Parsed;
+
+            impls: [FromStr, TryFrom];
+            register;
+        );
+
+        let foo: Parsed = "foo".parse().unwrap();
+        assert_eq!(foo.as_str(), "foo");
+        assert_eq!(Parsed::try_from("foo"), Ok(foo));
+    }
+
+    #[test]
+    #[cfg(all(feature = "schemars", feature = "arbitrary"))]
+    #[allow(dead_code)]
+    fn impls_features() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        custom_key!(
+            pub struct Derived;
+
+            impls: [JsonSchema, Arbitrary];
+        );
+
+        let schema = schemars::schema_for!(Derived);
+        assert_eq!(schema.get("type").unwrap(), "string");
+
+        let mut u = Unstructured::new(&[3, b'f', b'o', b'o']);
+        let key = Derived::arbitrary(&mut u).unwrap();
+        assert_eq!(Derived::try_new_existing(key.as_str()), Some(key));
+    }
 }
@@ -0,0 +1,255 @@
+//! Normalizing strings before they are interned.
+//!
+//! See [`ParaCord::with_pipeline`].
+
+use std::borrow::Cow;
+use std::hash::BuildHasher;
+
+use crate::collections::KeyOnceMap;
+use crate::{Key, ParaCord};
+
+/// A sequence of string transforms applied before interning.
+///
+/// The enabled transforms are always applied in the following order, in a single pass over the string:
+/// 1. NFC normalization (requires the `unicode-normalization` feature)
+/// 2. lowercasing
+/// 3. trimming and collapsing whitespace
+///
+/// # Examples
+///
+/// ```
+/// use paracord::normalize::Pipeline;
+///
+/// let pipeline = Pipeline::new().trim().collapse_whitespace().lowercase();
+/// assert_eq!(pipeline.apply("  Hello   World "), "hello world");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Pipeline {
+    trim: bool,
+    collapse_whitespace: bool,
+    #[cfg(feature = "unicode-normalization")]
+    nfc: bool,
+    lowercase: bool,
+    keep_original: bool,
+}
+
+impl Pipeline {
+    /// Create a new pipeline that applies no transforms.
+    pub const fn new() -> Self {
+        Self {
+            trim: false,
+            collapse_whitespace: false,
+            #[cfg(feature = "unicode-normalization")]
+            nfc: false,
+            lowercase: false,
+            keep_original: false,
+        }
+    }
+
+    /// Remove leading and trailing whitespace.
+    pub const fn trim(mut self) -> Self {
+        self.trim = true;
+        self
+    }
+
+    /// Replace every run of whitespace with a single space.
+    pub const fn collapse_whitespace(mut self) -> Self {
+        self.collapse_whitespace = true;
+        self
+    }
+
+    /// Normalize the string to Unicode Normalization Form C.
+    #[cfg(feature = "unicode-normalization")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-normalization")))]
+    pub const fn nfc(mut self) -> Self {
+        self.nfc = true;
+        self
+    }
+
+    /// Convert the string to lowercase.
+    pub const fn lowercase(mut self) -> Self {
+        self.lowercase = true;
+        self
+    }
+
+    /// Remember the original string that first produced each key,
+    /// available with [`NormalizingParaCord::original`].
+    pub const fn keep_original(mut self) -> Self {
+        self.keep_original = true;
+        self
+    }
+
+    fn is_identity(&self) -> bool {
+        #[cfg(feature = "unicode-normalization")]
+        if self.nfc {
+            return false;
+        }
+        !self.trim && !self.collapse_whitespace && !self.lowercase
+    }
+
+    /// Apply the transforms to the string.
+    ///
+    /// Borrows the input if no transforms are enabled or the string is unchanged.
+    pub fn apply<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        if self.is_identity() {
+            return Cow::Borrowed(s);
+        }
+
+        #[cfg(feature = "unicode-normalization")]
+        if self.nfc {
+            use unicode_normalization::UnicodeNormalization;
+            return self.apply_chars(s, s.nfc());
+        }
+
+        self.apply_chars(s, s.chars())
+    }
+
+    fn apply_chars<'a>(
+        &self,
+        original: &'a str,
+        chars: impl Iterator<Item = char>,
+    ) -> Cow<'a, str> {
+        let mut out = String::with_capacity(original.len());
+        // whitespace is held back until we know that it isn't trailing.
+        let mut pending = String::new();
+
+        let mut push = |c: char| {
+            if c.is_whitespace() && (self.trim || self.collapse_whitespace) {
+                if self.trim && out.is_empty() {
+                    // leading whitespace
+                } else if self.collapse_whitespace {
+                    pending.clear();
+                    pending.push(' ');
+                } else {
+                    pending.push(c);
+                }
+            } else {
+                out.push_str(&pending);
+                pending.clear();
+                out.push(c);
+            }
+        };
+
+        for c in chars {
+            if self.lowercase {
+                c.to_lowercase().for_each(&mut push);
+            } else {
+                push(c);
+            }
+        }
+
+        if !self.trim {
+            out.push_str(&pending);
+        }
+
+        if out == original {
+            Cow::Borrowed(original)
+        } else {
+            Cow::Owned(out)
+        }
+    }
+}
+
+/// A [`ParaCord`] that normalizes strings with a [`Pipeline`] before interning them.
+///
+/// Created with [`ParaCord::with_pipeline`].
+pub struct NormalizingParaCord<S = foldhash::fast::RandomState> {
+    paracord: ParaCord<S>,
+    pipeline: Pipeline,
+    originals: KeyOnceMap<Box<str>>,
+}
+
+impl<S> std::fmt::Debug for NormalizingParaCord<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NormalizingParaCord")
+            .field("paracord", &self.paracord)
+            .field("pipeline", &self.pipeline)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: BuildHasher> ParaCord<S> {
+    /// Wrap this interner with a normalization [`Pipeline`], which is applied to every string
+    /// before it is looked up or interned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::normalize::Pipeline;
+    /// use paracord::ParaCord;
+    ///
+    /// let pipeline = Pipeline::new().trim().lowercase().keep_original();
+    /// let paracord = ParaCord::default().with_pipeline(pipeline);
+    ///
+    /// let foo = paracord.get_or_intern(" FOO ");
+    /// assert_eq!(paracord.get_or_intern("foo"), foo);
+    /// assert_eq!(paracord.resolve(foo), "foo");
+    /// assert_eq!(paracord.original(foo), Some(" FOO "));
+    /// ```
+    pub fn with_pipeline(self, pipeline: Pipeline) -> NormalizingParaCord<S> {
+        NormalizingParaCord {
+            paracord: self,
+            pipeline,
+            originals: KeyOnceMap::new(),
+        }
+    }
+}
+
+impl<S> NormalizingParaCord<S> {
+    /// Get the underlying [`ParaCord`].
+    pub fn paracord(&self) -> &ParaCord<S> {
+        &self.paracord
+    }
+
+    /// Remove the pipeline, returning the underlying [`ParaCord`].
+    pub fn into_inner(self) -> ParaCord<S> {
+        self.paracord
+    }
+
+    /// Get the [`Pipeline`] used by this instance.
+    pub fn pipeline(&self) -> &Pipeline {
+        &self.pipeline
+    }
+
+    /// Resolve the normalized string associated with this key.
+    ///
+    /// # Panics
+    ///
+    /// See [`ParaCord::resolve`].
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &str {
+        self.paracord.resolve(key)
+    }
+
+    /// Get the original string that first produced this key, before normalization.
+    ///
+    /// Returns [`None`] unless [`Pipeline::keep_original`] is enabled.
+    #[must_use]
+    pub fn original(&self, key: Key) -> Option<&str> {
+        if self.pipeline.keep_original {
+            self.originals.get(key).map(|s| &**s)
+        } else {
+            None
+        }
+    }
+}
+
+impl<S: BuildHasher> NormalizingParaCord<S> {
+    /// Try and get the [`Key`] associated with the normalized string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        self.paracord.get(&self.pipeline.apply(s))
+    }
+
+    /// Try and get the [`Key`] associated with the normalized string.
+    /// Allocates a new key if not found.
+    pub fn get_or_intern(&self, s: &str) -> Key {
+        let key = self.paracord.get_or_intern(&self.pipeline.apply(s));
+        if self.pipeline.keep_original {
+            self.originals.get_or_init(key, || s.into());
+        }
+        key
+    }
+}
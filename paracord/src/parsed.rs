@@ -0,0 +1,57 @@
+use core::any::{Any, TypeId};
+use std::panic::AssertUnwindSafe;
+use std::sync::Mutex;
+
+use crate::collections::KeyOnceMap;
+
+type ErasedMap = Box<dyn Any + Send + Sync>;
+
+/// Lazily parsed values of the interned strings, with one [`KeyOnceMap`] per parsed type.
+#[derive(Default)]
+pub(crate) struct ParsedCache {
+    // values are only ever initialized once, so a panic while parsing cannot leave
+    // a map in an inconsistent state.
+    maps: AssertUnwindSafe<boxcar::Vec<(TypeId, ErasedMap)>>,
+    /// Held while adding a map, so every type has at most one.
+    insert: Mutex<()>,
+}
+
+impl core::fmt::Debug for ParsedCache {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ParsedCache")
+            .field("types", &self.maps.count())
+            .finish()
+    }
+}
+
+impl ParsedCache {
+    /// Get the map of parsed values of type `T`, creating it if necessary.
+    pub(crate) fn map<T: Send + Sync + 'static>(&self) -> &KeyOnceMap<Option<T>> {
+        if let Some(map) = self.find::<T>() {
+            return map;
+        }
+
+        let _guard = self.insert.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(map) = self.find::<T>() {
+            return map;
+        }
+        let map: ErasedMap = Box::<KeyOnceMap<Option<T>>>::default();
+        let i = self.maps.push((TypeId::of::<T>(), map));
+        self.maps[i]
+            .1
+            .downcast_ref()
+            .expect("map has the right type")
+    }
+
+    fn find<T: Send + Sync + 'static>(&self) -> Option<&KeyOnceMap<Option<T>>> {
+        self.maps
+            .iter()
+            .find(|(_, (id, _))| *id == TypeId::of::<T>())
+            .and_then(|(_, (_, map))| map.downcast_ref())
+    }
+
+    /// Forget every parsed value.
+    pub(crate) fn clear(&mut self) {
+        self.maps.clear();
+    }
+}
@@ -0,0 +1,144 @@
+//! Consistent read snapshots of a [`ParaCord`].
+//!
+//! See [`ParaCord::pin`].
+
+use std::hash::BuildHasher;
+
+use foldhash::fast::RandomState;
+
+use crate::{Key, ParaCord};
+
+/// A consistent view of the strings interned in a [`ParaCord`] at a single point in time.
+///
+/// Created with [`ParaCord::pin`].
+///
+/// The view covers every key allocated before a watermark that is fixed when the pin is created.
+/// Strings interned concurrently after that point are not visible through the pin, so
+/// [`Pin::len`], [`Pin::iter`] and [`Pin::try_resolve`] always agree with each other.
+pub struct Pin<'a, S = RandomState> {
+    paracord: &'a ParaCord<S>,
+    start: usize,
+    watermark: usize,
+}
+
+impl<S> Clone for Pin<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Pin<'_, S> {}
+
+impl<S> std::fmt::Debug for Pin<'_, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<S> ParaCord<S> {
+    /// Take a consistent snapshot of the strings interned so far.
+    ///
+    /// See [`Pin`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::ParaCord;
+    ///
+    /// let paracord = ParaCord::default();
+    /// let foo = paracord.get_or_intern("foo");
+    ///
+    /// let pin = paracord.pin();
+    /// let bar = paracord.get_or_intern("bar");
+    ///
+    /// assert_eq!(pin.len(), 1);
+    /// assert_eq!(pin.try_resolve(foo), Some("foo"));
+    /// assert_eq!(pin.try_resolve(bar), None);
+    /// assert_eq!(pin.iter().collect::<Vec<_>>(), [(foo, "foo")]);
+    /// ```
+    #[must_use]
+    pub fn pin(&self) -> Pin<'_, S> {
+        Pin {
+            paracord: self,
+            start: self.inner.first_index(),
+            watermark: self.inner.settled_index(),
+        }
+    }
+}
+
+impl<'a, S> Pin<'a, S> {
+    /// Get the [`ParaCord`] this pin was created from.
+    pub fn paracord(&self) -> &'a ParaCord<S> {
+        self.paracord
+    }
+
+    /// Determine if the key was allocated before this pin was created, and since the last
+    /// [`ParaCord::clear`].
+    ///
+    /// These keys are exactly those returned by [`Pin::iter`], plus any that have been removed.
+    #[must_use]
+    pub fn contains(&self, key: Key) -> bool {
+        (self.start..self.watermark).contains(&(key.into_repr() as usize))
+    }
+
+    /// The index of the first key allocated after this pin was created.
+    #[must_use]
+    pub fn watermark(&self) -> usize {
+        self.watermark
+    }
+
+    /// Determine how many strings are visible through this pin.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.watermark - self.start - self.paracord.inner.removed()
+    }
+
+    /// Determine if no strings are visible through this pin.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Try resolve the string associated with this [`Key`].
+    ///
+    /// Returns [`None`] if the key is not visible through this pin,
+    /// or in the same cases as [`ParaCord::try_resolve`].
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&'a str> {
+        if !self.contains(key) {
+            return None;
+        }
+        self.paracord.try_resolve(key)
+    }
+
+    /// Resolve the string associated with this [`Key`].
+    ///
+    /// # Panics
+    /// This will panic if the key is not visible through this pin,
+    /// or in the same cases as [`ParaCord::resolve`].
+    #[track_caller]
+    #[must_use]
+    pub fn resolve(&self, key: Key) -> &'a str {
+        assert!(self.contains(key), "key is not visible through this pin");
+        self.paracord.resolve(key)
+    }
+
+    /// Get an iterator over every ([`Key`], `&str`) pair visible through this pin, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &'a str)> + 'a {
+        let paracord = self.paracord;
+        (self.start..self.watermark).filter_map(move |i| {
+            let key = Key::from_index(i);
+            Some((key, paracord.try_resolve(key)?))
+        })
+    }
+}
+
+impl<S: BuildHasher> Pin<'_, S> {
+    /// Try and get the [`Key`] associated with the given string.
+    ///
+    /// Returns [`None`] if not found, or if it was interned after this pin was created.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        self.paracord.get(s).filter(|&key| self.contains(key))
+    }
+}
@@ -0,0 +1,244 @@
+//! A process-wide pool of arena pages, shared by every [`ParaCord`](crate::ParaCord) instance.
+//!
+//! With the `page-pool` feature, interned strings are copied into fixed size pages taken from this
+//! pool, and the pages are returned to the pool when the instance is dropped or cleared. Programs
+//! that create and drop many short-lived instances, eg one per request or per file, then reuse
+//! the same pages instead of going through the system allocator each time.
+//!
+//! Strings larger than a page are allocated separately, and are not pooled.
+//!
+//! ```
+//! use paracord::ParaCord;
+//!
+//! for i in 0..10 {
+//!     let paracord = ParaCord::default();
+//!     paracord.get_or_intern(&format!("request {i}"));
+//! }
+//!
+//! let stats = paracord::pool::stats();
+//! assert!(stats.reused > 0);
+//! ```
+
+use std::alloc::Layout;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// The size of every pooled page, in bytes.
+pub const PAGE_SIZE: usize = 4096;
+
+/// The alignment of every pooled page.
+const PAGE_ALIGN: usize = 16;
+
+const PAGE_LAYOUT: Layout = match Layout::from_size_align(PAGE_SIZE, PAGE_ALIGN) {
+    Ok(layout) => layout,
+    Err(_) => panic!("invalid page layout"),
+};
+
+/// A page of memory with [`PAGE_LAYOUT`].
+struct Page(NonNull<u8>);
+
+// Safety: a page is uniquely owned memory.
+unsafe impl Send for Page {}
+
+struct Pool {
+    free: Mutex<Vec<Page>>,
+    max_pages: AtomicUsize,
+    allocated: AtomicUsize,
+    reused: AtomicUsize,
+    returned: AtomicUsize,
+    released: AtomicUsize,
+}
+
+static POOL: Pool = Pool {
+    free: Mutex::new(Vec::new()),
+    max_pages: AtomicUsize::new(64 * 1024 * 1024 / PAGE_SIZE),
+    allocated: AtomicUsize::new(0),
+    reused: AtomicUsize::new(0),
+    returned: AtomicUsize::new(0),
+    released: AtomicUsize::new(0),
+};
+
+impl Pool {
+    fn free(&self) -> std::sync::MutexGuard<'_, Vec<Page>> {
+        // the free list is always valid, even if a thread panicked while holding the lock.
+        self.free.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn take(&self) -> Page {
+        if let Some(page) = self.free().pop() {
+            self.reused.fetch_add(1, Ordering::Relaxed);
+            return page;
+        }
+
+        self.allocated.fetch_add(1, Ordering::Relaxed);
+        // Safety: the layout has a non-zero size.
+        let ptr = unsafe { std::alloc::alloc(PAGE_LAYOUT) };
+        match NonNull::new(ptr) {
+            Some(ptr) => Page(ptr),
+            None => std::alloc::handle_alloc_error(PAGE_LAYOUT),
+        }
+    }
+
+    fn give(&self, pages: impl ExactSizeIterator<Item = Page>) {
+        let mut free = self.free();
+        let max = self.max_pages.load(Ordering::Relaxed);
+        let keep = pages.len().min(max.saturating_sub(free.len()));
+        self.returned.fetch_add(keep, Ordering::Relaxed);
+        self.released
+            .fetch_add(pages.len() - keep, Ordering::Relaxed);
+
+        let mut pages = pages;
+        free.extend(pages.by_ref().take(keep));
+        drop(free);
+        pages.for_each(release);
+    }
+}
+
+fn release(page: Page) {
+    // Safety: every page is allocated with the page layout.
+    unsafe { std::alloc::dealloc(page.0.as_ptr(), PAGE_LAYOUT) }
+}
+
+/// Statistics of the page pool. Returned by [`stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PoolStats {
+    /// How many pages are currently in the pool, ready to be reused.
+    pub pooled_pages: usize,
+    /// How many bytes are currently held by the pool.
+    pub pooled_bytes: usize,
+    /// How many pages have been allocated from the system allocator.
+    pub allocated: usize,
+    /// How many pages have been taken from the pool instead of being allocated.
+    pub reused: usize,
+    /// How many pages have been returned to the pool.
+    pub returned: usize,
+    /// How many pages have been released to the system allocator, because the pool was full
+    /// or was trimmed.
+    pub released: usize,
+}
+
+/// Get the current statistics of the page pool.
+#[must_use]
+pub fn stats() -> PoolStats {
+    let pooled_pages = POOL.free().len();
+    PoolStats {
+        pooled_pages,
+        pooled_bytes: pooled_pages * PAGE_SIZE,
+        allocated: POOL.allocated.load(Ordering::Relaxed),
+        reused: POOL.reused.load(Ordering::Relaxed),
+        returned: POOL.returned.load(Ordering::Relaxed),
+        released: POOL.released.load(Ordering::Relaxed),
+    }
+}
+
+/// Set the maximum number of bytes the pool holds on to. Pages returned to a full pool are
+/// released to the system allocator instead. Defaults to 64 MiB.
+///
+/// This does not release pages that are already in the pool, see [`trim`].
+pub fn set_max_pooled_bytes(bytes: usize) {
+    POOL.max_pages.store(bytes / PAGE_SIZE, Ordering::Relaxed);
+}
+
+/// Release every page in the pool to the system allocator.
+pub fn trim() {
+    let pages = std::mem::take(&mut *POOL.free());
+    POOL.released.fetch_add(pages.len(), Ordering::Relaxed);
+    pages.into_iter().for_each(release);
+}
+
+/// An arena that allocates from the page pool.
+pub(crate) struct Pages {
+    pages: Vec<Page>,
+    /// Pages kept by [`Pages::reset`], which are used before taking pages from the pool.
+    spare: Vec<Page>,
+    /// How many bytes of the last page are in use.
+    used: usize,
+    /// Allocations that did not fit in a page.
+    large: Vec<(NonNull<u8>, Layout)>,
+}
+
+// Safety: the pages are uniquely owned memory.
+unsafe impl Send for Pages {}
+// Safety: `&Pages` does not give access to the memory.
+unsafe impl Sync for Pages {}
+
+impl Default for Pages {
+    fn default() -> Self {
+        Self {
+            pages: Vec::new(),
+            spare: Vec::new(),
+            used: PAGE_SIZE,
+            large: Vec::new(),
+        }
+    }
+}
+
+impl Drop for Pages {
+    fn drop(&mut self) {
+        POOL.give(self.pages.drain(..));
+        POOL.give(self.spare.drain(..));
+        self.release_large();
+    }
+}
+
+impl Pages {
+    fn release_large(&mut self) {
+        for (ptr, layout) in self.large.drain(..) {
+            // Safety: the pointer was allocated with this layout.
+            unsafe { std::alloc::dealloc(ptr.as_ptr(), layout) }
+        }
+    }
+
+    /// Free every allocation, but keep the pages so they can be reused by this arena.
+    ///
+    /// Allocations that did not fit in a page are released.
+    pub(crate) fn reset(&mut self) {
+        self.release_large();
+        // reuse the pages in the same order they were first used.
+        self.spare.extend(self.pages.drain(..).rev());
+        self.used = PAGE_SIZE;
+    }
+
+    /// The bytes held by this arena, including spare pages.
+    pub(crate) fn reserved(&self) -> usize {
+        let large: usize = self.large.iter().map(|(_, layout)| layout.size()).sum();
+        (self.pages.len() + self.spare.len()) * PAGE_SIZE + large
+    }
+
+    /// Allocate space for `len` values. The space is valid until the arena is dropped.
+    pub(crate) fn alloc_uninit<T>(&mut self, len: usize) -> &mut [MaybeUninit<T>] {
+        let layout = Layout::array::<T>(len).expect("slice should fit in memory");
+
+        let ptr = if layout.size() == 0 {
+            NonNull::<T>::dangling().cast::<u8>()
+        } else if layout.size() > PAGE_SIZE || layout.align() > PAGE_ALIGN {
+            // Safety: the layout has a non-zero size.
+            let ptr = unsafe { std::alloc::alloc(layout) };
+            let Some(ptr) = NonNull::new(ptr) else {
+                std::alloc::handle_alloc_error(layout)
+            };
+            self.large.push((ptr, layout));
+            ptr
+        } else {
+            let mut offset = self.used.next_multiple_of(layout.align());
+            if offset + layout.size() > PAGE_SIZE {
+                let page = self.spare.pop().unwrap_or_else(|| POOL.take());
+                self.pages.push(page);
+                offset = 0;
+            }
+            self.used = offset + layout.size();
+            let page = self.pages.last().expect("a page was pushed");
+            // Safety: offset + size is within the page.
+            let ptr = unsafe { page.0.as_ptr().add(offset) };
+            // Safety: the page is not null, so neither is a pointer into it.
+            unsafe { NonNull::new_unchecked(ptr) }
+        };
+
+        // Safety: the memory is valid for the layout, is not used by any other allocation,
+        // and `MaybeUninit` does not need to be initialized.
+        unsafe { std::slice::from_raw_parts_mut(ptr.as_ptr().cast::<MaybeUninit<T>>(), len) }
+    }
+}
@@ -0,0 +1,201 @@
+//! Reference-counted keys, so unused strings can be reclaimed.
+//!
+//! See [`RefCountedParaCord`].
+
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use foldhash::fast::RandomState;
+
+use crate::collections::{KeyBitSet, KeyOnceMap};
+use crate::slice::SweepReport;
+use crate::{Key, ParaCord};
+
+/// A string interner that hands out reference-counted [`Handle`]s instead of plain keys.
+///
+/// Once every handle to a string has been dropped, the string is removed by the next call to
+/// [`RefCountedParaCord::sweep`]. This bounds the memory of long running services that see an
+/// unbounded number of unique strings, at the cost of an atomic increment and decrement per handle.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::refcount::RefCountedParaCord;
+///
+/// let mut paracord = RefCountedParaCord::default();
+///
+/// let foo = paracord.get_or_intern("foo");
+/// let bar = paracord.get_or_intern("bar");
+/// let bar_key = bar.key();
+/// drop(bar);
+///
+/// let report = paracord.sweep();
+/// assert_eq!(report.removed, 1);
+///
+/// assert_eq!(paracord.resolve(&foo), "foo");
+/// assert_eq!(paracord.try_resolve(bar_key), None);
+/// ```
+pub struct RefCountedParaCord<S = RandomState> {
+    inner: ParaCord<S>,
+    counts: Arc<KeyOnceMap<AtomicUsize>>,
+}
+
+/// A reference-counted key from a [`RefCountedParaCord`].
+///
+/// The string stays interned for as long as any handle to it is alive.
+/// Handles compare and hash by their key.
+pub struct Handle {
+    key: Key,
+    counts: Arc<KeyOnceMap<AtomicUsize>>,
+}
+
+impl<S> std::fmt::Debug for RefCountedParaCord<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl Default for RefCountedParaCord {
+    fn default() -> Self {
+        Self::with_hasher(RandomState::default())
+    }
+}
+
+impl<S: BuildHasher> RefCountedParaCord<S> {
+    /// Create a new `RefCountedParaCord` instance with the given hasher state.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            inner: ParaCord::with_hasher(hasher),
+            counts: Arc::default(),
+        }
+    }
+
+    /// Try and get a [`Handle`] to the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Handle> {
+        self.inner.get(s).map(|key| self.handle(key))
+    }
+
+    /// Try and get a [`Handle`] to the given string.
+    /// Allocates a new key if not found.
+    pub fn get_or_intern(&self, s: &str) -> Handle {
+        self.handle(self.inner.get_or_intern(s))
+    }
+
+    /// Remove every string that has no live [`Handle`], releasing its memory.
+    ///
+    /// The keys of removed strings are never reused.
+    pub fn sweep(&mut self) -> SweepReport {
+        let live: KeyBitSet = self
+            .counts
+            .iter()
+            .filter(|(_, count)| count.load(Ordering::Acquire) > 0)
+            .map(|(key, _)| key)
+            .collect();
+        self.inner.sweep(&live)
+    }
+}
+
+impl<S> RefCountedParaCord<S> {
+    fn handle(&self, key: Key) -> Handle {
+        let count = self.counts.get_or_init(key, || AtomicUsize::new(0));
+        count.fetch_add(1, Ordering::Relaxed);
+        Handle {
+            key,
+            counts: Arc::clone(&self.counts),
+        }
+    }
+
+    /// Resolve the string associated with this [`Handle`].
+    ///
+    /// # Panics
+    /// This can panic if given a handle from a different [`RefCountedParaCord`] instance,
+    /// but it might return an arbitrary string as well.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, handle: &Handle) -> &str {
+        self.inner.resolve(handle.key)
+    }
+
+    /// Try and resolve the string associated with this [`Key`].
+    ///
+    /// Returns [`None`] if the string has been removed by [`RefCountedParaCord::sweep`].
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&str> {
+        self.inner.try_resolve(key)
+    }
+
+    /// Determine how many strings are interned, including strings with no live handles
+    /// that have not been swept yet.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Determine if no strings are interned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl Handle {
+    /// Get the [`Key`] of this handle.
+    ///
+    /// The key does not keep the string alive.
+    #[must_use]
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Get the number of live handles to this string.
+    #[must_use]
+    pub fn ref_count(&self) -> usize {
+        self.count().load(Ordering::Relaxed)
+    }
+
+    fn count(&self) -> &AtomicUsize {
+        self.counts
+            .get(self.key)
+            .expect("handles are only created after their count is initialized")
+    }
+}
+
+impl Clone for Handle {
+    fn clone(&self) -> Self {
+        self.count().fetch_add(1, Ordering::Relaxed);
+        Self {
+            key: self.key,
+            counts: Arc::clone(&self.counts),
+        }
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        // pairs with the acquire load in `sweep`.
+        self.count().fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl std::fmt::Debug for Handle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Handle").field(&self.key).finish()
+    }
+}
+
+impl PartialEq for Handle {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Handle {}
+
+impl Hash for Handle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
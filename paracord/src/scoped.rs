@@ -0,0 +1,168 @@
+//! Interners that store no strings of their own.
+//!
+//! A [`ScopedParaCord`] only stores pointers to strings that outlive it, either borrowed from
+//! the input being parsed, or allocated in an arena owned by the caller. This suits short-lived
+//! interners, eg one per parse, which would otherwise maintain arena chunks of their own.
+//!
+//! ```
+//! use paracord::scoped::ScopedParaCord;
+//!
+//! let source = String::from("let x = x + 1");
+//!
+//! let paracord = ScopedParaCord::default();
+//! let keys: Vec<_> = source
+//!     .split_whitespace()
+//!     .map(|token| paracord.get_or_intern_borrowed(token))
+//!     .collect();
+//!
+//! assert_eq!(keys[1], keys[3]);
+//! // the strings are borrowed from `source`, so they outlive the interner.
+//! let x = paracord.resolve(keys[1]);
+//! drop(paracord);
+//! assert_eq!(x, "x");
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+use std::hash::BuildHasher;
+
+use foldhash::fast::RandomState;
+
+use crate::{Key, ParaCord};
+
+/// An interner whose strings all live for `'a`, and are never copied by the interner.
+///
+/// See the [module docs](self).
+///
+/// `'a` cannot be shortened, since that would allow interning strings that don't live for the
+/// original `'a`:
+///
+/// ```compile_fail
+/// use paracord::scoped::ScopedParaCord;
+///
+/// fn shorten<'l: 's, 's>(p: &'s ScopedParaCord<'l>) -> &'s ScopedParaCord<'s> {
+///     p
+/// }
+/// ```
+pub struct ScopedParaCord<'a, S = RandomState> {
+    /// Only ever holds strings that live for `'a`.
+    inner: ParaCord<S>,
+    /// Invariant in `'a`, so the lifetime cannot be shortened to intern strings that don't
+    /// live as long as the strings already interned.
+    _strings: PhantomData<fn(&'a str) -> &'a str>,
+}
+
+impl<S> fmt::Debug for ScopedParaCord<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl Default for ScopedParaCord<'_> {
+    fn default() -> Self {
+        Self::with_hasher(RandomState::default())
+    }
+}
+
+impl<'a, S> ScopedParaCord<'a, S> {
+    /// Try and resolve the string associated with this [`Key`].
+    ///
+    /// The string lives for `'a`, so it can outlive the interner.
+    ///
+    /// This can return [`None`] if the key was not allocated by this instance.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&'a str> {
+        let s = self.inner.try_resolve(key)?;
+        // Safety: only strings that live for 'a are interned.
+        Some(unsafe { &*(s as *const str) })
+    }
+
+    /// Resolve the string associated with this [`Key`].
+    ///
+    /// The string lives for `'a`, so it can outlive the interner.
+    ///
+    /// # Panics
+    /// This can panic if the key was not allocated by this instance.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &'a str {
+        self.try_resolve(key)
+            .expect("key should be allocated by this paracord instance")
+    }
+
+    /// Determine how many strings have been interned.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Determine if no strings have been interned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Get an iterator over every ([`Key`], `&str`) pair in this instance, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &'a str)> + '_ {
+        self.inner.iter().map(|(key, s)| {
+            // Safety: only strings that live for 'a are interned.
+            (key, unsafe { &*(s as *const str) })
+        })
+    }
+}
+
+impl<'a, S: BuildHasher> ScopedParaCord<'a, S> {
+    /// Create a new `ScopedParaCord` instance with the given hasher state.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            inner: ParaCord::with_hasher(hasher),
+            _strings: PhantomData,
+        }
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        self.inner.get(s)
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Interns a pointer to the string if not found, without copying it.
+    pub fn get_or_intern_borrowed(&self, s: &'a str) -> Key {
+        // Safety: the string outlives this instance, and is only resolved as a `&'a str`.
+        let s: &'static str = unsafe { &*(s as *const str) };
+        self.inner.get_or_intern_static(s)
+    }
+
+    /// Try and get the [`Key`] associated with the given string, along with the interned string.
+    /// Copies the string into `arena` if not found.
+    ///
+    /// If several threads intern the same new string concurrently, each of them might copy it,
+    /// but only one copy is interned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::Bump;
+    /// use paracord::scoped::ScopedParaCord;
+    ///
+    /// let arena = Bump::new();
+    /// let paracord = ScopedParaCord::default();
+    ///
+    /// let (foo, s) = paracord.get_or_intern_in(&String::from("foo"), &arena);
+    /// assert_eq!(paracord.get_or_intern_in("foo", &arena), (foo, s));
+    ///
+    /// drop(paracord);
+    /// assert_eq!(s, "foo");
+    /// ```
+    #[cfg(feature = "bumpalo")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bumpalo")))]
+    pub fn get_or_intern_in(&self, s: &str, arena: &'a bumpalo::Bump) -> (Key, &'a str) {
+        let key = match self.get(s) {
+            Some(key) => key,
+            None => self.get_or_intern_borrowed(arena.alloc_str(s)),
+        };
+        (key, self.resolve(key))
+    }
+}
@@ -0,0 +1,109 @@
+use core::fmt;
+
+use crate::{Key, ParaCord};
+
+/// A [`Key`] along with the id and [generation](ParaCord::generation) of the [`ParaCord`]
+/// instance that allocated it.
+///
+/// Keys can be sent between threads freely, but it's easy to resolve them against the wrong
+/// instance, or after the instance was cleared, which returns an arbitrary string. A [`SendKey`]
+/// is checked when it's turned back into a key, which makes it practical to move keys through
+/// channels between subsystems that hold different interners.
+///
+/// Created with [`ParaCord::send_key`].
+///
+/// # Examples
+///
+/// ```
+/// use paracord::ParaCord;
+///
+/// let a = ParaCord::default();
+/// let b = ParaCord::default();
+///
+/// let foo = a.send_key(a.get_or_intern("foo"));
+///
+/// let foo = std::thread::spawn(move || foo).join().unwrap();
+/// assert!(foo.into_key(&b).is_err());
+/// assert_eq!(a.resolve(foo.into_key(&a).unwrap()), "foo");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SendKey {
+    key: Key,
+    generation: u32,
+    instance: u64,
+}
+
+/// Returned by [`SendKey::into_key`] when the key was allocated by a different instance, or
+/// before the instance was cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WrongInstance;
+
+impl fmt::Display for WrongInstance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("key was allocated by a different paracord instance, or a previous generation")
+    }
+}
+
+impl std::error::Error for WrongInstance {}
+
+impl<S> ParaCord<S> {
+    /// Wrap a key allocated by this instance, so it can be checked when it's used again.
+    ///
+    /// See [`SendKey`].
+    #[inline]
+    #[must_use]
+    pub fn send_key(&self, key: Key) -> SendKey {
+        SendKey {
+            key,
+            generation: self.inner.generation(),
+            instance: self.inner.id(),
+        }
+    }
+}
+
+impl SendKey {
+    /// Get the key, if it was allocated by the given instance, in its current generation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WrongInstance`] if the key was wrapped by a different instance, or before
+    /// the instance was cleared.
+    #[inline]
+    pub fn into_key<S>(self, paracord: &ParaCord<S>) -> Result<Key, WrongInstance> {
+        if self.belongs_to(paracord) {
+            Ok(self.key)
+        } else {
+            Err(WrongInstance)
+        }
+    }
+
+    /// Determine if the key was allocated by the given instance, in its current generation.
+    #[inline]
+    #[must_use]
+    pub fn belongs_to<S>(&self, paracord: &ParaCord<S>) -> bool {
+        self.instance == paracord.inner.id() && self.generation == paracord.inner.generation()
+    }
+
+    /// Check that the key was allocated by the given instance and still resolves, when debug
+    /// assertions are enabled. In release builds this does nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if debug assertions are enabled and the key was allocated by a different instance,
+    /// or does not resolve.
+    #[inline]
+    #[track_caller]
+    pub fn debug_validate<S>(&self, paracord: &ParaCord<S>) {
+        if cfg!(debug_assertions) {
+            crate::macros::debug_validate(paracord, *self);
+        }
+    }
+
+    /// Get the key without checking which instance allocated it.
+    #[inline]
+    #[must_use]
+    pub fn into_key_unchecked(self) -> Key {
+        self.key
+    }
+}
@@ -1,6 +1,8 @@
+use std::fmt;
 use std::hash::{BuildHasher, Hash};
 
-use serde::de::{DeserializeSeed, Visitor};
+use serde::de::{DeserializeSeed, SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
 
 use crate::{slice, Key, ParaCord};
 
@@ -46,6 +48,84 @@ impl<'de, T: Deserialize<'de> + Hash + Eq + Copy, S: BuildHasher> DeserializeSee
     }
 }
 
+/// Serializes every string in key order, as a sequence of `Option<&str>`.
+///
+/// Removed keys, and keys from before the last [`ParaCord::clear`], are serialized as `None`,
+/// so every key has the same value after deserializing.
+impl<S> Serialize for ParaCord<S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        // strings interned concurrently are not included.
+        let count = self.inner.slots(0).count();
+        let mut seq = serializer.serialize_seq(Some(count))?;
+        for s in self.inner.slots(0).take(count) {
+            // Safety: we insert only strings, so it's valid utf8
+            seq.serialize_element(&s.map(|b| unsafe { core::str::from_utf8_unchecked(b) }))?;
+        }
+        seq.end()
+    }
+}
+
+/// Deserializes a sequence written by the [`Serialize`] impl,
+/// giving every string the same key it had when serialized.
+impl<'de, S: BuildHasher + Default> Deserialize<'de> for ParaCord<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ParaCordVisitor<S>(core::marker::PhantomData<S>);
+
+        impl<'de, S: BuildHasher + Default> Visitor<'de> for ParaCordVisitor<S> {
+            type Value = ParaCord<S>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence of optional strings")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut this = ParaCord::with_hasher(S::default());
+                let mut next = 0;
+                while let Some(key) = seq.next_element_seed(EntrySeed(&mut this))? {
+                    if key != Key::from_index(next) {
+                        return Err(serde::de::Error::custom("duplicate string"));
+                    }
+                    next += 1;
+                }
+                Ok(this)
+            }
+        }
+
+        deserializer.deserialize_seq(ParaCordVisitor(core::marker::PhantomData))
+    }
+}
+
+/// Pushes one `Option<&str>` entry into the instance, without copying the string.
+struct EntrySeed<'a, S>(&'a mut ParaCord<S>);
+
+impl<'de, S: BuildHasher> DeserializeSeed<'de> for EntrySeed<'_, S> {
+    type Value = Key;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Key, D::Error> {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de, S: BuildHasher> Visitor<'de> for EntrySeed<'_, S> {
+    type Value = Key;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an optional string")
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<Key, E> {
+        Ok(self.0.inner.push_tombstone_mut())
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Key, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Key, E> {
+        Ok(self.0.inner.push_mut(v.as_bytes()))
+    }
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! custom_key_serde {
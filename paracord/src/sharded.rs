@@ -0,0 +1,128 @@
+use std::hash::BuildHasher;
+
+use crate::{slice, Key};
+
+/// A variant of [`ParaCord`](crate::ParaCord) that allocates keys independently in each shard.
+///
+/// This removes the shared key counter that all inserts into a [`ParaCord`](crate::ParaCord)
+/// contend on, which helps highly parallel bulk loads. In exchange, keys are not ordered by allocation.
+/// See [`slice::ShardedParaCord`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::ShardedParaCord;
+///
+/// let paracord = ShardedParaCord::default();
+///
+/// let foo = paracord.get_or_intern("foo");
+/// let bar = paracord.get_or_intern("bar");
+///
+/// assert_ne!(foo, bar);
+/// assert_eq!(paracord.get("foo"), Some(foo));
+/// assert_eq!(paracord.resolve(foo), "foo");
+/// assert_eq!(paracord.resolve(bar), "bar");
+/// ```
+pub struct ShardedParaCord<S = foldhash::fast::RandomState> {
+    inner: slice::ShardedParaCord<u8, S>,
+}
+
+impl<S> std::fmt::Debug for ShardedParaCord<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl Default for ShardedParaCord {
+    fn default() -> Self {
+        Self {
+            inner: slice::ShardedParaCord::default(),
+        }
+    }
+}
+
+impl<S: BuildHasher> ShardedParaCord<S> {
+    /// Create a new `ShardedParaCord` instance with the given hasher state,
+    /// and a number of shards based on the available parallelism.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            inner: slice::ShardedParaCord::with_hasher(hasher),
+        }
+    }
+
+    /// Create a new `ShardedParaCord` instance with the given hasher state and number of shards.
+    ///
+    /// The number of shards is rounded up to a power of two, and limited to `2^16`.
+    pub fn with_shards_and_hasher(shards: usize, hasher: S) -> Self {
+        Self {
+            inner: slice::ShardedParaCord::with_shards_and_hasher(shards, hasher),
+        }
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        self.inner.get(s.as_bytes())
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Allocates a new key if not found.
+    #[inline]
+    pub fn get_or_intern(&self, s: &str) -> Key {
+        self.inner.get_or_intern(s.as_bytes())
+    }
+}
+
+impl<S> ShardedParaCord<S> {
+    /// Determine how many shards this instance has.
+    pub fn shards(&self) -> usize {
+        self.inner.shards()
+    }
+
+    /// Try and resolve the string associated with this [`Key`].
+    ///
+    /// This can return [`None`] if the key was not allocated by this instance.
+    #[inline]
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&str> {
+        // Safety: we only insert strings, so it's definitely valid utf8
+        self.inner
+            .try_resolve(key)
+            .map(|s| unsafe { core::str::from_utf8_unchecked(s) })
+    }
+
+    /// Resolve the string associated with this [`Key`].
+    ///
+    /// # Panics
+    /// This can panic if the key was not allocated by this instance.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &str {
+        // Safety: we only insert strings, so it's definitely valid utf8
+        unsafe { core::str::from_utf8_unchecked(self.inner.resolve(key)) }
+    }
+
+    /// Determine how many strings have been allocated
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Determine if no strings have been allocated
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Get an iterator over every ([`Key`], `&str`) pair
+    /// that has been allocated in this instance, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &str)> {
+        self.inner.iter().map(|(k, s)| {
+            // Safety: we only insert strings, so it's definitely valid utf8
+            (k, unsafe { core::str::from_utf8_unchecked(s) })
+        })
+    }
+}
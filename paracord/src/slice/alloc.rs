@@ -2,24 +2,273 @@ use std::hash::{BuildHasher, Hash};
 use std::mem::MaybeUninit;
 
 use hashbrown::hash_table::Entry;
+#[cfg(not(feature = "page-pool"))]
 use sync_wrapper::SyncWrapper;
+#[cfg(not(feature = "page-pool"))]
 use typed_arena::Arena;
 
 use crate::slice::{Collection, ParaCord};
 use crate::Key;
 
-pub(super) struct Alloc<T>(SyncWrapper<Arena<T>>);
+pub(super) struct Alloc<T> {
+    #[cfg(not(feature = "page-pool"))]
+    arena: SyncWrapper<Arena<T>>,
+    #[cfg(feature = "page-pool")]
+    arena: crate::pool::Pages,
+    /// Slices that were adopted instead of copied into the arena.
+    adopted: Vec<Box<[T]>>,
+    /// `'static` slices that were interned without copying them.
+    statics: Vec<StaticSlice<T>>,
+    /// How many elements have been allocated in the arena, or adopted.
+    len: usize,
+    /// The arena does not report its capacity, so its chunks are tracked here.
+    #[cfg(not(feature = "page-pool"))]
+    chunks: Chunks,
+    /// The arena cannot be reset, so the memory kept by `clear_retaining_capacity` lives here.
+    #[cfg(not(feature = "page-pool"))]
+    retained: Retained<T>,
+}
+
+/// A chunk that is filled before the arena, and reused instead of freed on clear.
+#[cfg(not(feature = "page-pool"))]
+struct Retained<T> {
+    /// Only used for its allocation, so its length is always zero.
+    buf: Vec<T>,
+    /// How many elements of `buf` have been handed out.
+    len: usize,
+}
+
+#[cfg(not(feature = "page-pool"))]
+impl<T> Retained<T> {
+    fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn alloc_uninit(&mut self, len: usize) -> Option<&mut [MaybeUninit<T>]> {
+        if len > self.buf.capacity() - self.len {
+            return None;
+        }
+        // `as_mut_ptr` does not create a reference to the buffer, so the slices handed out
+        // before stay valid.
+        // Safety: `self.len + len` is within the capacity of `buf`.
+        let ptr = unsafe { self.buf.as_mut_ptr().add(self.len) };
+        self.len += len;
+        // Safety: these elements are allocated, and no slice handed out before overlaps them.
+        Some(unsafe { std::slice::from_raw_parts_mut(ptr.cast::<MaybeUninit<T>>(), len) })
+    }
+}
+
+/// Mirrors how `typed_arena` grows its chunks, to count the elements it has reserved.
+#[cfg(not(feature = "page-pool"))]
+#[derive(Clone, Copy)]
+struct Chunks {
+    /// The capacity of the current chunk.
+    capacity: usize,
+    /// How many elements of the current chunk are in use.
+    len: usize,
+    /// The total capacity of every chunk.
+    reserved: usize,
+}
+
+#[cfg(not(feature = "page-pool"))]
+impl Chunks {
+    /// The first chunk of `Arena::new`, which is 1KiB.
+    fn new<T>() -> Self {
+        Self::with_capacity(1024 / std::mem::size_of::<T>().max(1))
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            len: 0,
+            reserved: capacity,
+        }
+    }
+
+    fn alloc(&mut self, len: usize) {
+        if len > self.capacity - self.len {
+            self.capacity = usize::max(self.capacity * 2, len.next_power_of_two());
+            self.len = 0;
+            self.reserved += self.capacity;
+        }
+        self.len += len;
+    }
+}
 
 impl<T> Default for Alloc<T> {
     fn default() -> Self {
-        Self(SyncWrapper::new(Arena::new()))
+        Self {
+            #[cfg(not(feature = "page-pool"))]
+            arena: SyncWrapper::new(Arena::new()),
+            #[cfg(feature = "page-pool")]
+            arena: crate::pool::Pages::default(),
+            adopted: Vec::new(),
+            statics: Vec::new(),
+            len: 0,
+            #[cfg(not(feature = "page-pool"))]
+            chunks: Chunks::new::<T>(),
+            #[cfg(not(feature = "page-pool"))]
+            retained: Retained::new(),
+        }
     }
 }
 
 impl<T> Alloc<T> {
-    #[cfg(test)]
-    pub(super) fn size(&mut self) -> usize {
-        self.0.get_mut().len() * std::mem::size_of::<T>()
+    /// Create an allocator whose first chunk fits `capacity` elements.
+    ///
+    /// With the `page-pool` feature, pages are a fixed size, so this is the same as the default.
+    pub(super) fn with_capacity(capacity: usize) -> Self {
+        #[cfg(feature = "page-pool")]
+        let _ = capacity;
+        Self {
+            #[cfg(not(feature = "page-pool"))]
+            arena: SyncWrapper::new(Arena::with_capacity(capacity)),
+            #[cfg(feature = "page-pool")]
+            arena: crate::pool::Pages::default(),
+            adopted: Vec::new(),
+            statics: Vec::new(),
+            len: 0,
+            #[cfg(not(feature = "page-pool"))]
+            chunks: Chunks::with_capacity(capacity),
+            #[cfg(not(feature = "page-pool"))]
+            retained: Retained::new(),
+        }
+    }
+
+    /// Free every allocation, but keep the memory for new allocations where possible.
+    ///
+    /// With the `page-pool` feature, the pages are kept and refilled. Otherwise the retained
+    /// chunk is kept and refilled. If the arena had to be used because the retained chunk was
+    /// too small, the arena is freed and the retained chunk grows to fit every element, so
+    /// refilling to the same size does not allocate.
+    pub(super) fn clear_retaining_capacity(&mut self) {
+        #[cfg(not(feature = "page-pool"))]
+        {
+            let adopted: usize = self.adopted.iter().map(|s| s.len()).sum();
+            let len = self.len - adopted;
+            if len > self.retained.buf.capacity() {
+                self.retained.buf = Vec::new();
+                self.retained.buf.reserve_exact(len);
+            }
+            self.retained.len = 0;
+            if self.arena.get_mut().len() > 0 {
+                self.arena = SyncWrapper::new(Arena::new());
+                self.chunks = Chunks::new::<T>();
+            }
+        }
+        #[cfg(feature = "page-pool")]
+        self.arena.reset();
+        self.adopted.clear();
+        self.statics.clear();
+        self.len = 0;
+    }
+
+    /// The bytes used by the allocated elements.
+    pub(super) fn used(&self) -> usize {
+        self.len * std::mem::size_of::<T>()
+    }
+
+    /// The bytes held by this allocator, including unused space in the arena, alignment
+    /// padding, and the list of adopted slices.
+    pub(super) fn reserved(&self) -> usize {
+        let adopted: usize = self.adopted.iter().map(|s| s.len()).sum();
+        let adopted = adopted * std::mem::size_of::<T>()
+            + self.adopted.capacity() * std::mem::size_of::<Box<[T]>>()
+            + self.statics.capacity() * std::mem::size_of::<StaticSlice<T>>();
+
+        #[cfg(not(feature = "page-pool"))]
+        let arena =
+            (self.chunks.reserved + self.retained.buf.capacity()) * std::mem::size_of::<T>();
+        #[cfg(feature = "page-pool")]
+        let arena = self.arena.reserved();
+
+        adopted + arena
+    }
+
+    /// Take ownership of the slice, without copying it.
+    ///
+    /// The slice lives on the heap, so it does not move when `adopted` grows.
+    pub(super) fn adopt(&mut self, s: Box<[T]>) -> &[T] {
+        self.len += s.len();
+        self.adopted.push(s);
+        self.adopted.last().expect("just pushed")
+    }
+
+    /// Remember a `'static` slice, without copying it.
+    pub(super) fn borrow_static(&mut self, s: &'static [T]) -> &'static [T] {
+        self.statics.push(StaticSlice::new(s));
+        s
+    }
+
+    /// Store a slice taken by [`Alloc::take_unowned`], without copying it.
+    pub(super) fn keep(&mut self, s: Unowned<T>) -> &[T] {
+        match s {
+            Unowned::Static(s) => {
+                self.statics.push(s);
+                s.slice()
+            }
+            Unowned::Adopted(s) => self.adopt(s),
+        }
+    }
+
+    /// Take every slice that is not stored in the arena, so that it can be kept where it is.
+    pub(super) fn take_unowned(&mut self) -> impl Iterator<Item = Unowned<T>> + '_ {
+        let adopted: usize = self.adopted.iter().map(|s| s.len()).sum();
+        self.len -= adopted;
+        let statics = self.statics.drain(..).map(Unowned::Static);
+        statics.chain(self.adopted.drain(..).map(Unowned::Adopted))
+    }
+}
+
+/// A slice that was interned without copying it into the arena.
+pub(super) enum Unowned<T> {
+    Static(StaticSlice<T>),
+    Adopted(Box<[T]>),
+}
+
+impl<T> AsRef<[T]> for Unowned<T> {
+    fn as_ref(&self) -> &[T] {
+        match self {
+            Unowned::Static(s) => s.slice(),
+            Unowned::Adopted(s) => s,
+        }
+    }
+}
+
+/// Represents a `&'static [T]`, without requiring `T: 'static` everywhere it is stored.
+pub(super) struct StaticSlice<T> {
+    ptr: *const T,
+    len: usize,
+}
+
+impl<T> Clone for StaticSlice<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for StaticSlice<T> {}
+
+// Safety: `StaticSlice` has the same safety requirements as `&[T]`
+unsafe impl<T: Sync> Sync for StaticSlice<T> {}
+// Safety: `StaticSlice` has the same safety requirements as `&[T]`
+unsafe impl<T: Sync> Send for StaticSlice<T> {}
+
+impl<T> StaticSlice<T> {
+    fn new(s: &'static [T]) -> Self {
+        Self {
+            ptr: s.as_ptr(),
+            len: s.len(),
+        }
+    }
+
+    fn slice<'a>(self) -> &'a [T] {
+        // Safety: the ptr and len came from a &'static [T] to begin with.
+        unsafe { &*core::ptr::slice_from_raw_parts(self.ptr, self.len) }
     }
 }
 
@@ -39,8 +288,8 @@ unsafe impl<T: Sync> Sync for InternedPtr<T> {}
 unsafe impl<T: Sync> Send for InternedPtr<T> {}
 
 impl<T> InternedPtr<T> {
-    fn new(s: &[T], key: Key) -> Self {
-        let len = u32::try_from(s.len()).expect("slice lengths must be less than u32::MAX");
+    pub(super) fn new(s: &[T], key: Key) -> Self {
+        let len = u32::try_from(s.len()).expect(super::TOO_LONG);
         Self {
             ptr: s.as_ptr(),
             len,
@@ -48,7 +297,40 @@ impl<T> InternedPtr<T> {
         }
     }
 
+    /// Represents a key whose slice has been removed.
+    pub(super) fn tombstone(key: Key) -> Self {
+        Self {
+            ptr: core::ptr::null(),
+            len: 0,
+            key,
+        }
+    }
+
+    pub(super) fn is_tombstone(&self) -> bool {
+        self.ptr.is_null()
+    }
+
+    /// Get the slice, or `None` if this is a tombstone.
+    #[inline]
+    pub(super) fn get(&self) -> Option<&[T]> {
+        if self.is_tombstone() {
+            None
+        } else {
+            Some(self.slice())
+        }
+    }
+
+    /// Get the length of the slice, without reading it.
+    pub(super) fn slice_len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Get the slice. A tombstone is an empty slice, since its pointer is null.
+    #[inline]
     pub(super) fn slice(&self) -> &[T] {
+        if self.is_tombstone() {
+            return &[];
+        }
         // Safety: the ptr and len came from a &[T] to begin with.
         unsafe { &*core::ptr::slice_from_raw_parts(self.ptr, self.len as usize) }
     }
@@ -56,7 +338,7 @@ impl<T> InternedPtr<T> {
 
 impl<T: Copy> Alloc<T> {
     #[inline]
-    fn alloc(&mut self, s: &[T]) -> &mut [T] {
+    pub(super) fn alloc(&mut self, s: &[T]) -> &mut [T] {
         /// Polyfill for [`MaybeUninit::copy_from_slice`]
         fn copy_from_slice<'a, T: Copy>(this: &'a mut [MaybeUninit<T>], src: &[T]) -> &'a mut [T] {
             let uninit_src: &[MaybeUninit<T>] =
@@ -76,10 +358,19 @@ impl<T: Copy> Alloc<T> {
             unsafe { &mut *(slice as *mut [MaybeUninit<T>] as *mut [T]) }
         }
 
-        let arena = self.0.get_mut();
+        self.len += s.len();
 
-        // Safety: we are making sure to init all the elements without panicking.
-        let uninit = unsafe { arena.alloc_uninitialized(s.len()) };
+        #[cfg(not(feature = "page-pool"))]
+        let uninit = match self.retained.alloc_uninit(s.len()) {
+            Some(uninit) => uninit,
+            None => {
+                self.chunks.alloc(s.len());
+                // Safety: we are making sure to init all the elements without panicking.
+                unsafe { self.arena.get_mut().alloc_uninitialized(s.len()) }
+            }
+        };
+        #[cfg(feature = "page-pool")]
+        let uninit = self.arena.alloc_uninit(s.len());
         copy_from_slice(uninit, s)
     }
 }
@@ -87,61 +378,172 @@ impl<T: Copy> Alloc<T> {
 impl<T: Hash + Eq + Copy, S: BuildHasher> ParaCord<T, S> {
     #[cold]
     pub(super) fn intern_slow(&self, s: &[T], hash: u64) -> Key {
-        let _len = u32::try_from(s.len()).expect("slice lengths must be less than u32::MAX");
-
-        let Collection { table, alloc } = &mut *self.slice_to_keys.get_write_shard(hash);
+        self.intern_slow_inserted(s, hash).0
+    }
 
-        // safety: k is allocated correct
-        let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
-        // safety: k is allocated correct
-        let hasher = |k: &*const InternedPtr<T>| unsafe { self.hasher.hash_one((**k).slice()) };
+    /// Like [`ParaCord::intern_slow`], but also returns whether the slice was newly interned.
+    #[cold]
+    pub(super) fn intern_slow_inserted(&self, s: &[T], hash: u64) -> (Key, bool) {
+        self.bloom_insert(hash);
+        let (key, inserted) = self.slice_to_keys.get_write_shard(hash).intern(
+            &self.keys_to_slice,
+            self.base,
+            &self.hasher,
+            s,
+            hash,
+        );
+        // the shard lock is released before calling into user code.
+        if inserted {
+            self.record_insert(s.len());
+        }
+        (key, inserted)
+    }
 
-        match table.entry(hash, eq, hasher) {
-            // safety: entry is allocated correct
-            Entry::Occupied(entry) => unsafe { (**entry.get()).key },
-            Entry::Vacant(entry) => {
-                let key = self.keys_to_slice.push_with(|key| {
-                    let key = Key::from_index(key);
-                    let s = alloc.alloc(s);
-                    InternedPtr::new(s, key)
-                });
+    #[cold]
+    pub(super) fn intern_slow_boxed(&self, s: Box<[T]>, hash: u64) -> Key {
+        let len = s.len();
+        self.bloom_insert(hash);
+        let (key, inserted) = self.slice_to_keys.get_write_shard(hash).intern_boxed(
+            &self.keys_to_slice,
+            self.base,
+            &self.hasher,
+            s,
+            hash,
+        );
+        // the shard lock is released before calling into user code.
+        if inserted {
+            self.record_insert(len);
+        }
+        key
+    }
 
-                // safety: we have just inserted this entry
-                let interned_ptr = unsafe { self.keys_to_slice.get_unchecked(key) };
-                entry.insert(interned_ptr as *const InternedPtr<T>);
+    #[cold]
+    pub(super) fn intern_slow_static(&self, s: &'static [T], hash: u64) -> Key {
+        self.bloom_insert(hash);
+        let (key, inserted) = self.slice_to_keys.get_write_shard(hash).intern_static(
+            &self.keys_to_slice,
+            self.base,
+            &self.hasher,
+            s,
+            hash,
+        );
+        // the shard lock is released before calling into user code.
+        if inserted {
+            self.record_insert(s.len());
+        }
+        key
+    }
 
-                interned_ptr.key
-            }
+    /// Like [`ParaCord::intern_slow_mut`], but keeps a slice taken by [`Alloc::take_unowned`]
+    /// where it is.
+    pub(super) fn intern_unowned_mut(&mut self, s: Unowned<T>) -> Key {
+        let len = s.as_ref().len();
+        let hash = self.hasher.hash_one(s.as_ref());
+        self.bloom_insert(hash);
+        let shard = self.slice_to_keys.get_mut(hash);
+        let (key, inserted) = shard.intern_with(
+            &self.keys_to_slice,
+            self.base,
+            &self.hasher,
+            s,
+            hash,
+            Alloc::keep,
+        );
+        if inserted {
+            self.record_insert(len);
         }
+        key
     }
 
     #[cold]
     pub(super) fn intern_slow_mut(&mut self, s: &[T], hash: u64) -> Key {
-        let _len = u32::try_from(s.len()).expect("slice lengths must be less than u32::MAX");
+        self.bloom_insert(hash);
+        let shard = self.slice_to_keys.get_mut(hash);
+        let (key, inserted) = shard.intern(&self.keys_to_slice, self.base, &self.hasher, s, hash);
+        if inserted {
+            self.record_insert(s.len());
+        }
+        key
+    }
+}
+
+impl<T: Hash + Eq + Copy> Collection<T> {
+    /// Intern the slice into this shard, allocating the next key in `keys_to_slice` if it is new.
+    ///
+    /// Returns the key, and whether it was newly allocated.
+    pub(super) fn intern(
+        &mut self,
+        keys_to_slice: &boxcar::Vec<InternedPtr<T>>,
+        base: usize,
+        hasher: &impl BuildHasher,
+        s: &[T],
+        hash: u64,
+    ) -> (Key, bool) {
+        self.intern_with(keys_to_slice, base, hasher, s, hash, |alloc, s| {
+            alloc.alloc(s)
+        })
+    }
+
+    /// Like [`Collection::intern`], but takes ownership of the slice, adopting it if it is new.
+    pub(super) fn intern_boxed(
+        &mut self,
+        keys_to_slice: &boxcar::Vec<InternedPtr<T>>,
+        base: usize,
+        hasher: &impl BuildHasher,
+        s: Box<[T]>,
+        hash: u64,
+    ) -> (Key, bool) {
+        self.intern_with(keys_to_slice, base, hasher, s, hash, Alloc::adopt)
+    }
 
-        let Collection { table, alloc } = &mut *self.slice_to_keys.get_mut(hash);
+    /// Like [`Collection::intern`], but only stores a pointer to the slice if it is new.
+    pub(super) fn intern_static(
+        &mut self,
+        keys_to_slice: &boxcar::Vec<InternedPtr<T>>,
+        base: usize,
+        hasher: &impl BuildHasher,
+        s: &'static [T],
+        hash: u64,
+    ) -> (Key, bool) {
+        self.intern_with(keys_to_slice, base, hasher, s, hash, |alloc, s| {
+            alloc.borrow_static(s)
+        })
+    }
+
+    /// Intern the slice into this shard, calling `store` to store the slice if it is new.
+    fn intern_with<A: AsRef<[T]>>(
+        &mut self,
+        keys_to_slice: &boxcar::Vec<InternedPtr<T>>,
+        base: usize,
+        hasher: &impl BuildHasher,
+        s: A,
+        hash: u64,
+        store: impl FnOnce(&mut Alloc<T>, A) -> &[T],
+    ) -> (Key, bool) {
+        let _len = u32::try_from(s.as_ref().len()).expect(super::TOO_LONG);
+
+        let Collection { table, alloc } = self;
 
         // safety: k is allocated correct
-        let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
+        let eq = |k: &*const InternedPtr<T>| unsafe { s.as_ref() == (**k).slice() };
         // safety: k is allocated correct
-        let hasher = |k: &*const InternedPtr<T>| unsafe { self.hasher.hash_one((**k).slice()) };
+        let hasher = |k: &*const InternedPtr<T>| unsafe { hasher.hash_one((**k).slice()) };
 
         match table.entry(hash, eq, hasher) {
             // safety: entry is allocated correct
-            Entry::Occupied(entry) => unsafe { (**entry.get()).key },
+            Entry::Occupied(entry) => (unsafe { (**entry.get()).key }, false),
             Entry::Vacant(entry) => {
-                let key = self.keys_to_slice.push_with(|key| {
-                    let key = Key::from_index(key);
-                    let s = alloc.alloc(s);
+                let key = keys_to_slice.push_with(|key| {
+                    let key = Key::from_index(base + key);
+                    let s = store(alloc, s);
                     InternedPtr::new(s, key)
                 });
 
                 // safety: we have just inserted this entry
-                let interned_ptr = unsafe { self.keys_to_slice.get_unchecked(key) };
-
+                let interned_ptr = unsafe { keys_to_slice.get_unchecked(key) };
                 entry.insert(interned_ptr as *const InternedPtr<T>);
 
-                interned_ptr.key
+                (interned_ptr.key, true)
             }
         }
     }
@@ -0,0 +1,77 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A bloom filter of slice hashes, consulted before probing the shards.
+///
+/// Bits are only ever set while the instance is shared, so a slice that is not in the filter is
+/// definitely not interned. Removed slices stay in the filter until it is rebuilt.
+pub(super) struct BloomFilter {
+    bits: Box<[AtomicU64]>,
+    /// `bits.len() * 64 - 1`. The number of bits is a power of two.
+    mask: u64,
+    hashes: u32,
+}
+
+impl BloomFilter {
+    /// Create a filter with the given false positive rate, once `expected` slices are interned.
+    #[track_caller]
+    pub(super) fn new(expected: usize, false_positive_rate: f64) -> Self {
+        assert!(
+            false_positive_rate > 0.0 && false_positive_rate < 1.0,
+            "false positive rate must be between 0 and 1"
+        );
+
+        let ln2 = core::f64::consts::LN_2;
+        let expected = expected.max(1) as f64;
+        let bits = (-expected * false_positive_rate.ln() / (ln2 * ln2)).ceil();
+        let hashes = (bits / expected * ln2).round().clamp(1.0, 16.0) as u32;
+
+        let words = (bits as usize).div_ceil(64).next_power_of_two();
+        Self::with_words(words, hashes)
+    }
+
+    fn with_words(words: usize, hashes: u32) -> Self {
+        Self {
+            bits: (0..words).map(|_| AtomicU64::new(0)).collect(),
+            mask: (words * 64 - 1) as u64,
+            hashes,
+        }
+    }
+
+    /// Create an empty filter with the same size.
+    pub(super) fn empty_like(&self) -> Self {
+        Self::with_words(self.bits.len(), self.hashes)
+    }
+
+    /// The bit positions of the hash.
+    fn positions(&self, hash: u64) -> impl Iterator<Item = (usize, u64)> + '_ {
+        // the shards use the same hash, so mix it before splitting it up.
+        let hash = hash.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        let h1 = hash >> 32;
+        let h2 = (hash & 0xffff_ffff) | 1;
+        (0..u64::from(self.hashes)).map(move |i| {
+            let bit = h1.wrapping_add(i.wrapping_mul(h2)) & self.mask;
+            ((bit / 64) as usize, 1 << (bit % 64))
+        })
+    }
+
+    pub(super) fn insert(&self, hash: u64) {
+        for (word, bit) in self.positions(hash) {
+            self.bits[word].fetch_or(bit, Ordering::Release);
+        }
+    }
+
+    /// Returns `false` if no slice with this hash has been inserted.
+    pub(super) fn may_contain(&self, hash: u64) -> bool {
+        self.positions(hash)
+            .all(|(word, bit)| self.bits[word].load(Ordering::Acquire) & bit != 0)
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.bits.iter_mut().for_each(|w| *w.get_mut() = 0);
+    }
+
+    /// The memory used by the filter, in bytes.
+    pub(super) fn size(&self) -> usize {
+        self.bits.len() * 8
+    }
+}
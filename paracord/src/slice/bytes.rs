@@ -0,0 +1,127 @@
+use core::fmt;
+#[cfg(feature = "serde_bytes")]
+use std::hash::BuildHasher;
+
+use base64::engine::general_purpose::STANDARD;
+
+use crate::slice::ParaCord;
+use crate::Key;
+
+/// Displays a byte slice as standard padded base64. Created with [`ParaCord::display_base64`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base64Display<'a>(&'a [u8]);
+
+impl fmt::Display for Base64Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        base64::display::Base64Display::new(self.0, &STANDARD).fmt(f)
+    }
+}
+
+impl<S> ParaCord<u8, S> {
+    /// Display the bytes associated with this [`Key`] as base64.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`ParaCord::resolve`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let key = paracord.get_or_intern(b"hello");
+    /// assert_eq!(paracord.display_base64(key).to_string(), "aGVsbG8=");
+    /// ```
+    #[must_use]
+    #[track_caller]
+    #[cfg_attr(docsrs, doc(cfg(feature = "base64")))]
+    pub fn display_base64(&self, key: Key) -> Base64Display<'_> {
+        Base64Display(self.resolve(key))
+    }
+
+    /// Get a serializable view of the bytes associated with this [`Key`].
+    ///
+    /// Binary formats serialize the bytes like `serde_bytes` does, and human-readable formats
+    /// serialize them as a base64 string. Deserialize them again with [`ParaCord::bytes_seed`].
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`ParaCord::resolve`].
+    #[cfg(feature = "serde_bytes")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_bytes")))]
+    #[must_use]
+    #[track_caller]
+    pub fn serialize_bytes(&self, key: Key) -> BytesKey<'_> {
+        BytesKey(self.resolve(key))
+    }
+
+    /// Get a [`DeserializeSeed`](serde::de::DeserializeSeed) that interns bytes serialized by
+    /// [`ParaCord::serialize_bytes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::de::value::{Error, StrDeserializer};
+    /// use serde::de::DeserializeSeed;
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let key = paracord.get_or_intern(b"hello");
+    ///
+    /// let de = StrDeserializer::<Error>::new("aGVsbG8=");
+    /// assert_eq!(paracord.bytes_seed().deserialize(de).unwrap(), key);
+    /// ```
+    #[cfg(feature = "serde_bytes")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_bytes")))]
+    pub fn bytes_seed(&self) -> BytesSeed<'_, S> {
+        BytesSeed(self)
+    }
+}
+
+/// Serializes bytes from a [`ParaCord`]. Created with [`ParaCord::serialize_bytes`].
+#[cfg(feature = "serde_bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_bytes")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BytesKey<'a>(&'a [u8]);
+
+#[cfg(feature = "serde_bytes")]
+impl serde::Serialize for BytesKey<'_> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&Base64Display(self.0))
+        } else {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+}
+
+/// Interns bytes serialized by [`BytesKey`]. Created with [`ParaCord::bytes_seed`].
+#[cfg(feature = "serde_bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_bytes")))]
+pub struct BytesSeed<'a, S>(&'a ParaCord<u8, S>);
+
+#[cfg(feature = "serde_bytes")]
+impl<S> fmt::Debug for BytesSeed<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde_bytes")]
+impl<'de, S: BuildHasher> serde::de::DeserializeSeed<'de> for BytesSeed<'_, S> {
+    type Value = Key;
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Key, D::Error> {
+        use base64::Engine;
+        use serde::Deserialize;
+
+        let bytes = if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            STANDARD.decode(s).map_err(serde::de::Error::custom)?
+        } else {
+            serde_bytes::ByteBuf::deserialize(deserializer)?.into_vec()
+        };
+        Ok(self.0.get_or_intern_boxed(bytes.into_boxed_slice()))
+    }
+}
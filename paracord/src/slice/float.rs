@@ -0,0 +1,136 @@
+use core::cmp::Ordering;
+use core::fmt;
+use std::hash::{BuildHasher, Hash, Hasher};
+
+use super::ParaCord;
+use crate::Key;
+
+/// A float that is compared and hashed by its bit pattern, so that slices of floats can be
+/// interned.
+///
+/// Two floats are equal if they have the same bits, so `0.0` and `-0.0` are different, and a
+/// `NaN` is equal to any `NaN` with the same bits. Floats are ordered by their `total_cmp`, which
+/// agrees with this equality.
+///
+/// [`ParaCord`]s of `FloatBits<f32>` and `FloatBits<f64>` also have methods that take and return
+/// plain float slices, without copying them.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::slice::{FloatBits, ParaCord};
+/// let paracord = ParaCord::<FloatBits<f32>>::default();
+///
+/// let features = paracord.get_or_intern_floats(&[0.5, 1.0, f32::NAN]);
+/// assert_eq!(paracord.get_floats(&[0.5, 1.0, f32::NAN]), Some(features));
+/// assert_eq!(paracord.get_floats(&[-0.5, 1.0, f32::NAN]), None);
+/// assert_eq!(paracord.resolve_floats(features)[..2], [0.5, 1.0]);
+/// ```
+#[derive(Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct FloatBits<F>(pub F);
+
+impl<F> FloatBits<F> {
+    /// Wrap the float.
+    pub const fn new(f: F) -> Self {
+        Self(f)
+    }
+
+    /// Get back the float.
+    pub fn into_inner(self) -> F {
+        self.0
+    }
+}
+
+impl<F: fmt::Debug> fmt::Debug for FloatBits<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<F> From<F> for FloatBits<F> {
+    fn from(f: F) -> Self {
+        Self(f)
+    }
+}
+
+macro_rules! float_bits {
+    ($($float:ty),*) => {$(
+        impl PartialEq for FloatBits<$float> {
+            fn eq(&self, other: &Self) -> bool {
+                self.0.to_bits() == other.0.to_bits()
+            }
+        }
+
+        impl Eq for FloatBits<$float> {}
+
+        impl PartialOrd for FloatBits<$float> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for FloatBits<$float> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+
+        impl Hash for FloatBits<$float> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.0.to_bits().hash(state);
+            }
+        }
+
+        impl FloatBits<$float> {
+            #[doc = concat!("View a slice of `", stringify!($float), "` as a slice of `FloatBits`, without copying.")]
+            pub fn from_slice(s: &[$float]) -> &[Self] {
+                // Safety: `FloatBits` is a transparent wrapper.
+                unsafe { &*(s as *const [$float] as *const [Self]) }
+            }
+
+            #[doc = concat!("View a slice of `FloatBits` as a slice of `", stringify!($float), "`, without copying.")]
+            pub fn as_floats(s: &[Self]) -> &[$float] {
+                // Safety: `FloatBits` is a transparent wrapper.
+                unsafe { &*(s as *const [Self] as *const [$float]) }
+            }
+        }
+
+        impl<S> ParaCord<FloatBits<$float>, S> {
+            /// Try and resolve the floats associated with this [`Key`].
+            ///
+            /// This can return [`None`] if the key was not allocated by this instance.
+            #[must_use]
+            pub fn try_resolve_floats(&self, key: Key) -> Option<&[$float]> {
+                self.try_resolve(key).map(FloatBits::<$float>::as_floats)
+            }
+
+            /// Resolve the floats associated with this [`Key`].
+            ///
+            /// # Panics
+            /// This can panic if the key was not allocated by this instance.
+            #[must_use]
+            #[track_caller]
+            pub fn resolve_floats(&self, key: Key) -> &[$float] {
+                FloatBits::<$float>::as_floats(self.resolve(key))
+            }
+        }
+
+        impl<S: BuildHasher> ParaCord<FloatBits<$float>, S> {
+            /// Try and get the [`Key`] associated with the given floats, compared by their bits.
+            /// Returns [`None`] if not found.
+            #[must_use]
+            pub fn get_floats(&self, s: &[$float]) -> Option<Key> {
+                self.get(FloatBits::<$float>::from_slice(s))
+            }
+
+            /// Try and get the [`Key`] associated with the given floats, compared by their bits.
+            /// Allocates a new key if not found.
+            pub fn get_or_intern_floats(&self, s: &[$float]) -> Key {
+                self.get_or_intern(FloatBits::<$float>::from_slice(s))
+            }
+        }
+    )*};
+}
+
+float_bits!(f32, f64);
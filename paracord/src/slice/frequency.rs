@@ -0,0 +1,187 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use std::hash::{BuildHasher, Hash};
+
+use crate::collections::{KeyOnceMap, RemapTable};
+use crate::slice::ParaCord;
+use crate::Key;
+
+/// Only 1 in this many accesses are counted.
+pub(super) const SAMPLE_RATE: u32 = 64;
+/// How many keys share one lazily allocated block of counters.
+const CHUNK: usize = 64;
+
+/// Approximate, sampled, access counts for each key.
+#[derive(Default)]
+pub(super) struct Frequency {
+    chunks: KeyOnceMap<[AtomicU8; CHUNK]>,
+}
+
+fn sample() -> bool {
+    thread_local! {
+        static RNG: Cell<u32> = const { Cell::new(0x9e37_79b9) };
+    }
+
+    // xorshift32
+    RNG.with(|rng| {
+        let mut x = rng.get();
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        rng.set(x);
+        x % SAMPLE_RATE == 0
+    })
+}
+
+impl Frequency {
+    #[inline]
+    pub(super) fn record(&self, key: Key) {
+        if sample() {
+            self.record_slow(key);
+        }
+    }
+
+    #[cold]
+    fn record_slow(&self, key: Key) {
+        let i = key.into_repr() as usize;
+        let chunk = self.chunks.get_or_init(Key::from_index(i / CHUNK), || {
+            core::array::from_fn(|_| AtomicU8::new(0))
+        });
+
+        // lossy under contention, but these counts are only approximate anyway.
+        let count = &chunk[i % CHUNK];
+        let n = count.load(Ordering::Relaxed);
+        if n < u8::MAX {
+            count.store(n + 1, Ordering::Relaxed);
+        }
+    }
+
+    /// Halve every sampled count, so that old accesses count for less than new ones.
+    ///
+    /// Returns how many counts were non-zero.
+    pub(super) fn decay(&mut self) -> usize {
+        let mut decayed = 0;
+        for (_, counts) in self.chunks.iter() {
+            for count in counts {
+                let n = count.load(Ordering::Relaxed);
+                if n > 0 {
+                    count.store(n / 2, Ordering::Relaxed);
+                    decayed += 1;
+                }
+            }
+        }
+        decayed
+    }
+
+    /// Iterate over the sampled count of every key that has been sampled at least once.
+    pub(super) fn iter(&self) -> impl Iterator<Item = (Key, u8)> + '_ {
+        self.chunks.iter().flat_map(|(chunk, counts)| {
+            let start = chunk.into_repr() as usize * CHUNK;
+            counts.iter().enumerate().filter_map(move |(i, n)| {
+                let n = n.load(Ordering::Relaxed);
+                (n > 0).then(|| (Key::from_index(start + i), n))
+            })
+        })
+    }
+}
+
+impl<T, S> ParaCord<T, S> {
+    /// Get the `n` most frequently accessed keys, with their approximate access counts,
+    /// most frequent first.
+    ///
+    /// Accesses by [`ParaCord::get`] and [`ParaCord::get_or_intern`] are sampled, so
+    /// keys that are rarely accessed might not be counted, and counts saturate at
+    /// `255 * 64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let hot = paracord.get_or_intern(&[1]);
+    /// for _ in 0..10_000 {
+    ///     paracord.get(&[1]);
+    /// }
+    /// paracord.get_or_intern(&[2]);
+    ///
+    /// let top = paracord.top_k(1);
+    /// assert_eq!(top[0].0, hot);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "frequency")))]
+    #[must_use]
+    pub fn top_k(&self, n: usize) -> Vec<(Key, u32)> {
+        let mut counts: Vec<_> = self
+            .frequency
+            .iter()
+            .filter(|&(key, _)| {
+                self.raw_slot(key)
+                    .and_then(|slot| self.keys_to_slice.get(slot))
+                    .is_some_and(|s| !s.is_tombstone())
+            })
+            .map(|(key, count)| (key, count as u32 * SAMPLE_RATE))
+            .collect();
+        counts.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts.truncate(n);
+        counts
+    }
+}
+
+impl<T: Hash + Eq + Copy, S: BuildHasher> ParaCord<T, S> {
+    /// Renumber the keys so that the most frequently accessed slices have the smallest keys.
+    ///
+    /// Accesses are sampled as described in [`ParaCord::top_k`]. Slices with the same count
+    /// keep their relative order, and removed keys are dropped, so the keys are dense again.
+    /// This makes streams of keys compress better, eg with [`wire`](crate::wire).
+    ///
+    /// The returned [`RemapTable`] maps the old keys to their new keys.
+    /// The sampled counts are reset, ready for the next sampling pass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let mut paracord = ParaCord::default();
+    ///
+    /// let cold = paracord.get_or_intern(&[1]);
+    /// let hot = paracord.get_or_intern(&[2]);
+    /// for _ in 0..10_000 {
+    ///     paracord.get(&[2]);
+    /// }
+    ///
+    /// let remap = paracord.rebuild_frequency_ordered();
+    /// let hot = remap.get(hot).unwrap();
+    /// let cold = remap.get(cold).unwrap();
+    /// assert!(hot < cold);
+    /// assert_eq!(paracord.resolve(hot), &[2]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "frequency")))]
+    pub fn rebuild_frequency_ordered(&mut self) -> RemapTable {
+        let mut counts: Vec<u8> = Vec::new();
+        for (key, n) in self.frequency.iter() {
+            let i = key.into_repr() as usize;
+            if i >= counts.len() {
+                counts.resize(i + 1, 0);
+            }
+            counts[i] = n;
+        }
+
+        let mut unowned = self.take_unowned();
+        let (old_keys, _old_shards) = self.take_storage(true);
+
+        let mut order: Vec<_> = old_keys
+            .iter()
+            .filter_map(|(_, ptr)| Some((ptr.key, ptr.get()?)))
+            .collect();
+        let count = |key: Key| counts.get(key.into_repr() as usize).copied().unwrap_or(0);
+        order.sort_by_key(|&(key, _)| core::cmp::Reverse(count(key)));
+
+        let mut remap = RemapTable::with_capacity(old_keys.count());
+        for (old, s) in order {
+            let new = self.push_kept_mut(s, &mut unowned);
+            remap.insert(old, new);
+        }
+        remap
+    }
+}
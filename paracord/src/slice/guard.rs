@@ -0,0 +1,119 @@
+use core::cell::OnceCell;
+use core::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::ops::Deref;
+
+use crate::slice::{Collection, InternedPtr, ParaCord};
+use crate::Key;
+
+/// A shard read lock, which is held until the guard is dropped.
+type ShardGuard<'a, T> = Box<dyn Deref<Target = Collection<T>> + 'a>;
+
+/// A guard for doing many reads of a [`ParaCord`], which keeps every shard lock it takes.
+/// Created with [`ParaCord::guard`].
+///
+/// [`ParaCord::get`] takes a shard read lock for every call. [`ReadGuard::get`] only takes each
+/// shard's read lock the first time that shard is needed, so tight loops of lookups don't pay
+/// for synchronization on every call.
+///
+/// While the guard is alive, slices cannot be interned into any shard it has locked. Interning into
+/// the same instance from the thread holding the guard may never return, so drop the guard first.
+pub struct ReadGuard<'a, T, S> {
+    paracord: &'a ParaCord<T, S>,
+    shards: Box<[OnceCell<ShardGuard<'a, T>>]>,
+}
+
+impl<T: fmt::Debug, S> fmt::Debug for ReadGuard<'_, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.paracord.fmt(f)
+    }
+}
+
+impl<T, S> ParaCord<T, S> {
+    /// Get a [`ReadGuard`], for doing many lookups without taking a lock for every one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    /// let foo = paracord.get_or_intern(&[1, 2, 3]);
+    ///
+    /// let guard = paracord.guard();
+    /// for _ in 0..100 {
+    ///     assert_eq!(guard.get(&[1, 2, 3]), Some(foo));
+    ///     assert_eq!(guard.resolve(foo), &[1, 2, 3]);
+    /// }
+    /// drop(guard);
+    ///
+    /// paracord.get_or_intern(&[4, 5, 6]);
+    /// ```
+    #[must_use]
+    pub fn guard(&self) -> ReadGuard<'_, T, S> {
+        ReadGuard {
+            paracord: self,
+            shards: (0..self.shard_count()).map(|_| OnceCell::new()).collect(),
+        }
+    }
+}
+
+impl<'a, T, S> ReadGuard<'a, T, S> {
+    /// Get the [`ParaCord`] this guard reads from.
+    #[must_use]
+    pub fn paracord(&self) -> &'a ParaCord<T, S> {
+        self.paracord
+    }
+}
+
+impl<'a, T: Hash + Eq, S: BuildHasher> ReadGuard<'a, T, S> {
+    /// Try and resolve the slice associated with this [`Key`].
+    ///
+    /// See [`ParaCord::try_resolve`].
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&'a [T]> {
+        self.paracord.try_resolve(key)
+    }
+
+    /// Resolve the slice associated with this [`Key`].
+    ///
+    /// # Panics
+    /// See [`ParaCord::resolve`].
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &'a [T] {
+        self.paracord.resolve(key)
+    }
+
+    /// Try and get the [`Key`] associated with the given slice.
+    /// Returns [`None`] if not found.
+    ///
+    /// See [`ParaCord::get`].
+    #[must_use]
+    pub fn get(&self, s: &[T]) -> Option<Key> {
+        let paracord = self.paracord;
+        if s.is_empty() {
+            if let Some(&key) = paracord.empty_key.get() {
+                #[cfg(feature = "frequency")]
+                paracord.frequency.record(key);
+                return Some(key);
+            }
+        }
+
+        let hash = paracord.hasher.hash_one(s);
+        let index = paracord.shard_index(hash);
+        let shard = self.shards[index]
+            .get_or_init(|| Box::new(paracord.slice_to_keys.shards()[index].read()));
+
+        // safety: k is allocated correct
+        let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
+        // safety: k is allocated correct
+        let map = |k: &*const InternedPtr<T>| unsafe { (**k).key };
+
+        let key = shard.table.find(hash, eq).map(map);
+        #[cfg(feature = "frequency")]
+        if let Some(key) = key {
+            paracord.frequency.record(key);
+        }
+        key
+    }
+}
@@ -0,0 +1,182 @@
+use core::fmt;
+use core::mem::size_of;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use super::alloc::InternedPtr;
+
+/// Soft thresholds on the size of a [`ParaCord`](super::ParaCord).
+///
+/// Crossing a soft limit does not stop strings being interned, it only invokes the callback
+/// registered with [`ParaCord::with_soft_limits`](super::ParaCord::with_soft_limits), so services can
+/// alert or start shedding load early.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SoftLimits {
+    entries: Option<usize>,
+    memory: Option<usize>,
+}
+
+impl SoftLimits {
+    /// No limits.
+    pub const fn new() -> Self {
+        Self {
+            entries: None,
+            memory: None,
+        }
+    }
+
+    /// Notify when more than `limit` entries have been interned.
+    pub const fn entries(mut self, limit: usize) -> Self {
+        self.entries = Some(limit);
+        self
+    }
+
+    /// Notify when the interned data uses more than `limit` bytes.
+    ///
+    /// This counts the interned data, plus the per-key overhead, but not the hash tables.
+    pub const fn memory(mut self, limit: usize) -> Self {
+        self.memory = Some(limit);
+        self
+    }
+}
+
+/// Which soft limit was exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SoftLimitKind {
+    /// The [`SoftLimits::entries`] limit.
+    Entries,
+    /// The [`SoftLimits::memory`] limit.
+    Memory,
+}
+
+/// Passed to the soft limit callback when a limit is first exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SoftLimitEvent {
+    /// Which limit was exceeded.
+    pub kind: SoftLimitKind,
+    /// The configured limit.
+    pub limit: usize,
+    /// The value that exceeded the limit.
+    pub current: usize,
+}
+
+/// The bytes counted against the memory limits for a slice of length `len`.
+fn entry_size<T>(len: usize) -> usize {
+    len * size_of::<T>() + size_of::<InternedPtr<T>>()
+}
+
+type Callback = Box<dyn Fn(SoftLimitEvent) + Send + Sync>;
+
+pub(super) struct SoftLimitState {
+    limits: SoftLimits,
+    // a panicking callback cannot leave the interner in an inconsistent state,
+    // so this does not need to make the interner `!RefUnwindSafe`.
+    callback: AssertUnwindSafe<Callback>,
+    /// Bytes used by the interned data since the last reset.
+    memory: AtomicUsize,
+    entries_fired: AtomicBool,
+    memory_fired: AtomicBool,
+}
+
+impl fmt::Debug for SoftLimitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SoftLimitState")
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SoftLimitState {
+    pub(super) fn new(limits: SoftLimits, callback: Callback) -> Self {
+        Self {
+            limits,
+            callback: AssertUnwindSafe(callback),
+            memory: AtomicUsize::new(0),
+            entries_fired: AtomicBool::new(false),
+            memory_fired: AtomicBool::new(false),
+        }
+    }
+
+    /// Record a newly interned slice of length `len`, invoking the callback for every limit
+    /// that is now exceeded for the first time.
+    pub(super) fn record<T>(&self, entries: usize, len: usize) {
+        let bytes = entry_size::<T>(len);
+        let memory = self.memory.fetch_add(bytes, Ordering::Relaxed) + bytes;
+
+        let checks = [
+            (
+                SoftLimitKind::Entries,
+                self.limits.entries,
+                entries,
+                &self.entries_fired,
+            ),
+            (
+                SoftLimitKind::Memory,
+                self.limits.memory,
+                memory,
+                &self.memory_fired,
+            ),
+        ];
+        for (kind, limit, current, fired) in checks {
+            let Some(limit) = limit else { continue };
+            if current > limit && !fired.swap(true, Ordering::Relaxed) {
+                (self.callback)(SoftLimitEvent {
+                    kind,
+                    limit,
+                    current,
+                });
+            }
+        }
+    }
+
+    /// Forget all recorded entries, so every limit can fire again.
+    pub(super) fn clear(&mut self) {
+        *self.memory.get_mut() = 0;
+        *self.entries_fired.get_mut() = false;
+        *self.memory_fired.get_mut() = false;
+    }
+}
+
+/// A hard limit on the bytes used by the interned data, set with
+/// [`ParaCord::with_memory_limit`](super::ParaCord::with_memory_limit).
+#[derive(Debug)]
+pub(super) struct MemoryLimit {
+    limit: usize,
+    /// Bytes used by the interned data since the last reset.
+    used: AtomicUsize,
+}
+
+impl MemoryLimit {
+    pub(super) fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            used: AtomicUsize::new(0),
+        }
+    }
+
+    pub(super) fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Count a slice of length `len`, unless it would exceed the limit.
+    pub(super) fn try_reserve<T>(&self, len: usize) -> bool {
+        let bytes = entry_size::<T>(len);
+        self.used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(bytes).filter(|&used| used <= self.limit)
+            })
+            .is_ok()
+    }
+
+    /// Count a slice of length `len`, even if it exceeds the limit.
+    pub(super) fn charge<T>(&self, len: usize) {
+        self.used.fetch_add(entry_size::<T>(len), Ordering::Relaxed);
+    }
+
+    /// Forget all counted slices.
+    pub(super) fn clear(&mut self) {
+        *self.used.get_mut() = 0;
+    }
+}
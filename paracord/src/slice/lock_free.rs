@@ -0,0 +1,211 @@
+use core::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use super::alloc::InternedPtr;
+use crate::Key;
+
+/// The value of a slot whose key has not been allocated yet. This is the [`Key`] niche.
+const PENDING: u32 = u32::MAX;
+/// The value of a slot whose insert panicked before allocating its key. Keys are only allocated
+/// below this, so it is never the repr of a key.
+const POISONED: u32 = u32::MAX - 1;
+/// How many times to spin while waiting for a key, before yielding to the scheduler.
+const SPINS: u32 = 64;
+
+/// A variant of [`ParaCord`](super::ParaCord) that never takes a lock.
+///
+/// [`ParaCord`](super::ParaCord) protects each shard of its table with a read-write lock, so
+/// lookups contend with inserts into the same shard. This type stores its table in a
+/// [`papaya::HashMap`] instead, so lookups never wait, which suits read-heavy workloads with
+/// many threads.
+///
+/// The trade-offs are:
+/// * every slice is allocated separately, instead of in a shared arena.
+/// * an insert that races with an insert of the same slice waits for the other insert to
+///   allocate its key. If that insert panics, the slice can no longer be interned, and every
+///   later insert of it panics too.
+/// * removal, clearing and sweeping are not supported.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::slice::LockFreeParaCord;
+///
+/// let paracord = LockFreeParaCord::default();
+///
+/// let foo = paracord.get_or_intern(&[1,2,3,4]);
+/// let bar = paracord.get_or_intern(&[5,6,7,8]);
+///
+/// assert_ne!(foo, bar);
+/// assert_eq!(paracord.get(&[1,2,3,4]), Some(foo));
+/// assert_eq!(paracord.resolve(foo), &[1,2,3,4]);
+/// assert_eq!(paracord.resolve(bar), &[5,6,7,8]);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "lock-free")))]
+pub struct LockFreeParaCord<T, S = foldhash::fast::RandomState> {
+    /// Each slice, with the repr of its key, or [`PENDING`] while the key is being allocated.
+    slice_to_keys: papaya::HashMap<Box<[T]>, AtomicU32, S>,
+    /// Points into the slices owned by `slice_to_keys`, which are never removed.
+    keys_to_slice: boxcar::Vec<InternedPtr<T>>,
+}
+
+impl<T: fmt::Debug, S> fmt::Debug for LockFreeParaCord<T, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Default for LockFreeParaCord<T> {
+    fn default() -> Self {
+        Self::with_hasher(foldhash::fast::RandomState::default())
+    }
+}
+
+impl<T, S> LockFreeParaCord<T, S> {
+    /// Create a new `LockFreeParaCord` instance with the given hasher state.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            slice_to_keys: papaya::HashMap::with_hasher(hasher),
+            keys_to_slice: boxcar::Vec::new(),
+        }
+    }
+
+    /// Try and resolve the slice associated with this [`Key`].
+    ///
+    /// This can return [`None`] if the key was not allocated by this instance.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&[T]> {
+        let s = self.keys_to_slice.get(key.into_repr() as usize)?;
+        Some(s.slice())
+    }
+
+    /// Resolve the slice associated with this [`Key`].
+    ///
+    /// # Panics
+    /// This can panic if the key was not allocated by this instance.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &[T] {
+        self.try_resolve(key)
+            .expect("key should be allocated by this paracord instance")
+    }
+
+    /// Determine how many slices have been allocated
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.keys_to_slice.count()
+    }
+
+    /// Determine if no slices have been allocated
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get an iterator over every ([`Key`], `&[T]`) pair
+    /// that has been allocated in this instance, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &[T])> {
+        self.keys_to_slice.iter().map(|(_, s)| (s.key, s.slice()))
+    }
+}
+
+/// Get the key stored in a slot, or `None` if it is still being allocated.
+///
+/// # Panics
+/// If the insert that allocated the slot panicked.
+#[track_caller]
+fn load(slot: &AtomicU32) -> Option<Key> {
+    match slot.load(Ordering::Acquire) {
+        POISONED => panic!("another thread panicked while interning this slice"),
+        repr => Key::try_from_repr(repr),
+    }
+}
+
+/// Wait for a concurrent insert of the same slice to allocate its key.
+#[cold]
+#[track_caller]
+fn wait(slot: &AtomicU32) -> Key {
+    let mut spins = 0;
+    loop {
+        if let Some(key) = load(slot) {
+            return key;
+        }
+        if spins < SPINS {
+            spins += 1;
+            std::hint::spin_loop();
+        } else {
+            // the inserting thread might be descheduled.
+            std::thread::yield_now();
+        }
+    }
+}
+
+/// Poisons the slot of an insert that panics before storing its key, so that waiters panic
+/// instead of waiting forever.
+struct PoisonOnUnwind<'a>(&'a AtomicU32);
+
+impl Drop for PoisonOnUnwind<'_> {
+    fn drop(&mut self) {
+        self.0.store(POISONED, Ordering::Release);
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> LockFreeParaCord<T, S> {
+    /// Try and get the [`Key`] associated with the given slice.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &[T]) -> Option<Key> {
+        match self.slice_to_keys.pin().get(s)?.load(Ordering::Acquire) {
+            POISONED => None,
+            slot => Key::try_from_repr(slot),
+        }
+    }
+}
+
+impl<T: Hash + Eq + Copy, S: BuildHasher> LockFreeParaCord<T, S> {
+    /// Try and get the [`Key`] associated with the given slice.
+    /// Allocates a new key if not found.
+    ///
+    /// # Panics
+    /// If all the keys have been allocated, or if another thread panicked while interning the
+    /// same slice.
+    #[track_caller]
+    pub fn get_or_intern(&self, s: &[T]) -> Key {
+        let map = self.slice_to_keys.pin();
+        match map.get(s) {
+            Some(slot) => match load(slot) {
+                Some(key) => key,
+                None => wait(slot),
+            },
+            None => self.intern_slow(s),
+        }
+    }
+
+    #[cold]
+    #[track_caller]
+    fn intern_slow(&self, s: &[T]) -> Key {
+        let _len = u32::try_from(s.len()).expect(super::TOO_LONG);
+
+        let map = self.slice_to_keys.pin();
+        match map.try_insert(Box::from(s), AtomicU32::new(PENDING)) {
+            Ok(slot) => {
+                let guard = PoisonOnUnwind(slot);
+                // the slice is owned by the map, and is never removed, so it lives as long as `self`.
+                let (interned, _) = map.get_key_value(s).expect("slice was just inserted");
+                let i = self.keys_to_slice.push_with(|i| {
+                    assert!(i < POISONED as usize, "too many keys allocated");
+                    InternedPtr::new(interned, Key::from_index(i))
+                });
+                let key = Key::from_index(i);
+                slot.store(key.into_repr(), Ordering::Release);
+                core::mem::forget(guard);
+                key
+            }
+            Err(err) => match load(err.current) {
+                Some(key) => key,
+                None => wait(err.current),
+            },
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use core::mem::{size_of, size_of_val};
+
+use super::alloc::InternedPtr;
+use super::{Collection, ParaCord};
+
+/// The memory used by a [`ParaCord`], in bytes, broken down by component.
+///
+/// Returned by [`ParaCord::memory_breakdown`]. Element sizes come from `size_of::<T>()`, so they
+/// include any padding inside `T`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MemoryBreakdown {
+    /// The elements of every interned slice, not counting `'static` slices.
+    pub data: usize,
+    /// Memory held by the arenas beyond `data`: unused space at the end of each chunk or page,
+    /// padding between slices, and the bookkeeping for adopted and `'static` slices.
+    pub arena_overhead: usize,
+    /// The entry of each key, which points to its slice.
+    pub keys: usize,
+    /// The hash table of each shard.
+    pub tables: usize,
+    /// The [bloom filter](ParaCord::with_bloom_filter), if any.
+    pub bloom: usize,
+    /// The `ParaCord` itself, and its array of shards.
+    pub fixed: usize,
+}
+
+impl MemoryBreakdown {
+    /// The total memory used, in bytes.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.data + self.arena_overhead + self.keys + self.tables + self.bloom + self.fixed
+    }
+
+    fn add_shard(&mut self, shard: &ShardMemory) {
+        self.data += shard.arena_used;
+        self.arena_overhead += shard.arena_reserved - shard.arena_used;
+        self.tables += shard.table;
+    }
+}
+
+/// The memory used by a [`ParaCord`], in bytes, with the arenas and tables of each shard.
+///
+/// Returned by [`ParaCord::memory_stats`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MemoryStats {
+    /// The index from each key to its slice.
+    pub keys: usize,
+    /// The [bloom filter](ParaCord::with_bloom_filter), if any.
+    pub bloom: usize,
+    /// The `ParaCord` itself, and its array of shards.
+    pub fixed: usize,
+    /// The memory used by each shard.
+    pub shards: Vec<ShardMemory>,
+}
+
+/// The memory used by one shard of a [`ParaCord`], in bytes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ShardMemory {
+    /// How many slices are in the shard.
+    pub entries: usize,
+    /// The hash table of the shard.
+    pub table: usize,
+    /// The elements of the slices in the shard's arena.
+    pub arena_used: usize,
+    /// The memory held by the shard's arena, including `arena_used`.
+    pub arena_reserved: usize,
+}
+
+impl MemoryStats {
+    /// The total memory used, in bytes.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.breakdown().total()
+    }
+
+    /// Sum the memory of every shard.
+    #[must_use]
+    pub fn breakdown(&self) -> MemoryBreakdown {
+        let mut memory = MemoryBreakdown {
+            keys: self.keys,
+            bloom: self.bloom,
+            fixed: self.fixed,
+            ..MemoryBreakdown::default()
+        };
+        for shard in &self.shards {
+            memory.add_shard(shard);
+        }
+        memory
+    }
+}
+
+impl ShardMemory {
+    fn new<T>(shard: &Collection<T>) -> Self {
+        Self {
+            entries: shard.table.len(),
+            table: shard.table.allocation_size(),
+            arena_used: shard.alloc.used(),
+            arena_reserved: shard.alloc.reserved(),
+        }
+    }
+}
+
+impl<T, S> ParaCord<T, S> {
+    /// Determine how much memory is used by this instance, broken down by component,
+    /// briefly locking each shard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::<u64>::default();
+    /// paracord.get_or_intern(&[1, 2, 3]);
+    ///
+    /// let memory = paracord.memory_breakdown();
+    /// assert_eq!(memory.data, 3 * 8);
+    /// assert!(memory.arena_overhead > 0);
+    /// assert_eq!(memory.total(), paracord.memory_usage());
+    /// ```
+    #[must_use]
+    pub fn memory_breakdown(&self) -> MemoryBreakdown {
+        let mut memory = self.memory_without_shards();
+        for shard in self.slice_to_keys.shards() {
+            memory.add_shard(&ShardMemory::new(&shard.read()));
+        }
+        memory
+    }
+
+    /// Determine how much memory is used by this instance, with the arenas and tables of each
+    /// shard, briefly locking each shard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::<u8>::default();
+    /// paracord.get_or_intern(b"foo");
+    ///
+    /// let stats = paracord.memory_stats();
+    /// assert_eq!(stats.shards.len(), paracord.shard_count());
+    /// assert_eq!(stats.shards.iter().map(|s| s.arena_used).sum::<usize>(), 3);
+    /// assert_eq!(stats.total(), paracord.memory_usage());
+    /// ```
+    #[must_use]
+    pub fn memory_stats(&self) -> MemoryStats {
+        let memory = self.memory_without_shards();
+        MemoryStats {
+            keys: memory.keys,
+            bloom: memory.bloom,
+            fixed: memory.fixed,
+            shards: self
+                .slice_to_keys
+                .shards()
+                .iter()
+                .map(|shard| ShardMemory::new(&shard.read()))
+                .collect(),
+        }
+    }
+
+    /// Everything except for the arenas and tables of the shards.
+    fn memory_without_shards(&self) -> MemoryBreakdown {
+        MemoryBreakdown {
+            keys: self.keys_to_slice.count() * size_of::<InternedPtr<T>>(),
+            bloom: self.bloom.as_ref().map_or(0, |b| b.size()),
+            fixed: size_of::<Self>() + size_of_val(self.slice_to_keys.shards()),
+            ..MemoryBreakdown::default()
+        }
+    }
+
+    /// Determine how much memory is used by this instance, in bytes, briefly locking each shard.
+    ///
+    /// This is the [total](MemoryBreakdown::total) of [`ParaCord::memory_breakdown`].
+    #[must_use]
+    pub fn memory_usage(&self) -> usize {
+        self.memory_breakdown().total()
+    }
+
+    /// Determine how much memory is used by this instance, in bytes.
+    ///
+    /// Unlike [`ParaCord::memory_usage`], this does not need to lock any shards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let mut paracord = ParaCord::<u16>::default();
+    /// paracord.get_or_intern(&[1, 2, 3]);
+    ///
+    /// assert!(paracord.current_memory_usage() > 3 * 2);
+    /// ```
+    #[must_use]
+    pub fn current_memory_usage(&mut self) -> usize {
+        let mut memory = self.memory_without_shards();
+        for shard in self.slice_to_keys.shards_mut() {
+            memory.add_shard(&ShardMemory::new(shard.get_mut()));
+        }
+        memory.total()
+    }
+
+    /// Like [`ParaCord::memory_stats`], without locking any shards.
+    pub(super) fn current_memory_stats(&mut self) -> MemoryStats {
+        let memory = self.memory_without_shards();
+        MemoryStats {
+            keys: memory.keys,
+            bloom: memory.bloom,
+            fixed: memory.fixed,
+            shards: self
+                .slice_to_keys
+                .shards_mut()
+                .iter_mut()
+                .map(|shard| ShardMemory::new(shard.get_mut()))
+                .collect(),
+        }
+    }
+}
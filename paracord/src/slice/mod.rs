@@ -26,7 +26,11 @@
 use alloc::{Alloc, InternedPtr};
 use core::fmt;
 use std::hash::{BuildHasher, Hash};
-use std::ops::Index;
+use std::ops::{Bound, Index, RangeBounds};
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 
 use clashmap::ClashCollection;
 use hashbrown::HashTable;
@@ -34,6 +38,38 @@ use hashbrown::HashTable;
 use crate::Key;
 
 mod alloc;
+mod bloom;
+#[cfg(feature = "base64")]
+mod bytes;
+mod float;
+#[cfg(feature = "frequency")]
+mod frequency;
+mod guard;
+mod limits;
+#[cfg(feature = "lock-free")]
+mod lock_free;
+mod memory;
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "rand")]
+mod sample;
+mod sharded;
+mod sweep;
+
+#[cfg(feature = "base64")]
+#[cfg_attr(docsrs, doc(cfg(feature = "base64")))]
+pub use bytes::Base64Display;
+#[cfg(feature = "serde_bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_bytes")))]
+pub use bytes::{BytesKey, BytesSeed};
+pub use float::FloatBits;
+pub use guard::ReadGuard;
+pub use limits::{SoftLimitEvent, SoftLimitKind, SoftLimits};
+#[cfg(feature = "lock-free")]
+pub use lock_free::LockFreeParaCord;
+pub use memory::{MemoryBreakdown, MemoryStats, ShardMemory};
+pub use sharded::ShardedParaCord;
+pub use sweep::SweepReport;
 
 /// [`ParaCord`] is a lightweight, thread-safe, memory efficient [string interer](https://en.wikipedia.org/wiki/String_interning).
 ///
@@ -77,9 +113,40 @@ mod alloc;
 pub struct ParaCord<T, S = foldhash::fast::RandomState> {
     slice_to_keys: ClashCollection<Collection<T>>,
     keys_to_slice: boxcar::Vec<InternedPtr<T>>,
+    /// How many entries in `keys_to_slice` are tombstones.
+    removed: usize,
+    /// Cache of the key for the empty slice.
+    empty_key: OnceLock<Key>,
+    /// Lower bound on the number of leading key slots that have finished being allocated.
+    settled: AtomicUsize,
+    /// The index of the key stored in the first slot. Keys below this were allocated before a clear.
+    base: usize,
+    stale_key_policy: StaleKeyPolicy,
+    /// Called with the first stale key that is seen, set with `with_stale_key_callback`.
+    // a panicking callback cannot leave the interner in an inconsistent state.
+    stale_key_callback: Option<AssertUnwindSafe<Arc<StaleKeyCallback>>>,
+    /// Whether the stale key callback has been called.
+    stale_key_reported: AtomicBool,
+    soft_limits: Option<Box<limits::SoftLimitState>>,
+    /// Hard limit on the bytes used by the interned data, only enforced by `try_get_or_intern`.
+    memory_limit: Option<Box<limits::MemoryLimit>>,
+    /// How many elements fit in the first chunk of each new arena, set with `with_arena_chunk_size`.
+    arena_chunk: Option<usize>,
+    /// Sampled access counts.
+    #[cfg(feature = "frequency")]
+    frequency: frequency::Frequency,
+    /// Unique id of this instance, for checking where keys came from.
+    id: u64,
+    /// How many times the keys of this instance have been invalidated, eg by a clear.
+    generation: u32,
+    /// Filter of the interned slices, checked before probing a shard.
+    bloom: Option<Box<bloom::BloomFilter>>,
     hasher: S,
 }
 
+/// The id of the next instance created.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
 impl<T: fmt::Debug, S> fmt::Debug for ParaCord<T, S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_map().entries(self.iter()).finish()
@@ -111,6 +178,15 @@ impl<T> Default for ParaCord<T> {
     }
 }
 
+impl<T: Hash> ParaCord<T> {
+    /// Create a new `ParaCord` instance with room for at least `capacity` slices.
+    ///
+    /// See [`ParaCord::reserve`].
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, foldhash::fast::RandomState::default())
+    }
+}
+
 impl<T, S: BuildHasher> ParaCord<T, S> {
     /// Create a new `ParaCord` instance with the given hasher state.
     ///
@@ -129,12 +205,76 @@ impl<T, S: BuildHasher> ParaCord<T, S> {
         Self {
             keys_to_slice: boxcar::Vec::default(),
             slice_to_keys: ClashCollection::default(),
+            removed: 0,
+            empty_key: OnceLock::new(),
+            settled: AtomicUsize::new(0),
+            base: 0,
+            stale_key_policy: StaleKeyPolicy::Reuse,
+            stale_key_callback: None,
+            stale_key_reported: AtomicBool::new(false),
+            soft_limits: None,
+            memory_limit: None,
+            arena_chunk: None,
+            #[cfg(feature = "frequency")]
+            frequency: Default::default(),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            generation: 0,
+            bloom: None,
             hasher,
         }
     }
 }
 
+impl<T: Hash, S: BuildHasher> ParaCord<T, S> {
+    /// Create a new `ParaCord` instance with the given hasher state,
+    /// and room for at least `capacity` slices.
+    ///
+    /// See [`ParaCord::reserve`].
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        let mut this = Self::with_hasher(hasher);
+        this.reserve(capacity);
+        this
+    }
+
+    /// Reserve room for at least `additional` more slices, so interning them doesn't
+    /// repeatedly grow the key storage and the hash table of every shard.
+    ///
+    /// Slices are spread over the shards by their hash, so a shard that receives more than its
+    /// share can still grow. The arenas are not presized, since the lengths of the slices
+    /// are not known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let mut paracord = ParaCord::default();
+    ///
+    /// paracord.reserve(1000);
+    /// for i in 0..1000u32 {
+    ///     paracord.get_or_intern(&[i]);
+    /// }
+    /// assert_eq!(paracord.len(), 1000);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        self.keys_to_slice.reserve(additional);
+
+        let per_shard = additional.div_ceil(self.shard_count());
+        let hasher = &self.hasher;
+        for shard in self.slice_to_keys.shards_mut() {
+            // safety: k is allocated correct
+            let rehash = |k: &*const InternedPtr<T>| unsafe { hasher.hash_one((**k).slice()) };
+            shard.get_mut().table.reserve(per_shard, rehash);
+        }
+    }
+}
+
 impl<T: Hash + Eq, S: BuildHasher> ParaCord<T, S> {
+    /// Compute the hash of the slice, exactly as this instance hashes it internally.
+    #[must_use]
+    pub fn hash_of(&self, s: &[T]) -> u64 {
+        self.hasher.hash_one(s)
+    }
+
     /// Try and get the [`Key`] associated with the given slice.
     /// Returns [`None`] if not found.
     ///
@@ -148,8 +288,89 @@ impl<T: Hash + Eq, S: BuildHasher> ParaCord<T, S> {
     /// assert_eq!(paracord.get(&[1,2,3,4]), Some(foo));
     /// assert_eq!(paracord.get(&[5,6,7,8]), None);
     /// ```
+    #[must_use]
     pub fn get(&self, s: &[T]) -> Option<Key> {
-        let hash = self.hasher.hash_one(s);
+        if s.is_empty() {
+            if let Some(&key) = self.empty_key.get() {
+                #[cfg(feature = "frequency")]
+                self.frequency.record(key);
+                return Some(key);
+            }
+        }
+
+        self.get_hashed(s, self.hasher.hash_one(s))
+    }
+
+    /// Determine which shard the slice is stored in, from `0` to [`ParaCord::shard_count`].
+    ///
+    /// This is deterministic for each instance, so applications can partition their own side
+    /// structures the same way, and avoid contending on more than one shard at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let shard = paracord.shard_of(&[1, 2, 3]);
+    /// assert!(shard < paracord.shard_count());
+    /// assert_eq!(paracord.shard_of(&[1, 2, 3]), shard);
+    /// ```
+    #[must_use]
+    pub fn shard_of(&self, s: &[T]) -> usize {
+        self.shard_index(self.hasher.hash_one(s))
+    }
+
+    /// Try and get the [`Key`] associated with each slice.
+    /// Returns the keys in the same order as the slices, with [`None`] for slices that are not found.
+    ///
+    /// The slices are grouped by shard, so each shard lock is taken once for the whole batch,
+    /// rather than once for every slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let foo = paracord.get_or_intern(&[1, 2]);
+    /// assert_eq!(paracord.get_many(&[[1, 2], [3, 4]]), [Some(foo), None]);
+    /// ```
+    #[must_use]
+    pub fn get_many<P: AsRef<[T]>>(&self, items: &[P]) -> Vec<Option<Key>> {
+        let hashes: Vec<u64> = items
+            .iter()
+            .map(|s| self.hasher.hash_one(s.as_ref()))
+            .collect();
+        let mut keys = vec![None; items.len()];
+
+        let indices = (0..items.len())
+            .filter(|&i| self.may_contain(hashes[i]))
+            .collect();
+        self.for_each_shard_group(&hashes, indices, |shard, group| {
+            let shard = self.slice_to_keys.shards()[shard].read();
+            for &i in group {
+                let s = items[i].as_ref();
+                // safety: k is allocated correct
+                let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
+                // safety: k is allocated correct
+                let map = |k: &*const InternedPtr<T>| unsafe { (**k).key };
+                keys[i] = shard.table.find(hashes[i], eq).map(map);
+            }
+        });
+
+        #[cfg(feature = "frequency")]
+        keys.iter()
+            .flatten()
+            .for_each(|&key| self.frequency.record(key));
+        keys
+    }
+
+    /// Like [`ParaCord::get`], with the hash of the slice already computed.
+    pub(crate) fn get_hashed(&self, s: &[T], hash: u64) -> Option<Key> {
+        if !self.may_contain(hash) {
+            return None;
+        }
         let shard = self.slice_to_keys.get_read_shard(hash);
 
         // safety: k is allocated correct
@@ -157,7 +378,20 @@ impl<T: Hash + Eq, S: BuildHasher> ParaCord<T, S> {
         // safety: k is allocated correct
         let map = |k: &*const InternedPtr<T>| unsafe { (**k).key };
 
-        shard.table.find(hash, eq).map(map)
+        let key = shard.table.find(hash, eq).map(map);
+        #[cfg(feature = "frequency")]
+        if let Some(key) = key {
+            self.frequency.record(key);
+        }
+        key
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> ParaCord<T, S> {
+    /// Like [`ParaCord::get`], but returns a reference to the key stored in this instance.
+    pub(crate) fn get_ref(&self, s: &[T]) -> Option<&Key> {
+        let key = self.get(s)?;
+        self.keys_to_slice.get(self.slot(key)?).map(|p| &p.key)
     }
 }
 
@@ -178,9 +412,46 @@ impl<T: Hash + Eq + Copy, S: BuildHasher> ParaCord<T, S> {
     /// assert_ne!(foo, bar);
     /// assert_eq!(foo, foo2);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice is new and has more than `u32::MAX` elements, or if every key has been
+    /// allocated. Use [`ParaCord::try_get_or_intern`] to handle these as errors.
     pub fn get_or_intern(&self, s: &[T]) -> Key {
-        let hash = self.hasher.hash_one(s);
+        if s.is_empty() {
+            let key = self.empty_key();
+            #[cfg(feature = "frequency")]
+            self.frequency.record(key);
+            return key;
+        }
+
+        self.get_or_intern_hashed(s, self.hasher.hash_one(s))
+    }
+
+    /// Like [`ParaCord::get_or_intern`], with the hash of the slice already computed.
+    pub(crate) fn get_or_intern_hashed(&self, s: &[T], hash: u64) -> Key {
+        let key = {
+            // safety: k is allocated correct
+            let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
+            // safety: k is allocated correct
+            let map = |k: &*const InternedPtr<T>| unsafe { (**k).key };
+
+            let shard = self.slice_to_keys.get_read_shard(hash);
+            shard.table.find(hash, eq).map(map)
+        };
 
+        let key = match key {
+            Some(key) => key,
+            None => self.intern_slow(s, hash),
+        };
+        #[cfg(feature = "frequency")]
+        self.frequency.record(key);
+        key
+    }
+
+    /// Like [`ParaCord::get_or_intern`], but also returns whether the slice was newly interned.
+    pub(crate) fn insert(&self, s: &[T]) -> (Key, bool) {
+        let hash = self.hasher.hash_one(s);
         let key = {
             // safety: k is allocated correct
             let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
@@ -191,22 +462,439 @@ impl<T: Hash + Eq + Copy, S: BuildHasher> ParaCord<T, S> {
             shard.table.find(hash, eq).map(map)
         };
 
-        let Some(key) = key else {
-            return self.intern_slow(s, hash);
+        let (key, inserted) = match key {
+            Some(key) => (key, false),
+            None => self.intern_slow_inserted(s, hash),
         };
+        #[cfg(feature = "frequency")]
+        self.frequency.record(key);
+        (key, inserted)
+    }
+
+    /// Try and get the [`Key`] associated with each slice, allocating new keys if not found.
+    /// Returns the keys in the same order as the slices.
+    ///
+    /// The slices are grouped by shard, so each shard lock is taken at most twice for the
+    /// whole batch, rather than once or twice for every slice. New keys are allocated in an
+    /// unspecified order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let keys = paracord.get_or_intern_all([[1, 2], [3, 4], [1, 2]]);
+    /// assert_eq!(keys[0], keys[2]);
+    /// assert_eq!(paracord.resolve(keys[1]), &[3, 4]);
+    /// ```
+    pub fn get_or_intern_all<I>(&self, iter: I) -> Vec<Key>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[T]>,
+    {
+        let items: Vec<I::Item> = iter.into_iter().collect();
+        let hashes: Vec<u64> = items
+            .iter()
+            .map(|s| self.hasher.hash_one(s.as_ref()))
+            .collect();
+        let mut keys = vec![None; items.len()];
+
+        let mut indices = Vec::with_capacity(items.len());
+        for (i, s) in items.iter().enumerate() {
+            if s.as_ref().is_empty() {
+                keys[i] = Some(self.empty_key());
+            } else {
+                indices.push(i);
+            }
+        }
+
+        let mut missing = Vec::new();
+        let mut inserted = Vec::new();
+        self.for_each_shard_group(&hashes, indices, |shard, group| {
+            let shard = &self.slice_to_keys.shards()[shard];
+            {
+                let shard = shard.read();
+                for &i in group {
+                    let s = items[i].as_ref();
+                    // safety: k is allocated correct
+                    let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
+                    // safety: k is allocated correct
+                    let map = |k: &*const InternedPtr<T>| unsafe { (**k).key };
+                    keys[i] = shard.table.find(hashes[i], eq).map(map);
+                    if keys[i].is_none() {
+                        missing.push(i);
+                    }
+                }
+            }
+
+            if !missing.is_empty() {
+                let mut shard = shard.write();
+                for i in missing.drain(..) {
+                    let s = items[i].as_ref();
+                    self.bloom_insert(hashes[i]);
+                    let (key, new) =
+                        shard.intern(&self.keys_to_slice, self.base, &self.hasher, s, hashes[i]);
+                    keys[i] = Some(key);
+                    if new {
+                        inserted.push(s.len());
+                    }
+                }
+            }
+
+            // the shard lock is released before calling into user code.
+            inserted.drain(..).for_each(|len| self.record_insert(len));
+        });
+
+        keys.into_iter()
+            .map(|key| {
+                let key = key.expect("every slice should be interned");
+                #[cfg(feature = "frequency")]
+                self.frequency.record(key);
+                key
+            })
+            .collect()
+    }
+
+    /// Like [`ParaCord::get_or_intern`], but takes ownership of the slice,
+    /// storing it without copying if it is new.
+    pub(crate) fn get_or_intern_boxed(&self, s: Box<[T]>) -> Key {
+        if s.is_empty() {
+            return self.get_or_intern(&s);
+        }
+
+        let hash = self.hasher.hash_one(&*s);
+        let key = {
+            // safety: k is allocated correct
+            let eq = |k: &*const InternedPtr<T>| unsafe { *s == *(**k).slice() };
+            // safety: k is allocated correct
+            let map = |k: &*const InternedPtr<T>| unsafe { (**k).key };
+
+            let shard = self.slice_to_keys.get_read_shard(hash);
+            shard.table.find(hash, eq).map(map)
+        };
+
+        let key = match key {
+            Some(key) => key,
+            None => self.intern_slow_boxed(s, hash),
+        };
+        #[cfg(feature = "frequency")]
+        self.frequency.record(key);
+        key
+    }
+
+    /// Try and get the [`Key`] associated with the given slice.
+    /// Allocates a new key if not found.
+    ///
+    /// Unlike [`ParaCord::get_or_intern`], the slice is not copied into the arena if it is new,
+    /// only a pointer to it is stored. This saves memory for keywords and other constants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// static FOO: [u8; 4] = [1, 2, 3, 4];
+    ///
+    /// let foo = paracord.get_or_intern_static(&FOO);
+    /// assert_eq!(paracord.get_or_intern(&[1,2,3,4]), foo);
+    /// assert!(std::ptr::eq(paracord.resolve(foo), &FOO[..]));
+    /// ```
+    pub fn get_or_intern_static(&self, s: &'static [T]) -> Key {
+        if s.is_empty() {
+            return self.get_or_intern(s);
+        }
+
+        let hash = self.hasher.hash_one(s);
+        if let Some(key) = self.get_hashed(s, hash) {
+            return key;
+        }
+
+        let key = self.intern_slow_static(s, hash);
+        #[cfg(feature = "frequency")]
+        self.frequency.record(key);
         key
     }
+
+    /// Like [`ParaCord::get_or_intern`], but gives up if the shard lock cannot be acquired
+    /// within `timeout`.
+    ///
+    /// This never blocks on the shard lock, it retries until the timeout expires instead.
+    /// A zero timeout makes a single attempt. This lets latency sensitive threads
+    /// fall back to handling the slice without interning it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let foo = paracord.get_or_intern_timeout(&[1,2,3,4], Duration::ZERO).unwrap();
+    /// assert_eq!(paracord.get(&[1,2,3,4]), Some(foo));
+    /// ```
+    pub fn get_or_intern_timeout(&self, s: &[T], timeout: Duration) -> Result<Key, Timeout> {
+        if s.is_empty() {
+            if let Some(&key) = self.empty_key.get() {
+                #[cfg(feature = "frequency")]
+                self.frequency.record(key);
+                return Ok(key);
+            }
+        }
+
+        // a timeout too large to represent never expires.
+        let deadline = Instant::now().checked_add(timeout);
+        let hash = self.hasher.hash_one(s);
+        let shard = &self.slice_to_keys.shards()[self.shard_index(hash)];
+
+        let key = {
+            // safety: k is allocated correct
+            let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
+            // safety: k is allocated correct
+            let map = |k: &*const InternedPtr<T>| unsafe { (**k).key };
+
+            let shard = retry_until(deadline, || shard.try_read())?;
+            shard.table.find(hash, eq).map(map)
+        };
+
+        let key = match key {
+            Some(key) => key,
+            None => {
+                self.bloom_insert(hash);
+                let (key, inserted) = retry_until(deadline, || shard.try_write())?.intern(
+                    &self.keys_to_slice,
+                    self.base,
+                    &self.hasher,
+                    s,
+                    hash,
+                );
+                // the shard lock is released before calling into user code.
+                if inserted {
+                    self.record_insert(s.len());
+                }
+                key
+            }
+        };
+        #[cfg(feature = "frequency")]
+        self.frequency.record(key);
+        Ok(key)
+    }
+
+    /// Like [`ParaCord::get_or_intern`], but returns an error instead of panicking if the slice
+    /// is too long, or if every key has been allocated.
+    ///
+    /// This lets servers that intern untrusted input reject it, rather than crash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InternError::TooLong`] if the slice has more than `u32::MAX` elements,
+    /// [`InternError::KeySpaceExhausted`] if the slice is new and no keys are left, and
+    /// [`InternError::MemoryLimit`] if the slice is new and would exceed the
+    /// [memory limit](ParaCord::with_memory_limit).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let foo = paracord.try_get_or_intern(&[1, 2, 3]).unwrap();
+    /// assert_eq!(paracord.try_get_or_intern(&[1, 2, 3]), Ok(foo));
+    /// ```
+    pub fn try_get_or_intern(&self, s: &[T]) -> Result<Key, InternError> {
+        if u32::try_from(s.len()).is_err() {
+            return Err(InternError::TooLong);
+        }
+
+        let hash = self.hasher.hash_one(s);
+        if let Some(key) = self.get_hashed(s, hash) {
+            return Ok(key);
+        }
+
+        self.bloom_insert(hash);
+        let (key, inserted) = {
+            let mut shard = self.slice_to_keys.get_write_shard(hash);
+
+            // safety: k is allocated correct
+            let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
+            // every insert holds a shard lock while it allocates its key, so leaving room for
+            // one key per shard covers every concurrent insert.
+            let exhausted = self.next_index() + self.shard_count() > KEY_SPACE;
+            let limited = exhausted || self.memory_limit.is_some();
+            if limited && shard.table.find(hash, eq).is_none() {
+                if exhausted {
+                    return Err(InternError::KeySpaceExhausted);
+                }
+                // the slice is new, and stays new while the shard is locked.
+                if let Some(limit) = &self.memory_limit {
+                    if !limit.try_reserve::<T>(s.len()) {
+                        return Err(InternError::MemoryLimit);
+                    }
+                }
+            }
+
+            shard.intern(&self.keys_to_slice, self.base, &self.hasher, s, hash)
+        };
+        // the shard lock is released before calling into user code.
+        if inserted {
+            // the memory was already reserved.
+            self.record_soft_limits(s.len());
+        }
+        #[cfg(feature = "frequency")]
+        self.frequency.record(key);
+        Ok(key)
+    }
+
+    /// Get the [`Key`] associated with the empty slice, allocating it if necessary.
+    ///
+    /// The empty slice always has the same key. After the first call this
+    /// does not need to perform any lookups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::<u8>::default();
+    ///
+    /// let empty = paracord.empty_key();
+    /// assert_eq!(paracord.get_or_intern(&[]), empty);
+    /// assert!(paracord.resolve(empty).is_empty());
+    /// ```
+    pub fn empty_key(&self) -> Key {
+        *self.empty_key.get_or_init(|| {
+            let hash = self.hasher.hash_one::<&[T]>(&[]);
+            self.intern_slow(&[], hash)
+        })
+    }
+}
+
+impl<S: BuildHasher> ParaCord<u8, S> {
+    /// Try and get the [`Key`] associated with the bytes of the given string.
+    /// Returns [`None`] if not found.
+    ///
+    /// Strings hash the same as their bytes, so text and binary data can share one interner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let foo = paracord.get_or_intern(b"foo");
+    /// assert_eq!(paracord.get_str("foo"), Some(foo));
+    /// assert_eq!(paracord.get_or_intern_str("foo"), foo);
+    /// assert_eq!(paracord.resolve_str(foo), Some("foo"));
+    /// ```
+    #[must_use]
+    pub fn get_str(&self, s: &str) -> Option<Key> {
+        self.get(s.as_bytes())
+    }
+
+    /// Try and get the [`Key`] associated with the bytes of the given string.
+    /// Allocates a new key if not found.
+    pub fn get_or_intern_str(&self, s: &str) -> Key {
+        self.get_or_intern(s.as_bytes())
+    }
+
+    /// Resolve the bytes associated with this [`Key`] as a string.
+    ///
+    /// Returns [`None`] if the bytes are not valid UTF-8.
+    ///
+    /// # Panics
+    /// See [`ParaCord::resolve`].
+    #[must_use]
+    pub fn resolve_str(&self, key: Key) -> Option<&str> {
+        core::str::from_utf8(self.resolve(key)).ok()
+    }
+
+    /// Try and get the [`Key`] associated with the concatenation of the given pieces.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get_concat<P: AsRef<[u8]>>(&self, pieces: &[P]) -> Option<Key> {
+        match pieces {
+            [piece] => self.get(piece.as_ref()),
+            _ => with_concat(pieces, |s| self.get(s)),
+        }
+    }
+
+    /// Try and get the [`Key`] associated with the concatenation of the given pieces.
+    /// Allocates a new key if not found.
+    ///
+    /// The pieces are joined in a reusable thread-local buffer, so this does not allocate a
+    /// temporary `Vec`, and the bytes are only copied into the arena if they are new.
+    ///
+    /// The pieces cannot be hashed one at a time instead, since a [`Hasher`](std::hash::Hasher)
+    /// may hash bytes differently depending on how they are split between calls to `write`,
+    /// which would give the concatenation a different hash from the same slice interned whole.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let key = paracord.get_or_intern_concat(&[b"foo".as_slice(), b"::", b"bar"]);
+    /// assert_eq!(paracord.get(b"foo::bar"), Some(key));
+    /// ```
+    pub fn get_or_intern_concat<P: AsRef<[u8]>>(&self, pieces: &[P]) -> Key {
+        match pieces {
+            [piece] => self.get_or_intern(piece.as_ref()),
+            _ => with_concat(pieces, |s| self.get_or_intern(s)),
+        }
+    }
+}
+
+/// Join the pieces in a thread-local buffer, and call `f` with the result.
+///
+/// Joining is required to hash the pieces like the slice they form, see
+/// [`ParaCord::get_or_intern_concat`].
+fn with_concat<P: AsRef<[u8]>, R>(pieces: &[P], f: impl FnOnce(&[u8]) -> R) -> R {
+    let mut buf = crate::buffer::take();
+    pieces
+        .iter()
+        .for_each(|p| buf.extend_from_slice(p.as_ref()));
+    let r = f(&buf);
+    crate::buffer::give(buf);
+    r
 }
 
 impl<T: Hash + Eq, S> ParaCord<T, S> {
     /// Try and resolve the slice associated with this [`Key`].
     ///
     /// This can only return `None` if given a key that was allocated from
-    /// a different [`ParaCord`] instance, but it might return an arbitrary slice
+    /// a different [`ParaCord`] instance, or a key that has been removed by [`ParaCord::sweep`],
+    /// [`ParaCord::delete`] or [`ParaCord::retain`], but it might return an arbitrary slice
     /// as well.
+    ///
+    /// Keys allocated before [`ParaCord::clear`] are handled according to the [`StaleKeyPolicy`].
+    #[must_use]
+    #[track_caller]
     pub fn try_resolve(&self, key: Key) -> Option<&[T]> {
-        let s = self.keys_to_slice.get(key.into_repr() as usize)?;
-        Some(s.slice())
+        self.keys_to_slice.get(self.slot(key)?)?.get()
+    }
+
+    /// Resolve the address and length of the slice associated with this [`Key`].
+    ///
+    /// Slices are never moved by methods that take `&self`, so the pointer stays valid and unchanged
+    /// until this instance is dropped or a method that takes `&mut self` is called. Several of those
+    /// free or move slices, eg [`ParaCord::clear`], [`ParaCord::sweep`], [`ParaCord::reclaim`],
+    /// [`ParaCord::shrink_to_fit`] and [`ParaCord::maintenance_with`].
+    ///
+    /// # Panics
+    /// See [`ParaCord::resolve`].
+    #[must_use]
+    #[track_caller]
+    pub fn resolve_ptr(&self, key: Key) -> (*const T, usize) {
+        let s = self.resolve(key);
+        (s.as_ptr(), s.len())
+    }
+
+    /// Resolve the slice associated with this [`Key`], and pass it to `f`.
+    ///
+    /// Returns [`None`] in the same cases as [`ParaCord::try_resolve`].
+    pub fn resolve_with<R>(&self, key: Key, f: impl FnOnce(&[T]) -> R) -> Option<R> {
+        self.try_resolve(key).map(f)
     }
 
     /// Resolve the slice associated with this [`Key`].
@@ -215,8 +903,60 @@ impl<T: Hash + Eq, S> ParaCord<T, S> {
     /// This can panic if given a key that was allocated from
     /// a different [`ParaCord`] instance, but it might return an arbitrary slice
     /// as well.
+    ///
+    /// This will panic if the key has been removed by [`ParaCord::sweep`],
+    /// or if it was allocated before [`ParaCord::clear`] and the [`StaleKeyPolicy`] is not
+    /// [`StaleKeyPolicy::Reuse`].
+    #[must_use]
+    #[track_caller]
     pub fn resolve(&self, key: Key) -> &[T] {
-        self.keys_to_slice[key.into_repr() as usize].slice()
+        let slot = self.slot(key).expect(STALE_KEY);
+        self.keys_to_slice
+            .get(slot)
+            .expect("key should be allocated by this paracord instance")
+            .get()
+            .expect("key was removed from this paracord instance")
+    }
+
+    /// Resolve the slice associated with each key, appending them to `out` in the same order.
+    ///
+    /// This reuses the caller's buffer, so hot paths that resolve many keys at once
+    /// don't allocate for each batch.
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [`ParaCord::resolve`], for any of the keys.
+    /// Slices resolved before the panic are left in `out`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    ///
+    /// let foo = paracord.get_or_intern(&[1, 2]);
+    /// let bar = paracord.get_or_intern(&[3]);
+    ///
+    /// let mut out = Vec::new();
+    /// paracord.resolve_many(&[bar, foo, bar], &mut out);
+    /// assert_eq!(out, [&[3][..], &[1, 2], &[3]]);
+    /// ```
+    #[track_caller]
+    pub fn resolve_many<'a>(&'a self, keys: &[Key], out: &mut Vec<&'a [T]>) {
+        out.reserve(keys.len());
+        for &key in keys {
+            out.push(self.resolve(key));
+        }
+    }
+
+    /// Try and resolve the slice associated with each key, appending them to `out` in the same order.
+    ///
+    /// Each key resolves to [`None`] in the same cases as [`ParaCord::try_resolve`].
+    #[track_caller]
+    pub fn try_resolve_many<'a>(&'a self, keys: &[Key], out: &mut Vec<Option<&'a [T]>>) {
+        out.reserve(keys.len());
+        for &key in keys {
+            out.push(self.try_resolve(key));
+        }
     }
 
     /// Resolve the slice associated with this [`Key`].
@@ -224,21 +964,341 @@ impl<T: Hash + Eq, S> ParaCord<T, S> {
     /// # Safety
     /// This key must have been allocated in this paracord instance,
     /// and [`ParaCord::clear`] must not have been called.
+    /// The key must not have been removed by [`ParaCord::sweep`], [`ParaCord::delete`]
+    /// or [`ParaCord::retain`], including when the removal was followed by
+    /// [`ParaCord::reclaim`].
+    #[must_use]
     pub unsafe fn resolve_unchecked(&self, key: Key) -> &[T] {
         // Safety: If the key was allocated in self, then key is inbounds.
-        unsafe { self.keys_to_slice.get_unchecked(key.into_repr() as usize) }.slice()
+        unsafe {
+            self.keys_to_slice
+                .get_unchecked(key.into_repr() as usize - self.base)
+        }
+        .slice()
     }
 }
 
 impl<T, S> ParaCord<T, S> {
     /// Determine how many slices have been allocated
+    #[must_use]
     pub fn len(&self) -> usize {
-        self.keys_to_slice.count()
+        self.keys_to_slice.count() - self.removed
     }
 
     /// Determine if no slices have been allocated
+    #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.keys_to_slice.is_empty()
+        self.len() == 0
+    }
+
+    /// Determine the total number of elements in every slice that has been allocated.
+    ///
+    /// This visits every key, so it takes time proportional to [`ParaCord::len`].
+    #[must_use]
+    pub fn total_len(&self) -> usize {
+        self.keys_to_slice.iter().map(|(_, p)| p.slice_len()).sum()
+    }
+
+    /// Determine how many shards the slices are partitioned into.
+    ///
+    /// See [`ParaCord::shard_of`].
+    #[must_use]
+    pub fn shard_count(&self) -> usize {
+        self.slice_to_keys.shards().len()
+    }
+
+    fn shard_index(&self, hash: u64) -> usize {
+        self.slice_to_keys.determine_shard(hash)
+    }
+
+    /// Call `f` with the index of each shard, and the indices whose hashes belong to that shard.
+    fn for_each_shard_group(
+        &self,
+        hashes: &[u64],
+        mut indices: Vec<usize>,
+        mut f: impl FnMut(usize, &[usize]),
+    ) {
+        let shard_of = |i: &usize| self.shard_index(hashes[*i]);
+        indices.sort_by_key(shard_of);
+
+        let mut rest = &indices[..];
+        while let Some(first) = rest.first() {
+            let index = shard_of(first);
+            let len = rest.iter().take_while(|i| shard_of(i) == index).count();
+            let (group, tail) = rest.split_at(len);
+            rest = tail;
+            f(index, group);
+        }
+    }
+
+    /// The index that the next allocated key will have, including removed keys.
+    pub(crate) fn next_index(&self) -> usize {
+        self.base + self.keys_to_slice.count()
+    }
+
+    /// The unique id of this instance.
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Invalidate every key allocated so far.
+    pub(super) fn next_generation(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// The index of the first key allocated since the last clear.
+    pub(crate) fn first_index(&self) -> usize {
+        self.base
+    }
+
+    /// The number of leading key slots that have finished being allocated.
+    ///
+    /// Every key below this index can be resolved (unless removed), even while other threads are interning.
+    pub(crate) fn settled_index(&self) -> usize {
+        let count = self.keys_to_slice.count();
+        let mut settled = self.settled.load(Ordering::Acquire);
+        while settled < count && self.keys_to_slice.get(settled).is_some() {
+            settled += 1;
+        }
+        let settled = self
+            .settled
+            .fetch_max(settled, Ordering::AcqRel)
+            .max(settled);
+        self.base + settled
+    }
+
+    /// Get the slot of the key in `keys_to_slice`, or `None` if it was allocated before the last clear.
+    fn raw_slot(&self, key: Key) -> Option<usize> {
+        (key.into_repr() as usize).checked_sub(self.base)
+    }
+
+    /// Like [`ParaCord::raw_slot`], but applies the [`StaleKeyPolicy`] to stale keys.
+    #[inline]
+    #[track_caller]
+    fn slot(&self, key: Key) -> Option<usize> {
+        let slot = self.raw_slot(key);
+        if slot.is_none() {
+            self.stale_key(key);
+        }
+        slot
+    }
+
+    #[cold]
+    #[track_caller]
+    fn stale_key(&self, key: Key) {
+        if self.stale_key_policy == StaleKeyPolicy::Reuse {
+            return;
+        }
+        if let Some(callback) = &self.stale_key_callback {
+            if !self.stale_key_reported.swap(true, Ordering::Relaxed) {
+                callback(key);
+            }
+        }
+        if self.stale_key_policy == StaleKeyPolicy::Panic {
+            panic!("{STALE_KEY}: {key:?}");
+        }
+    }
+
+    /// Invoke `callback` when this instance first grows past one of the given soft limits.
+    ///
+    /// The callback is called on the thread that interned the entry that crossed the limit,
+    /// at most once per limit until the instance is cleared or swept.
+    pub fn with_soft_limits(
+        mut self,
+        limits: SoftLimits,
+        callback: impl Fn(SoftLimitEvent) + Send + Sync + 'static,
+    ) -> Self {
+        let state = limits::SoftLimitState::new(limits, Box::new(callback));
+        self.soft_limits = Some(Box::new(state));
+        self
+    }
+
+    /// Limit the interned data to `bytes`, counted like [`SoftLimits::memory`].
+    ///
+    /// Once the limit is reached, [`ParaCord::try_get_or_intern`] returns
+    /// [`InternError::MemoryLimit`] for new slices. Slices that are already interned can still
+    /// be looked up, and their keys stay valid. Other methods, like [`ParaCord::get_or_intern`],
+    /// ignore the limit, but the memory they use is still counted.
+    ///
+    /// Removing slices does not free any memory, so it is only reclaimed by a clear or sweep.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::{InternError, ParaCord};
+    /// let paracord = ParaCord::<u8>::default().with_memory_limit(100);
+    ///
+    /// let small = paracord.try_get_or_intern(&[1, 2, 3]).unwrap();
+    /// assert_eq!(paracord.try_get_or_intern(&[0; 100]), Err(InternError::MemoryLimit));
+    /// assert_eq!(paracord.try_get_or_intern(&[1, 2, 3]), Ok(small));
+    /// ```
+    pub fn with_memory_limit(mut self, bytes: usize) -> Self {
+        let limit = limits::MemoryLimit::new(bytes);
+        for (_, s) in self.iter() {
+            limit.charge::<T>(s.len());
+        }
+        self.memory_limit = Some(Box::new(limit));
+        self
+    }
+
+    /// Size the first chunk of each shard's arena to `bytes`, instead of 1KiB.
+    ///
+    /// Every shard allocates its first chunk eagerly, so a small chunk size reduces the memory
+    /// held by instances that intern little, and a large one avoids growing the arenas of
+    /// instances that intern a lot. Later chunks still double in size. The chunk size is kept
+    /// when the arenas are recreated, eg by [`ParaCord::clear`].
+    ///
+    /// With the `page-pool` feature, arenas are made of fixed-size pages, so this has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let small = ParaCord::<u8>::default().with_arena_chunk_size(64);
+    /// let foo = small.get_or_intern(b"foo");
+    /// assert_eq!(small.resolve(foo), b"foo");
+    ///
+    /// # #[cfg(not(feature = "page-pool"))]
+    /// assert!(small.memory_usage() < ParaCord::<u8>::default().memory_usage());
+    /// ```
+    pub fn with_arena_chunk_size(mut self, bytes: usize) -> Self {
+        self.arena_chunk = Some(bytes / core::mem::size_of::<T>().max(1));
+        self.renew_empty_arenas();
+        self
+    }
+
+    /// Replace every arena that holds no slices with one of the configured chunk size.
+    pub(super) fn renew_empty_arenas(&mut self) {
+        let Some(capacity) = self.arena_chunk else {
+            return;
+        };
+        for shard in self.slice_to_keys.shards_mut() {
+            let alloc = &mut shard.get_mut().alloc;
+            if alloc.used() == 0 {
+                *alloc = Alloc::with_capacity(capacity);
+            }
+        }
+    }
+
+    /// Check a bloom filter before probing the shards in [`ParaCord::get`], so that most lookups
+    /// of slices that are not interned don't need to lock a shard.
+    ///
+    /// The filter is sized to give `false_positive_rate` once `expected` slices are interned.
+    /// It never grows, so the false positive rate degrades as more slices are interned. Removed
+    /// slices stay in the filter until the next sweep or clear.
+    ///
+    /// Any slices that are already interned are added to the filter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `false_positive_rate` is not between 0 and 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default().with_bloom_filter(1000, 0.01);
+    ///
+    /// let foo = paracord.get_or_intern(&[1, 2, 3]);
+    /// assert_eq!(paracord.get(&[1, 2, 3]), Some(foo));
+    /// assert_eq!(paracord.get(&[4, 5, 6]), None);
+    /// ```
+    #[track_caller]
+    pub fn with_bloom_filter(mut self, expected: usize, false_positive_rate: f64) -> Self
+    where
+        T: Hash,
+        S: BuildHasher,
+    {
+        let bloom = bloom::BloomFilter::new(expected, false_positive_rate);
+        for (_, s) in self.iter() {
+            bloom.insert(self.hasher.hash_one(s));
+        }
+        self.bloom = Some(Box::new(bloom));
+        self
+    }
+
+    /// Add the hash to the bloom filter. Must be called before the slice is added to its shard.
+    fn bloom_insert(&self, hash: u64) {
+        if let Some(bloom) = &self.bloom {
+            bloom.insert(hash);
+        }
+    }
+
+    /// Returns `false` if no slice with this hash is interned.
+    #[inline]
+    fn may_contain(&self, hash: u64) -> bool {
+        self.bloom.as_ref().map_or(true, |b| b.may_contain(hash))
+    }
+
+    /// Record a newly interned slice against the memory and soft limits.
+    fn record_insert(&self, len: usize) {
+        if let Some(limit) = &self.memory_limit {
+            limit.charge::<T>(len);
+        }
+        self.record_soft_limits(len);
+    }
+
+    /// Record a newly interned slice against the soft limits.
+    fn record_soft_limits(&self, len: usize) {
+        if let Some(limits) = &self.soft_limits {
+            limits.record::<T>(self.len(), len);
+        }
+    }
+
+    /// Set how keys that were allocated before [`ParaCord::clear`] are handled.
+    ///
+    /// See [`StaleKeyPolicy`].
+    pub fn with_stale_key_policy(mut self, policy: StaleKeyPolicy) -> Self {
+        self.stale_key_policy = policy;
+        self
+    }
+
+    /// Invoke `callback` with the first stale key that is seen by this instance, eg to log it.
+    ///
+    /// Stale keys are only detected with a [`StaleKeyPolicy`] other than
+    /// [`StaleKeyPolicy::Reuse`]. The callback is called before [`StaleKeyPolicy::Panic`] panics.
+    pub fn with_stale_key_callback(
+        mut self,
+        callback: impl Fn(Key) + Send + Sync + 'static,
+    ) -> Self {
+        self.stale_key_callback = Some(AssertUnwindSafe(Arc::new(callback)));
+        self
+    }
+
+    /// Get the generation of this instance, which counts how many times every key has been
+    /// invalidated, by [`ParaCord::clear`], [`ParaCord::clear_retaining_capacity`] or
+    /// [`ParaCord::sweep_compact`]. It starts at 0, and wraps around on overflow.
+    ///
+    /// Keys from an earlier generation must not be used with this instance. With a
+    /// [`StaleKeyPolicy`] other than [`StaleKeyPolicy::Reuse`] they are usually detected,
+    /// but keys are eventually reused. To reliably detect them, wrap keys in a
+    /// [`SendKey`](crate::SendKey), which records the generation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let mut paracord = ParaCord::default();
+    /// paracord.get_or_intern(&[1, 2, 3]);
+    /// assert_eq!(paracord.generation(), 0);
+    ///
+    /// paracord.clear();
+    /// assert_eq!(paracord.generation(), 1);
+    /// ```
+    #[must_use]
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// The number of keys that have been removed.
+    pub(crate) fn removed(&self) -> usize {
+        self.removed
+    }
+
+    /// Get the hasher state used by this instance.
+    #[must_use]
+    pub fn hasher(&self) -> &S {
+        &self.hasher
     }
 
     /// Get an iterator over every ([`Key`], `&[T]`) pair
@@ -247,35 +1307,448 @@ impl<T, S> ParaCord<T, S> {
         self.into_iter()
     }
 
+    /// Get the first [`Key`] allocated in this [`ParaCord`] instance.
+    ///
+    /// Returns [`None`] if no slices have been allocated.
+    #[must_use]
+    pub fn first_key(&self) -> Option<Key> {
+        self.iter().next().map(|(k, _)| k)
+    }
+
+    /// Get the most recently allocated [`Key`] in this [`ParaCord`] instance.
+    ///
+    /// Returns [`None`] if no slices have been allocated.
+    ///
+    /// If slices are being allocated concurrently, this only considers slices whose allocation
+    /// has completed.
+    #[must_use]
+    pub fn last_key(&self) -> Option<Key> {
+        (0..self.keys_to_slice.count())
+            .rev()
+            .filter_map(|i| self.keys_to_slice.get(i))
+            .find(|s| !s.is_tombstone())
+            .map(|s| s.key)
+    }
+
+    /// Determine if key `a` was allocated before key `b`.
+    ///
+    /// This is equivalent to `a < b`, see the ordering guarantees on [`Key`].
+    #[must_use]
+    pub fn key_before(&self, a: Key, b: Key) -> bool {
+        a < b
+    }
+
+    /// Get an iterator over every ([`Key`], `&[T]`) pair with a key in the given range,
+    /// in allocation order.
+    ///
+    /// Since keys are ordered by allocation, this can be used to find all slices
+    /// that were allocated between two points in time.
+    pub fn keys_in_range(&self, range: impl RangeBounds<Key>) -> impl Iterator<Item = (Key, &[T])> {
+        let start = match range.start_bound() {
+            Bound::Included(k) => k.into_repr() as usize,
+            Bound::Excluded(k) => k.into_repr() as usize + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(k) => k.into_repr() as usize + 1,
+            Bound::Excluded(k) => k.into_repr() as usize,
+            Bound::Unbounded => usize::MAX,
+        };
+        let start = start.saturating_sub(self.base);
+        let end = end
+            .saturating_sub(self.base)
+            .min(self.keys_to_slice.count());
+
+        (start..end)
+            .filter_map(|i| self.keys_to_slice.get(i))
+            .filter_map(|s| Some((s.key, s.get()?)))
+    }
+
+    /// Count the interned slices by length.
+    ///
+    /// `buckets` are the inclusive upper bounds of each bucket, in ascending order.
+    /// The returned vec has one more entry than `buckets`, counting the slices longer than the last bound.
+    ///
+    /// This only reads the stored lengths, not the slices themselves.
+    #[must_use]
+    pub fn length_histogram(&self, buckets: &[usize]) -> Vec<u64> {
+        debug_assert!(
+            buckets.windows(2).all(|w| w[0] <= w[1]),
+            "buckets must be sorted"
+        );
+
+        let mut counts = vec![0; buckets.len() + 1];
+        for s in self.keys_to_slice.iter().map(|(_, s)| s) {
+            if !s.is_tombstone() {
+                counts[buckets.partition_point(|&b| b < s.slice_len())] += 1;
+            }
+        }
+        counts
+    }
+
     /// Deallocate all interned slices, but can retain some allocated memory
+    ///
+    /// Unless the [`StaleKeyPolicy`] is [`StaleKeyPolicy::Reuse`], new keys continue on from the
+    /// keys allocated before the clear, so that those keys can be detected.
     pub fn clear(&mut self) {
-        self.keys_to_slice.clear();
+        self.reset();
         self.slice_to_keys.shards_mut().iter_mut().for_each(|s| {
             s.get_mut().table.clear();
             drop(core::mem::take(&mut s.get_mut().alloc));
         });
+        self.renew_empty_arenas();
+    }
+
+    /// Deallocate all interned slices, but keep the memory of the arenas and tables for new slices.
+    ///
+    /// This suits "reset and refill" usage, eg an instance that is cleared every frame or request,
+    /// which would otherwise reallocate its arenas every time. Keys are handled the same way as
+    /// with [`ParaCord::clear`].
+    ///
+    /// With the `page-pool` feature, the pages of each arena are kept and refilled. Otherwise,
+    /// the first clear replaces the chunks of each arena with a single chunk that fits every
+    /// slice it held, which is then kept and refilled, so later rounds of the same size do not
+    /// allocate. Slices larger than a page, or that were adopted without copying, are always
+    /// released.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let mut paracord = ParaCord::default();
+    ///
+    /// for frame in 0..3u32 {
+    ///     for i in 0..100 {
+    ///         paracord.get_or_intern(&[frame, i]);
+    ///     }
+    ///     assert_eq!(paracord.len(), 100);
+    ///     paracord.clear_retaining_capacity();
+    ///     assert!(paracord.is_empty());
+    /// }
+    /// ```
+    pub fn clear_retaining_capacity(&mut self) {
+        self.reset();
+        self.slice_to_keys.shards_mut().iter_mut().for_each(|s| {
+            let shard = s.get_mut();
+            shard.table.clear();
+            shard.alloc.clear_retaining_capacity();
+        });
+    }
+
+    /// The index of the first key allocated after a clear.
+    fn base_after_clear(&self) -> usize {
+        match self.stale_key_policy {
+            StaleKeyPolicy::Reuse => 0,
+            _ => match self.next_index() {
+                // start reusing keys before the key space runs out.
+                next if next >= KEY_SPACE / 2 => 0,
+                next => next,
+            },
+        }
+    }
+
+    /// Create an empty instance with the given hasher, whose keys are allocated as if this
+    /// instance was cleared. The [`StaleKeyPolicy`], stale key callback and memory limit are kept,
+    /// but soft limits are not.
+    pub(crate) fn successor(&self, hasher: S) -> Self
+    where
+        S: BuildHasher,
+    {
+        let mut next = Self::with_hasher(hasher).with_stale_key_policy(self.stale_key_policy);
+        next.stale_key_callback = self
+            .stale_key_callback
+            .as_ref()
+            .map(|callback| AssertUnwindSafe(Arc::clone(callback)));
+        next.base = self.base_after_clear();
+        next.generation = self.generation.wrapping_add(1);
+        next.bloom = self.bloom.as_ref().map(|b| Box::new(b.empty_like()));
+        next.memory_limit = self
+            .memory_limit
+            .as_ref()
+            .map(|l| Box::new(limits::MemoryLimit::new(l.limit())));
+        if let Some(capacity) = self.arena_chunk {
+            next = next.with_arena_chunk_size(capacity * core::mem::size_of::<T>().max(1));
+        }
+        next
+    }
+
+    /// Reset everything except for the shards, as part of clearing.
+    fn reset(&mut self) {
+        self.base = self.base_after_clear();
+        if let Some(bloom) = &mut self.bloom {
+            bloom.clear();
+        }
+        self.next_generation();
+        self.keys_to_slice.clear();
+        if let Some(limits) = &mut self.soft_limits {
+            limits.clear();
+        }
+        if let Some(limit) = &mut self.memory_limit {
+            limit.clear();
+        }
+        self.removed = 0;
+        self.empty_key = OnceLock::new();
+        *self.settled.get_mut() = 0;
+        #[cfg(feature = "frequency")]
+        {
+            self.frequency = Default::default();
+        }
+    }
+
+    /// Perform bounded housekeeping on this [`ParaCord`] instance.
+    ///
+    /// This shrinks any shard hash tables that are much larger than the number of entries they hold,
+    /// eg after a burst of inserts followed by [`ParaCord::clear`]. With the `frequency` feature, the
+    /// sampled access counts are halved, so [`ParaCord::top_k`] favours recent accesses. It is
+    /// intended to be called periodically, eg from a background task, by applications with
+    /// long-lived instances.
+    ///
+    /// Interned slices are never moved, so arena memory is not affected. See
+    /// [`ParaCord::maintenance_with`] to also coalesce the arenas.
+    pub fn maintenance(&mut self) -> MaintenanceReport
+    where
+        T: Hash,
+        S: BuildHasher,
+    {
+        let mut report = MaintenanceReport::default();
+        let hasher = &self.hasher;
+
+        for shard in self.slice_to_keys.shards_mut() {
+            let table = &mut shard.get_mut().table;
+
+            // only shrink tables that are less than a quarter full, to avoid thrashing
+            // with tables that are about to grow again.
+            if table.len() * 4 < table.capacity() {
+                let before = table.allocation_size();
+                // safety: k is allocated correct
+                let hasher = |k: &*const InternedPtr<T>| unsafe { hasher.hash_one((**k).slice()) };
+                table.shrink_to_fit(hasher);
+                report.table_bytes_released += before - table.allocation_size();
+                report.tables_shrunk += 1;
+            }
+        }
+
+        #[cfg(feature = "frequency")]
+        {
+            report.frequencies_decayed = self.frequency.decay();
+        }
+
+        report
+    }
+
+    /// Like [`ParaCord::maintenance`], with the extra work enabled by `options`.
+    ///
+    /// With [`MaintenanceOptions::coalesce_arenas`], if most of the arena memory is unused, eg after
+    /// [`ParaCord::clear_retaining_capacity`], the arenas are first coalesced with
+    /// [`ParaCord::shrink_to_fit`]. That copies every slice in the arenas, so it takes time in
+    /// proportion to the interned data, and it moves those slices, invalidating any pointer from
+    /// [`ParaCord::resolve_ptr`]. Keys do not change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::{MaintenanceOptions, ParaCord};
+    /// let mut paracord = ParaCord::default();
+    /// for i in 0..100_000u32 {
+    ///     paracord.get_or_intern(&[i]);
+    /// }
+    /// paracord.clear_retaining_capacity();
+    /// let foo = paracord.get_or_intern(&[1, 2, 3]);
+    ///
+    /// let report = paracord.maintenance_with(MaintenanceOptions::new().coalesce_arenas(true));
+    /// assert!(report.arena_bytes_released > 0);
+    /// assert_eq!(paracord.resolve(foo), &[1, 2, 3]);
+    /// ```
+    pub fn maintenance_with(&mut self, options: MaintenanceOptions) -> MaintenanceReport
+    where
+        T: Hash + Eq + Copy,
+        S: BuildHasher,
+    {
+        let mut coalesced = MaintenanceReport::default();
+        if options.coalesce_arenas {
+            // a freshly coalesced arena can still leave up to a page unused in each shard.
+            let before = self.current_memory_stats();
+            let unused: usize = before
+                .shards
+                .iter()
+                .map(|s| s.arena_reserved - s.arena_used)
+                .sum();
+            let used: usize = before.shards.iter().map(|s| s.arena_used).sum();
+            if unused > usize::max(used, self.shard_count() * MAINTENANCE_ARENA_SLACK) {
+                self.shrink_to_fit();
+                let after = self.current_memory_stats();
+                for (before, after) in before.shards.iter().zip(&after.shards) {
+                    if after.arena_reserved < before.arena_reserved {
+                        coalesced.arenas_coalesced += 1;
+                        coalesced.arena_bytes_released +=
+                            before.arena_reserved - after.arena_reserved;
+                    }
+                }
+            }
+        }
+
+        MaintenanceReport {
+            arenas_coalesced: coalesced.arenas_coalesced,
+            arena_bytes_released: coalesced.arena_bytes_released,
+            ..self.maintenance()
+        }
     }
 
     #[cfg(test)]
-    /// Determine how much space has been used to allocate all the slices.
-    pub(crate) fn current_memory_usage(&mut self) -> usize {
-        let keys_size = self.keys_to_slice.count() * core::mem::size_of::<InternedPtr<T>>();
-
-        let shards_size = {
-            let acc = core::mem::size_of_val(self.slice_to_keys.shards());
-            self.slice_to_keys
-                .shards_mut()
-                .iter_mut()
-                .fold(acc, |acc, shard| {
-                    let shard = shard.get_mut();
-                    acc + shard.table.allocation_size() + shard.alloc.size()
-                })
-        };
+    /// Start allocating keys from `base`, as if that many keys had been cleared.
+    pub(crate) fn set_base(&mut self, base: usize) {
+        self.base = base;
+    }
+
+    /// Get the number of entries in each shard, briefly locking each shard.
+    pub(crate) fn shard_lens(&self) -> impl Iterator<Item = usize> + '_ {
+        self.slice_to_keys
+            .shards()
+            .iter()
+            .map(|shard| shard.read().table.len())
+    }
+}
+
+#[cfg(test)]
+impl<T, S> ParaCord<T, S> {
+    /// Run `f` while holding a read lock on every shard.
+    pub(crate) fn with_shards_read_locked<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _guards: Vec<_> = self
+            .slice_to_keys
+            .shards()
+            .iter()
+            .map(|s| s.read())
+            .collect();
+        f()
+    }
+}
+
+/// How a [`ParaCord`] handles keys that were allocated before the last [`ParaCord::clear`].
+///
+/// Set with [`ParaCord::with_stale_key_policy`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StaleKeyPolicy {
+    /// Keys are reused after a clear, so stale keys resolve to whatever was interned
+    /// into their slot since. This is the default.
+    #[default]
+    Reuse,
+    /// Panic when a stale key is resolved.
+    Panic,
+    /// Treat stale keys like removed keys: [`ParaCord::try_resolve`] returns [`None`]
+    /// and [`ParaCord::resolve`] panics.
+    ReturnNone,
+}
+
+type StaleKeyCallback = dyn Fn(Key) + Send + Sync;
+
+const STALE_KEY: &str = "key was allocated before this paracord instance was cleared";
+
+/// Interned slices are limited to `u32::MAX` elements, so their length fits in 32 bits.
+const TOO_LONG: &str = "interned slices and strings must have at most u32::MAX \
+    elements or bytes, use `try_get_or_intern` to reject longer input";
+
+/// Keys are indices below `u32::MAX`.
+const KEY_SPACE: usize = u32::MAX as usize;
 
-        size_of::<Self>() + keys_size + shards_size
+/// Returned by [`ParaCord::get_or_intern_timeout`] when the shard lock could not be acquired in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out waiting for the shard lock")
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+/// Returned by [`ParaCord::try_get_or_intern`] when a slice cannot be interned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InternError {
+    /// Every key has been allocated.
+    KeySpaceExhausted,
+    /// The slice has more than `u32::MAX` elements.
+    TooLong,
+    /// The slice would exceed the [memory limit](ParaCord::with_memory_limit).
+    MemoryLimit,
+}
+
+impl fmt::Display for InternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InternError::KeySpaceExhausted => f.write_str("no keys left to allocate"),
+            InternError::TooLong => {
+                f.write_str("slice is too long, it must have at most u32::MAX elements")
+            }
+            InternError::MemoryLimit => f.write_str("memory limit exceeded"),
+        }
     }
 }
 
+impl std::error::Error for InternError {}
+
+/// Call `f` until it returns `Some`, or until the deadline has passed.
+fn retry_until<G>(
+    deadline: Option<Instant>,
+    mut f: impl FnMut() -> Option<G>,
+) -> Result<G, Timeout> {
+    loop {
+        if let Some(guard) = f() {
+            return Ok(guard);
+        }
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            return Err(Timeout);
+        }
+        std::hint::spin_loop();
+    }
+}
+
+/// Unused arena memory per shard that [`ParaCord::maintenance_with`] tolerates, in bytes.
+const MAINTENANCE_ARENA_SLACK: usize = 4096;
+
+/// Extra work for [`ParaCord::maintenance_with`] to do.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceOptions {
+    coalesce_arenas: bool,
+}
+
+impl MaintenanceOptions {
+    /// Only the work done by [`ParaCord::maintenance`].
+    pub const fn new() -> Self {
+        Self {
+            coalesce_arenas: false,
+        }
+    }
+
+    /// Coalesce the arenas into a single chunk per shard if most of their memory is unused.
+    ///
+    /// This moves the interned slices, see [`ParaCord::maintenance_with`].
+    pub const fn coalesce_arenas(mut self, coalesce: bool) -> Self {
+        self.coalesce_arenas = coalesce;
+        self
+    }
+}
+
+/// A summary of the actions taken by [`ParaCord::maintenance`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MaintenanceReport {
+    /// How many shard hash tables were shrunk.
+    pub tables_shrunk: usize,
+    /// How many bytes of hash table memory were released.
+    pub table_bytes_released: usize,
+    /// How many shard arenas were coalesced into a single chunk, by
+    /// [`MaintenanceOptions::coalesce_arenas`].
+    pub arenas_coalesced: usize,
+    /// How many bytes of arena memory were released.
+    pub arena_bytes_released: usize,
+    /// How many sampled access counts were halved, with the `frequency` feature.
+    pub frequencies_decayed: usize,
+}
+
 impl<T: Hash + Eq + Copy, I: AsRef<[T]>, S: BuildHasher + Default> FromIterator<I>
     for ParaCord<T, S>
 {
@@ -286,6 +1759,21 @@ impl<T: Hash + Eq + Copy, I: AsRef<[T]>, S: BuildHasher + Default> FromIterator<
         let mut this = Self {
             keys_to_slice: boxcar::Vec::with_capacity(len),
             slice_to_keys: ClashCollection::default(),
+            removed: 0,
+            empty_key: OnceLock::new(),
+            settled: AtomicUsize::new(0),
+            base: 0,
+            stale_key_policy: StaleKeyPolicy::Reuse,
+            stale_key_callback: None,
+            stale_key_reported: AtomicBool::new(false),
+            soft_limits: None,
+            memory_limit: None,
+            arena_chunk: None,
+            #[cfg(feature = "frequency")]
+            frequency: Default::default(),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            generation: 0,
+            bloom: None,
             hasher: S::default(),
         };
         this.extend(iter);
@@ -304,9 +1792,43 @@ impl<T: Hash + Eq + Copy, I: AsRef<[T]>, S: BuildHasher> Extend<I> for ParaCord<
     }
 }
 
+/// Low-level access to the key slots, for rebuilding an instance with the exact same keys.
+impl<T, S> ParaCord<T, S> {
+    /// Iterate over every key slot in key order, including removed keys.
+    ///
+    /// Stops at the first slot that has not finished being allocated.
+    pub(crate) fn slots(&self, start: usize) -> impl Iterator<Item = Option<&[T]>> {
+        // keys from before the last clear are written as removed.
+        let stale = (start..self.base).map(|_| None);
+        let current = (start.saturating_sub(self.base)..)
+            .map_while(|i| self.keys_to_slice.get(i))
+            .map(|s| s.get());
+        stale.chain(current)
+    }
+
+    /// Allocate a key that is already removed.
+    pub(crate) fn push_tombstone_mut(&mut self) -> Key {
+        let base = self.base;
+        let key = self
+            .keys_to_slice
+            .push_with(|key| InternedPtr::tombstone(Key::from_index(base + key)));
+        self.removed += 1;
+        Key::from_index(base + key)
+    }
+}
+
+impl<T: Hash + Eq + Copy, S: BuildHasher> ParaCord<T, S> {
+    /// Intern the slice, allocating the next key if it is new.
+    pub(crate) fn push_mut(&mut self, s: &[T]) -> Key {
+        let hash = self.hasher.hash_one(s);
+        self.intern_slow_mut(s, hash)
+    }
+}
+
 impl<T: Hash + Eq + Copy, S: BuildHasher> Index<Key> for ParaCord<T, S> {
     type Output = [T];
 
+    #[track_caller]
     fn index(&self, index: Key) -> &Self::Output {
         self.resolve(index)
     }
@@ -324,9 +1846,11 @@ pub(crate) mod iter_private {
         type Item = (Key, &'a [T]);
 
         fn next(&mut self) -> Option<Self::Item> {
-            let (key, s) = self.inner.next()?;
-            // SAFETY: we assume the key is correct given its existence in the set
-            Some(unsafe { (Key::new_unchecked(key as u32), s.slice()) })
+            loop {
+                let (_, ptr) = self.inner.next()?;
+                let Some(s) = ptr.get() else { continue };
+                return Some((ptr.key, s));
+            }
         }
     }
 }
@@ -344,7 +1868,7 @@ impl<'a, T, S> IntoIterator for &'a ParaCord<T, S> {
 
 #[cfg(test)]
 mod tests {
-    use super::ParaCord;
+    use super::{ParaCord, ShardedParaCord};
 
     fn is_send<T: Send>() {}
     fn is_sync<T: Sync>() {}
@@ -352,6 +1876,8 @@ mod tests {
     fn inner_check_send_sync<T: 'static + Send + Sync>() {
         is_send::<ParaCord<T>>();
         is_sync::<ParaCord<T>>();
+        is_send::<ShardedParaCord<T>>();
+        is_sync::<ShardedParaCord<T>>();
     }
 
     #[test]
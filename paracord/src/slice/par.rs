@@ -0,0 +1,122 @@
+use std::hash::{BuildHasher, Hash};
+
+use rayon::iter::{
+    FromParallelIterator, IndexedParallelIterator, IntoParallelIterator, ParallelExtend,
+    ParallelIterator,
+};
+
+use crate::slice::{Collection, ParaCord};
+use crate::Key;
+
+/// Exclusive access to one shard, to be handed to a rayon worker.
+struct ShardMut<'a, T>(&'a mut Collection<T>);
+
+// Safety: the shard's pointers point into `keys_to_slice` and the shard's own arena,
+// which are only read through `&[T]`, so this has the same requirements as sending `&mut Vec<T>`
+// and `&[T]` together.
+unsafe impl<T: Send + Sync> Send for ShardMut<'_, T> {}
+
+/// Interns in bulk by first partitioning the slices by shard, then interning every partition
+/// in parallel with exclusive access to its shard, so no shard locks are taken.
+///
+/// Keys are allocated in an unspecified order.
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+impl<T, I, S> ParallelExtend<I> for ParaCord<T, S>
+where
+    T: Hash + Eq + Copy + Send + Sync,
+    I: AsRef<[T]> + Send,
+    S: BuildHasher + Sync,
+{
+    fn par_extend<A: IntoParallelIterator<Item = I>>(&mut self, par_iter: A) {
+        let this = &*self;
+        let hasher = &self.hasher;
+        let partition = || (0..this.shard_count()).map(|_| Vec::new()).collect();
+
+        let partitions: Vec<Vec<(u64, I)>> = par_iter
+            .into_par_iter()
+            .fold(partition, |mut partitions: Vec<Vec<_>>, s| {
+                let hash = hasher.hash_one(s.as_ref());
+                partitions[this.shard_index(hash)].push((hash, s));
+                partitions
+            })
+            .reduce(partition, |mut a, b| {
+                for (a, b) in a.iter_mut().zip(b) {
+                    a.extend(b);
+                }
+                a
+            });
+
+        let keys_to_slice = &self.keys_to_slice;
+        let base = self.base;
+        let removed = self.removed;
+        let soft_limits = self.soft_limits.as_deref();
+        let memory_limit = self.memory_limit.as_deref();
+        let bloom = self.bloom.as_deref();
+        let shards: Vec<_> = self
+            .slice_to_keys
+            .shards_mut()
+            .iter_mut()
+            .map(|shard| ShardMut(shard.get_mut()))
+            .collect();
+
+        shards
+            .into_par_iter()
+            .zip(partitions)
+            .for_each(|(shard, partition)| {
+                for (hash, s) in partition {
+                    let s = s.as_ref();
+                    if let Some(bloom) = bloom {
+                        bloom.insert(hash);
+                    }
+                    let (_, inserted) = shard.0.intern(keys_to_slice, base, hasher, s, hash);
+                    if let Some(limit) = memory_limit.filter(|_| inserted) {
+                        limit.charge::<T>(s.len());
+                    }
+                    if let Some(limits) = soft_limits.filter(|_| inserted) {
+                        limits.record::<T>(keys_to_slice.count() - removed, s.len());
+                    }
+                }
+            });
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+impl<T, I, S> FromParallelIterator<I> for ParaCord<T, S>
+where
+    T: Hash + Eq + Copy + Send + Sync,
+    I: AsRef<[T]> + Send,
+    S: BuildHasher + Default + Sync,
+{
+    fn from_par_iter<A: IntoParallelIterator<Item = I>>(par_iter: A) -> Self {
+        let mut this = Self::with_hasher(S::default());
+        this.par_extend(par_iter);
+        this
+    }
+}
+
+impl<T: Sync, S: Sync> ParaCord<T, S> {
+    /// Get a parallel iterator over every ([`Key`], `&[T]`) pair in this instance,
+    /// in an unspecified order.
+    ///
+    /// Slices interned concurrently may not be included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// use rayon::prelude::*;
+    ///
+    /// let paracord: ParaCord<u8> = (0..100u8).into_par_iter().map(|i| [i]).collect();
+    /// let sum: usize = paracord.par_iter().map(|(_, s)| s[0] as usize).sum();
+    /// assert_eq!(sum, 4950);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = (Key, &[T])> {
+        (0..self.keys_to_slice.count())
+            .into_par_iter()
+            .filter_map(|i| {
+                let ptr = self.keys_to_slice.get(i)?;
+                Some((ptr.key, ptr.get()?))
+            })
+    }
+}
@@ -0,0 +1,88 @@
+use rand::seq::index;
+use rand::RngCore;
+
+use crate::slice::ParaCord;
+use crate::Key;
+
+impl<T, S> ParaCord<T, S> {
+    /// Choose up to `n` distinct keys uniformly at random, in a random order.
+    ///
+    /// Removed keys are never chosen. If fewer than `n` slices are interned, every key is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    /// for i in 0..100u8 {
+    ///     paracord.get_or_intern(&[i]);
+    /// }
+    ///
+    /// let sample = paracord.sample(&mut rand::rng(), 10);
+    /// assert_eq!(sample.len(), 10);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[must_use]
+    pub fn sample(&self, rng: &mut impl RngCore, n: usize) -> Vec<Key> {
+        if self.removed == 0 {
+            let slots = self.keys_to_slice.count();
+            return index::sample(rng, slots, n.min(slots))
+                .into_iter()
+                .filter_map(|slot| self.keys_to_slice.get(slot))
+                .map(|s| s.key)
+                .collect();
+        }
+
+        let live: Vec<Key> = self.iter().map(|(key, _)| key).collect();
+        index::sample(rng, live.len(), n.min(live.len()))
+            .into_iter()
+            .map(|i| live[i])
+            .collect()
+    }
+
+    /// Choose up to `n` distinct keys at random, weighted by how often they are accessed,
+    /// in a random order.
+    ///
+    /// Accesses are sampled as described in [`ParaCord::top_k`]. Every key has a weight of at
+    /// least one, so keys that were never counted can still be chosen. Removed keys are never
+    /// chosen. If fewer than `n` slices are interned, every key is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let paracord = ParaCord::default();
+    /// paracord.get_or_intern(&[0]);
+    /// for i in 1..100u8 {
+    ///     paracord.get_or_intern(&[i]);
+    /// }
+    /// for _ in 0..100_000 {
+    ///     paracord.get(&[0]);
+    /// }
+    ///
+    /// let sample = paracord.sample_weighted(&mut rand::rng(), 1);
+    /// assert_eq!(sample.len(), 1);
+    /// ```
+    #[cfg(feature = "frequency")]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "rand", feature = "frequency"))))]
+    #[must_use]
+    pub fn sample_weighted(&self, rng: &mut impl RngCore, n: usize) -> Vec<Key> {
+        let live: Vec<Key> = self.iter().map(|(key, _)| key).collect();
+        let mut weights = vec![1.0; self.keys_to_slice.count()];
+        for (key, count) in self.frequency.iter() {
+            if let Some(weight) = self.raw_slot(key).and_then(|slot| weights.get_mut(slot)) {
+                *weight += f64::from(count);
+            }
+        }
+
+        let weight = |i: usize| {
+            let slot = self.raw_slot(live[i]).expect("live keys have a slot");
+            weights[slot]
+        };
+        index::sample_weighted(rng, live.len(), weight, n.min(live.len()))
+            .expect("weights are positive and finite")
+            .into_iter()
+            .map(|i| live[i])
+            .collect()
+    }
+}
@@ -0,0 +1,250 @@
+use core::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::sync::RwLock;
+
+use hashbrown::hash_table::Entry;
+
+use super::alloc::InternedPtr;
+use super::Collection;
+use crate::Key;
+
+/// A variant of [`ParaCord`](super::ParaCord) that allocates keys independently in each shard.
+///
+/// [`ParaCord`](super::ParaCord) allocates every key from a single shared counter,
+/// which can become a point of contention when many threads insert new slices at once.
+/// This type instead partitions the key space: the top bits of a key's index select the shard,
+/// and the low bits are a counter local to that shard, only updated while the shard is locked.
+///
+/// The trade-offs are:
+/// * keys are no longer ordered by allocation, only by allocation within a shard.
+/// * each shard can allocate at most `2^32 / shards` keys, and will panic if it runs out.
+///   The last shard can allocate one fewer, as `u32::MAX` is not a valid key.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::slice::ShardedParaCord;
+///
+/// let paracord = ShardedParaCord::default();
+///
+/// let foo = paracord.get_or_intern(&[1,2,3,4]);
+/// let bar = paracord.get_or_intern(&[5,6,7,8]);
+///
+/// assert_ne!(foo, bar);
+/// assert_eq!(paracord.get(&[1,2,3,4]), Some(foo));
+/// assert_eq!(paracord.resolve(foo), &[1,2,3,4]);
+/// assert_eq!(paracord.resolve(bar), &[5,6,7,8]);
+/// ```
+pub struct ShardedParaCord<T, S = foldhash::fast::RandomState> {
+    shards: Box<[Shard<T>]>,
+    shard_bits: u32,
+    hasher: S,
+}
+
+struct Shard<T> {
+    collection: RwLock<Collection<T>>,
+    keys: boxcar::Vec<InternedPtr<T>>,
+}
+
+impl<T> Default for Shard<T> {
+    fn default() -> Self {
+        Self {
+            collection: RwLock::default(),
+            keys: boxcar::Vec::new(),
+        }
+    }
+}
+
+// Safety: no pointers are accessed without synchronisation.
+unsafe impl<T: Sync, S: Sync> Sync for ShardedParaCord<T, S> {}
+// Safety: ShardedParaCord only allows extracting `&[T]`, so it is find with `T: Sync` only.
+unsafe impl<T: Sync, S: Send> Send for ShardedParaCord<T, S> {}
+
+impl<T: fmt::Debug, S> fmt::Debug for ShardedParaCord<T, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Default for ShardedParaCord<T> {
+    fn default() -> Self {
+        Self::with_hasher(foldhash::fast::RandomState::default())
+    }
+}
+
+impl<T, S: BuildHasher> ShardedParaCord<T, S> {
+    /// Create a new `ShardedParaCord` instance with the given hasher state,
+    /// and a number of shards based on the available parallelism.
+    pub fn with_hasher(hasher: S) -> Self {
+        let parallelism = std::thread::available_parallelism().map_or(1, usize::from);
+        Self::with_shards_and_hasher(parallelism * 4, hasher)
+    }
+
+    /// Create a new `ShardedParaCord` instance with the given hasher state and number of shards.
+    ///
+    /// The number of shards is rounded up to a power of two, and limited to `2^16`.
+    pub fn with_shards_and_hasher(shards: usize, hasher: S) -> Self {
+        let shards = shards.clamp(1, 1 << 16).next_power_of_two();
+        Self {
+            shards: (0..shards).map(|_| Shard::default()).collect(),
+            shard_bits: shards.trailing_zeros(),
+            hasher,
+        }
+    }
+}
+
+impl<T, S> ShardedParaCord<T, S> {
+    /// Determine which shard a hash belongs to.
+    ///
+    /// This skips the top 7 bits that hashbrown uses for its control bytes.
+    #[inline]
+    fn shard_for_hash(&self, hash: u64) -> usize {
+        ((hash << 7) >> 32 >> (32 - self.shard_bits)) as usize
+    }
+
+    #[inline]
+    fn local_bits(&self) -> u32 {
+        32 - self.shard_bits
+    }
+
+    #[inline]
+    fn make_key(&self, shard: usize, local: usize) -> Key {
+        let mut limit = 1u64 << self.local_bits();
+        if shard == self.shards.len() - 1 {
+            // the final index of the last shard would be `u32::MAX`, which is reserved.
+            limit -= 1;
+        }
+        assert!(
+            (local as u64) < limit,
+            "too many keys allocated in one shard"
+        );
+        Key::from_index(((shard as u64) << self.local_bits()) as usize | local)
+    }
+
+    #[inline]
+    fn split_key(&self, key: Key) -> (usize, usize) {
+        let i = key.into_repr() as u64;
+        let shard = i >> self.local_bits();
+        let local = i & ((1 << self.local_bits()) - 1);
+        (shard as usize, local as usize)
+    }
+
+    /// Determine how many shards this instance has.
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Try and resolve the slice associated with this [`Key`].
+    ///
+    /// This can return [`None`] if the key was not allocated by this instance.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&[T]> {
+        let (shard, local) = self.split_key(key);
+        self.shards.get(shard)?.keys.get(local).map(|s| s.slice())
+    }
+
+    /// Resolve the slice associated with this [`Key`].
+    ///
+    /// # Panics
+    /// This can panic if the key was not allocated by this instance.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &[T] {
+        self.try_resolve(key)
+            .expect("key should be allocated by this paracord instance")
+    }
+
+    /// Determine how many slices have been allocated
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.keys.count()).sum()
+    }
+
+    /// Determine if no slices have been allocated
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get an iterator over every ([`Key`], `&[T]`) pair
+    /// that has been allocated in this instance, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &[T])> {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.keys.iter().map(|(_, s)| (s.key, s.slice())))
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> ShardedParaCord<T, S> {
+    /// Try and get the [`Key`] associated with the given slice.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &[T]) -> Option<Key> {
+        let hash = self.hasher.hash_one(s);
+        let shard = &self.shards[self.shard_for_hash(hash)];
+        let collection = shard.collection.read().unwrap_or_else(|e| e.into_inner());
+
+        // safety: k is allocated correct
+        let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
+        // safety: k is allocated correct
+        let map = |k: &*const InternedPtr<T>| unsafe { (**k).key };
+
+        collection.table.find(hash, eq).map(map)
+    }
+}
+
+impl<T: Hash + Eq + Copy, S: BuildHasher> ShardedParaCord<T, S> {
+    /// Try and get the [`Key`] associated with the given slice.
+    /// Allocates a new key if not found.
+    pub fn get_or_intern(&self, s: &[T]) -> Key {
+        let hash = self.hasher.hash_one(s);
+        let shard_idx = self.shard_for_hash(hash);
+        let shard = &self.shards[shard_idx];
+
+        {
+            let collection = shard.collection.read().unwrap_or_else(|e| e.into_inner());
+
+            // safety: k is allocated correct
+            let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
+            if let Some(k) = collection.table.find(hash, eq) {
+                // safety: k is allocated correct
+                return unsafe { (**k).key };
+            }
+        }
+
+        self.intern_slow(s, hash, shard_idx)
+    }
+
+    #[cold]
+    fn intern_slow(&self, s: &[T], hash: u64, shard_idx: usize) -> Key {
+        let _len = u32::try_from(s.len()).expect(super::TOO_LONG);
+
+        let shard = &self.shards[shard_idx];
+        let mut collection = shard.collection.write().unwrap_or_else(|e| e.into_inner());
+        let Collection { table, alloc } = &mut *collection;
+
+        // safety: k is allocated correct
+        let eq = |k: &*const InternedPtr<T>| unsafe { s == (**k).slice() };
+        // safety: k is allocated correct
+        let hasher = |k: &*const InternedPtr<T>| unsafe { self.hasher.hash_one((**k).slice()) };
+
+        match table.entry(hash, eq, hasher) {
+            // safety: entry is allocated correct
+            Entry::Occupied(entry) => unsafe { (**entry.get()).key },
+            Entry::Vacant(entry) => {
+                // the shard is write-locked, so the next index is not contended.
+                let key = self.make_key(shard_idx, shard.keys.count());
+                let local = shard
+                    .keys
+                    .push_with(|_| InternedPtr::new(alloc.alloc(s), key));
+                debug_assert_eq!(self.split_key(key), (shard_idx, local));
+
+                // safety: we have just inserted this entry
+                let interned_ptr = unsafe { shard.keys.get_unchecked(local) };
+                entry.insert(interned_ptr as *const InternedPtr<T>);
+
+                key
+            }
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::sync::OnceLock;
+
+use clashmap::ClashCollection;
+
+use super::alloc::{Alloc, InternedPtr, Unowned};
+use super::{Collection, ParaCord};
+use crate::collections::{KeyBitSet, RemapTable};
+use crate::Key;
+
+/// A summary of the entries removed by [`ParaCord::sweep`] or [`ParaCord::sweep_compact`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SweepReport {
+    /// How many entries were retained.
+    pub retained: usize,
+    /// How many entries were removed.
+    pub removed: usize,
+}
+
+impl<T: Hash + Eq + Copy, S: BuildHasher> ParaCord<T, S> {
+    /// Remove every slice whose key is not in the `live` set, releasing its memory.
+    ///
+    /// The keys of the retained slices are unchanged, and the keys of removed slices are never reused.
+    /// After a sweep, [`ParaCord::get`] will no longer find removed slices, [`ParaCord::try_resolve`]
+    /// will return `None` for their keys and [`ParaCord::resolve`] will panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::collections::KeyBitSet;
+    /// use paracord::slice::ParaCord;
+    ///
+    /// let mut paracord = ParaCord::default();
+    /// let foo = paracord.get_or_intern(&[1, 2, 3]);
+    /// let bar = paracord.get_or_intern(&[4, 5, 6]);
+    ///
+    /// let live = KeyBitSet::from_iter([bar]);
+    /// let report = paracord.sweep(&live);
+    /// assert_eq!(report.removed, 1);
+    ///
+    /// assert_eq!(paracord.try_resolve(foo), None);
+    /// assert_eq!(paracord.resolve(bar), &[4, 5, 6]);
+    /// ```
+    pub fn sweep(&mut self, live: &KeyBitSet) -> SweepReport {
+        self.rebuild(|key| live.contains(key), false).0
+    }
+
+    /// Remove every slice whose key is not in the `live` set, releasing its memory,
+    /// and renumber the retained slices so that the keys are dense again.
+    ///
+    /// Retained slices keep their relative order. The returned [`RemapTable`] maps the old
+    /// keys of the retained slices to their new keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::collections::KeyBitSet;
+    /// use paracord::slice::ParaCord;
+    ///
+    /// let mut paracord = ParaCord::default();
+    /// let foo = paracord.get_or_intern(&[1, 2, 3]);
+    /// let bar = paracord.get_or_intern(&[4, 5, 6]);
+    ///
+    /// let live = KeyBitSet::from_iter([bar]);
+    /// let (report, remap) = paracord.sweep_compact(&live);
+    /// assert_eq!(report.removed, 1);
+    ///
+    /// assert_eq!(remap.get(foo), None);
+    /// let bar2 = remap.get(bar).unwrap();
+    /// assert_eq!(paracord.resolve(bar2), &[4, 5, 6]);
+    /// assert_eq!(paracord.first_key(), Some(bar2));
+    /// ```
+    pub fn sweep_compact(&mut self, live: &KeyBitSet) -> (SweepReport, RemapTable) {
+        self.rebuild(|key| live.contains(key), true)
+    }
+
+    /// Remove the slice associated with this key, without changing any other key.
+    ///
+    /// The key is never reused, so it can't alias a different slice later. After a delete,
+    /// [`ParaCord::get`] will no longer find the slice, and [`ParaCord::try_resolve`] will return
+    /// `None` for the key. The memory of deleted slices is released by [`ParaCord::reclaim`]
+    /// or the next sweep, without renumbering any keys.
+    ///
+    /// Returns `false` if the key was already removed, or was not allocated by this instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    ///
+    /// let mut paracord = ParaCord::default();
+    /// let foo = paracord.get_or_intern(&[1, 2, 3]);
+    /// let bar = paracord.get_or_intern(&[4, 5, 6]);
+    ///
+    /// assert!(paracord.delete(foo));
+    /// assert!(!paracord.delete(foo));
+    /// assert_eq!(paracord.try_resolve(foo), None);
+    /// assert_eq!(paracord.get(&[1, 2, 3]), None);
+    ///
+    /// // interning the slice again allocates a new key.
+    /// assert_ne!(paracord.get_or_intern(&[1, 2, 3]), foo);
+    /// assert_eq!(paracord.resolve(bar), &[4, 5, 6]);
+    /// ```
+    pub fn delete(&mut self, key: Key) -> bool {
+        let Some(slot) = self.raw_slot(key) else {
+            return false;
+        };
+        let Some(ptr) = self.keys_to_slice.get(slot) else {
+            return false;
+        };
+        let Some(s) = ptr.get() else {
+            return false;
+        };
+
+        let hash = self.hasher.hash_one(s);
+        let target: *const InternedPtr<T> = ptr;
+        let shard = self.slice_to_keys.get_mut(hash);
+        if let Ok(entry) = shard.table.find_entry(hash, |k| core::ptr::eq(*k, target)) {
+            entry.remove();
+        }
+
+        if let Some(ptr) = self.keys_to_slice.get_mut(slot) {
+            *ptr = InternedPtr::tombstone(key);
+        }
+        if self.empty_key.get() == Some(&key) {
+            self.empty_key = OnceLock::new();
+        }
+        self.removed += 1;
+        true
+    }
+
+    /// Remove every slice for which `f` returns `false`, as if by [`ParaCord::delete`].
+    ///
+    /// Keys of the retained slices are unchanged, and the removed keys are never reused. The memory
+    /// of removed slices is released by [`ParaCord::reclaim`] or the next sweep.
+    ///
+    /// Returns how many slices were removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    ///
+    /// let mut paracord = ParaCord::default();
+    /// let foo = paracord.get_or_intern(&[1, 2, 3]);
+    /// let bar = paracord.get_or_intern(&[4, 5]);
+    ///
+    /// assert_eq!(paracord.retain(|_, s| s.len() == 2), 1);
+    /// assert_eq!(paracord.try_resolve(foo), None);
+    /// assert_eq!(paracord.get(&[4, 5]), Some(bar));
+    /// ```
+    pub fn retain(&mut self, mut f: impl FnMut(Key, &[T]) -> bool) -> usize {
+        let dead: Vec<Key> = self
+            .iter()
+            .filter_map(|(key, s)| (!f(key, s)).then_some(key))
+            .collect();
+        dead.iter().filter(|&&key| self.delete(key)).count()
+    }
+
+    /// Release the memory of every slice removed by [`ParaCord::delete`], without changing any keys.
+    ///
+    /// This copies every remaining slice into fresh storage, like [`ParaCord::sweep`].
+    pub fn reclaim(&mut self) {
+        self.rebuild(|_| true, false);
+    }
+
+    /// Release the excess memory left behind by bursts of interning and removal, without changing
+    /// any keys.
+    ///
+    /// Every remaining slice in an arena is copied into a single arena chunk per shard, the hash
+    /// table of every shard is shrunk to fit its entries, and the memory of slices removed by
+    /// [`ParaCord::delete`] is released. Slices interned with [`ParaCord::get_or_intern_static`]
+    /// or adopted without copying are kept where they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::slice::ParaCord;
+    /// let mut paracord = ParaCord::default();
+    /// for i in 0..1000u32 {
+    ///     paracord.get_or_intern(&[i]);
+    /// }
+    /// let foo = paracord.get_or_intern(&[1, 2, 3]);
+    ///
+    /// paracord.shrink_to_fit();
+    /// assert_eq!(paracord.get(&[1, 2, 3]), Some(foo));
+    /// assert_eq!(paracord.resolve(foo), &[1, 2, 3]);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        let mut unowned = self.take_unowned();
+
+        // size each arena so that every slice in an arena fits in its first chunk.
+        let mut sizes = vec![0; self.shard_count()];
+        for (_, s) in self.iter() {
+            if !unowned.contains_key(&(s.as_ptr(), s.len())) {
+                sizes[self.shard_of(s)] += s.len();
+            }
+        }
+
+        let (old_keys, _old_shards) = self.take_storage(false);
+        for (shard, size) in self.slice_to_keys.shards_mut().iter_mut().zip(sizes) {
+            shard.get_mut().alloc = Alloc::with_capacity(size);
+        }
+        self.keys_to_slice.reserve(old_keys.count());
+
+        for (_, ptr) in old_keys.iter() {
+            match ptr.get() {
+                Some(s) => {
+                    let key = self.push_kept_mut(s, &mut unowned);
+                    debug_assert_eq!(key, ptr.key);
+                }
+                None => {
+                    self.push_tombstone_mut();
+                }
+            }
+        }
+
+        let hasher = &self.hasher;
+        for shard in self.slice_to_keys.shards_mut() {
+            // safety: k is allocated correct
+            let rehash = |k: &*const InternedPtr<T>| unsafe { hasher.hash_one((**k).slice()) };
+            shard.get_mut().table.shrink_to_fit(rehash);
+        }
+    }
+
+    /// Take the slices that are not stored in an arena out of every shard, by address.
+    ///
+    /// The slices do not move, so they stay valid for the old keys.
+    pub(super) fn take_unowned(&mut self) -> HashMap<(*const T, usize), Unowned<T>> {
+        let mut unowned = HashMap::new();
+        for shard in self.slice_to_keys.shards_mut() {
+            for s in shard.get_mut().alloc.take_unowned() {
+                unowned.insert((s.as_ref().as_ptr(), s.as_ref().len()), s);
+            }
+        }
+        unowned
+    }
+
+    /// Intern a slice from the old keys, keeping it where it is if it was taken by
+    /// [`ParaCord::take_unowned`], or copying it into the arena otherwise.
+    pub(super) fn push_kept_mut(
+        &mut self,
+        s: &[T],
+        unowned: &mut HashMap<(*const T, usize), Unowned<T>>,
+    ) -> Key {
+        match unowned.remove(&(s.as_ptr(), s.len())) {
+            Some(s) => self.intern_unowned_mut(s),
+            None => self.push_mut(s),
+        }
+    }
+
+    /// Take the storage out of this instance, leaving it empty, so that slices can be copied
+    /// back into fresh storage.
+    ///
+    /// The old shards must outlive any copies out of the old keys, since they own the arenas.
+    pub(super) fn take_storage(
+        &mut self,
+        compact: bool,
+    ) -> (boxcar::Vec<InternedPtr<T>>, ClashCollection<Collection<T>>) {
+        let old_keys = core::mem::take(&mut self.keys_to_slice);
+        let old_shards = core::mem::take(&mut self.slice_to_keys);
+        self.renew_empty_arenas();
+        self.removed = 0;
+        if let Some(bloom) = &mut self.bloom {
+            bloom.clear();
+        }
+        self.empty_key = OnceLock::new();
+        if let Some(limits) = &mut self.soft_limits {
+            limits.clear();
+        }
+        if let Some(limit) = &mut self.memory_limit {
+            limit.clear();
+        }
+        *self.settled.get_mut() = 0;
+        if compact {
+            self.next_generation();
+            #[cfg(feature = "frequency")]
+            {
+                self.frequency = Default::default();
+            }
+        }
+        (old_keys, old_shards)
+    }
+
+    /// Copy every live slice into fresh storage, in key order.
+    ///
+    /// If `compact`, live slices are assigned new dense keys, otherwise every dead key is kept as a tombstone.
+    fn rebuild(
+        &mut self,
+        mut live: impl FnMut(Key) -> bool,
+        compact: bool,
+    ) -> (SweepReport, RemapTable) {
+        let mut unowned = self.take_unowned();
+        let (old_keys, _old_shards) = self.take_storage(compact);
+
+        let mut report = SweepReport::default();
+        let mut remap = RemapTable::with_capacity(if compact { old_keys.count() } else { 0 });
+
+        for (_, ptr) in old_keys.iter() {
+            match ptr.get() {
+                Some(s) if live(ptr.key) => {
+                    let key = self.push_kept_mut(s, &mut unowned);
+                    if compact {
+                        remap.insert(ptr.key, key);
+                    } else {
+                        debug_assert_eq!(key, ptr.key);
+                    }
+                    report.retained += 1;
+                }
+                s => {
+                    report.removed += s.is_some() as usize;
+                    if !compact {
+                        self.push_tombstone_mut();
+                    }
+                }
+            }
+        }
+
+        (report, remap)
+    }
+}
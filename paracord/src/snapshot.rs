@@ -0,0 +1,774 @@
+//! A binary snapshot format for [`ParaCord`] instances.
+//!
+//! Snapshots preserve the numeric value of every [`Key`](crate::Key), including keys removed by
+//! [`ParaCord::sweep`], so keys stored elsewhere remain valid after a reload.
+//!
+//! Every snapshot ends with a CRC-32 checksum of its contents, so a truncated or corrupted snapshot
+//! is detected when it is loaded, instead of resolving keys to the wrong strings.
+//!
+//! ```
+//! use paracord::ParaCord;
+//!
+//! let paracord = ParaCord::default();
+//! let foo = paracord.get_or_intern("foo");
+//!
+//! let mut buf = vec![];
+//! paracord.write_snapshot(&mut buf).unwrap();
+//!
+//! assert_eq!(paracord::snapshot::verify_snapshot(&buf[..]).unwrap(), 1);
+//!
+//! let paracord2: ParaCord = ParaCord::read_snapshot(&buf[..]).unwrap();
+//! assert_eq!(paracord2.resolve(foo), "foo");
+//! ```
+//!
+//! # Format
+//!
+//! All integers are little endian.
+//!
+//! | field    | type                | notes                                                      |
+//! |----------|---------------------|------------------------------------------------------------|
+//! | magic    | `[u8; 8]`           | `b"PARACORD"`                                              |
+//! | version  | `u32`               | `1` for full snapshots, `2` for partitions                 |
+//! | start    | `u64`               | only in version `2`: the index of the first key in the partition |
+//! | index    | `u32`               | only in version `2`: the index of the partition            |
+//! | parts    | `u32`               | only in version `2`: the number of partitions              |
+//! | total    | `u64`               | only in version `2`: the number of entries in every partition |
+//! | count    | `u64`               | number of entries                                          |
+//! | entries  | `count` entries     | a `u32` byte length followed by the UTF-8 string bytes. A length of `u32::MAX` marks a removed key |
+//! | checksum | `u32`               | CRC-32 of every preceding byte                             |
+//!
+//! # Partitions
+//!
+//! Large instances can be split into partitions with [`ParaCord::export_partitioned`], which can be
+//! written to separate files in parallel, and loaded in parallel with [`ParaCord::read_partitioned`].
+//!
+//! ```
+//! use paracord::ParaCord;
+//!
+//! let paracord = ParaCord::default();
+//! for i in 0..100 {
+//!     paracord.get_or_intern(&i.to_string());
+//! }
+//!
+//! let files: Vec<Vec<u8>> = std::thread::scope(|s| {
+//!     let handles: Vec<_> = paracord
+//!         .export_partitioned(4)
+//!         .into_iter()
+//!         .map(|task| {
+//!             s.spawn(move || {
+//!                 let mut buf = vec![];
+//!                 task.write(&mut buf).unwrap();
+//!                 buf
+//!             })
+//!         })
+//!         .collect();
+//!     handles.into_iter().map(|h| h.join().unwrap()).collect()
+//! });
+//!
+//! let paracord2: ParaCord = ParaCord::read_partitioned(files.iter().map(|f| &f[..])).unwrap();
+//! assert_eq!(paracord2.len(), 100);
+//! assert_eq!(paracord2.get("42"), paracord.get("42"));
+//! ```
+
+use std::hash::BuildHasher;
+use std::io::{self, Read, Write};
+
+use crc32fast::Hasher;
+use hashbrown::HashTable;
+
+use crate::{Key, ParaCord};
+
+const MAGIC: [u8; 8] = *b"PARACORD";
+const VERSION: u32 = 1;
+const PARTITION_VERSION: u32 = 2;
+const TOMBSTONE: u32 = u32::MAX;
+
+/// Error returned when loading or verifying a snapshot.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SnapshotError {
+    /// An IO error occurred while reading the snapshot.
+    Io(io::Error),
+    /// The snapshot ended before it was complete.
+    Truncated,
+    /// The snapshot does not start with the expected magic bytes.
+    BadMagic,
+    /// The snapshot was written with an unsupported format version.
+    UnsupportedVersion(u32),
+    /// The checksum stored in the snapshot does not match its contents.
+    ChecksumMismatch {
+        /// The checksum stored in the snapshot.
+        expected: u32,
+        /// The checksum of the snapshot contents.
+        actual: u32,
+    },
+    /// The snapshot contains invalid data, eg a string that is not UTF-8 or a duplicate string.
+    Corrupt,
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Io(_) => f.write_str("could not read snapshot"),
+            SnapshotError::Truncated => f.write_str("snapshot is truncated"),
+            SnapshotError::BadMagic => f.write_str("not a paracord snapshot"),
+            SnapshotError::UnsupportedVersion(v) => {
+                write!(f, "unsupported snapshot version {v}")
+            }
+            SnapshotError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "snapshot checksum mismatch: expected {expected:08x}, found {actual:08x}"
+            ),
+            SnapshotError::Corrupt => f.write_str("snapshot contains invalid data"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SnapshotError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            SnapshotError::Truncated
+        } else {
+            SnapshotError::Io(e)
+        }
+    }
+}
+
+struct ChecksumWriter<W> {
+    inner: W,
+    hasher: Hasher,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.hasher.update(buf);
+        self.inner.write_all(buf)
+    }
+}
+
+struct ChecksumReader<R> {
+    inner: R,
+    hasher: Hasher,
+}
+
+impl<R: Read> ChecksumReader<R> {
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(buf)?;
+        self.hasher.update(buf);
+        Ok(())
+    }
+
+    fn read_u32(&mut self) -> io::Result<u32> {
+        let mut buf = [0; 4];
+        self.read_exact(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn read_u64(&mut self) -> io::Result<u64> {
+        let mut buf = [0; 8];
+        self.read_exact(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    /// Read `len` bytes into `buf`, without trusting `len` for the allocation size.
+    fn read_bytes(&mut self, len: u32, buf: &mut Vec<u8>) -> io::Result<()> {
+        buf.clear();
+        let n = (&mut self.inner).take(len as u64).read_to_end(buf)?;
+        if n != len as usize {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        self.hasher.update(buf);
+        Ok(())
+    }
+}
+
+/// The position of a snapshot in the key space.
+#[derive(Clone, Copy)]
+struct Range {
+    start: usize,
+    count: usize,
+    /// The index of the partition, or 0 for a full snapshot.
+    index: u32,
+    /// The number of partitions, or 1 for a full snapshot.
+    parts: u32,
+    /// The number of entries in every partition, or `count` for a full snapshot.
+    total: usize,
+}
+
+/// Decode a snapshot or partition, calling `f` for every entry in key order.
+fn decode<R: Read>(
+    r: R,
+    mut f: impl FnMut(Option<&str>) -> Result<(), SnapshotError>,
+) -> Result<Range, SnapshotError> {
+    let mut r = ChecksumReader {
+        inner: r,
+        hasher: Hasher::new(),
+    };
+
+    let mut magic = [0; 8];
+    r.read_exact(&mut magic)?;
+    if magic != MAGIC {
+        return Err(SnapshotError::BadMagic);
+    }
+
+    let partition = match r.read_u32()? {
+        VERSION => None,
+        PARTITION_VERSION => Some((r.read_u64()?, r.read_u32()?, r.read_u32()?, r.read_u64()?)),
+        version => return Err(SnapshotError::UnsupportedVersion(version)),
+    };
+
+    let count = r.read_u64()?;
+    let count = usize::try_from(count).map_err(|_| SnapshotError::Corrupt)?;
+
+    let (start, index, parts, total) = partition.unwrap_or((0, 0, 1, count as u64));
+    let start = usize::try_from(start).map_err(|_| SnapshotError::Corrupt)?;
+    let total = usize::try_from(total).map_err(|_| SnapshotError::Corrupt)?;
+    if index >= parts || start.checked_add(count).map_or(true, |end| end > total) {
+        return Err(SnapshotError::Corrupt);
+    }
+
+    let mut buf = Vec::new();
+    for _ in 0..count {
+        match r.read_u32()? {
+            TOMBSTONE => f(None)?,
+            len => {
+                r.read_bytes(len, &mut buf)?;
+                let s = core::str::from_utf8(&buf).map_err(|_| SnapshotError::Corrupt)?;
+                f(Some(s))?;
+            }
+        }
+    }
+
+    let actual = r.hasher.finalize();
+    let mut expected = [0; 4];
+    r.inner.read_exact(&mut expected)?;
+    let expected = u32::from_le_bytes(expected);
+    if expected != actual {
+        return Err(SnapshotError::ChecksumMismatch { expected, actual });
+    }
+
+    Ok(Range {
+        start,
+        count,
+        index,
+        parts,
+        total,
+    })
+}
+
+/// Check that the snapshot is complete and not corrupted, without loading it.
+///
+/// Returns the number of entries in the snapshot. Partitions written by
+/// [`ParaCord::export_partitioned`] can also be verified.
+///
+/// ```
+/// use paracord::snapshot::{verify_snapshot, SnapshotError};
+/// use paracord::ParaCord;
+///
+/// let paracord = ParaCord::default();
+/// paracord.get_or_intern("foo");
+///
+/// let mut buf = vec![];
+/// paracord.write_snapshot(&mut buf).unwrap();
+/// assert_eq!(verify_snapshot(&buf[..]).unwrap(), 1);
+///
+/// let truncated = &buf[..buf.len() - 1];
+/// assert!(matches!(verify_snapshot(truncated), Err(SnapshotError::Truncated)));
+///
+/// buf[20] ^= 1;
+/// assert!(matches!(verify_snapshot(&buf[..]), Err(SnapshotError::ChecksumMismatch { .. })));
+/// ```
+pub fn verify_snapshot<R: Read>(r: R) -> Result<usize, SnapshotError> {
+    decode(r, |_| Ok(())).map(|range| range.count)
+}
+
+fn write_entries<'a, W: Write>(
+    w: W,
+    version: u32,
+    range: Range,
+    slots: impl Iterator<Item = Option<&'a [u8]>>,
+) -> io::Result<()> {
+    let Range {
+        start,
+        count,
+        index,
+        parts,
+        total,
+    } = range;
+    let mut w = ChecksumWriter {
+        inner: w,
+        hasher: Hasher::new(),
+    };
+
+    w.write_all(&MAGIC)?;
+    w.write_all(&version.to_le_bytes())?;
+    if version == PARTITION_VERSION {
+        w.write_all(&(start as u64).to_le_bytes())?;
+        w.write_all(&index.to_le_bytes())?;
+        w.write_all(&parts.to_le_bytes())?;
+        w.write_all(&(total as u64).to_le_bytes())?;
+    }
+    w.write_all(&(count as u64).to_le_bytes())?;
+
+    for slot in slots.take(count) {
+        match slot {
+            None => w.write_all(&TOMBSTONE.to_le_bytes())?,
+            Some(s) => {
+                // `InternedPtr` guarantees the length fits in a u32.
+                let len = s.len() as u32;
+                if len == TOMBSTONE {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "string is too long to be included in a snapshot",
+                    ));
+                }
+                w.write_all(&len.to_le_bytes())?;
+                w.write_all(s)?;
+            }
+        }
+    }
+
+    let checksum = w.hasher.finalize();
+    w.inner.write_all(&checksum.to_le_bytes())
+}
+
+/// A task that writes one partition of a [`ParaCord`] snapshot.
+///
+/// Created by [`ParaCord::export_partitioned`]. Tasks can be run in parallel, eg on separate threads.
+pub struct PartitionWriterTask<'a, S> {
+    paracord: &'a ParaCord<S>,
+    range: Range,
+}
+
+impl<S> std::fmt::Debug for PartitionWriterTask<'_, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PartitionWriterTask")
+            .field("start", &self.range.start)
+            .field("count", &self.range.count)
+            .field("index", &self.range.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> PartitionWriterTask<'_, S> {
+    /// The number of entries in this partition.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.range.count
+    }
+
+    /// Whether this partition has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.range.count == 0
+    }
+
+    /// Write this partition.
+    ///
+    /// The writer is not buffered, so consider wrapping it in a [`BufWriter`](std::io::BufWriter).
+    pub fn write<W: Write>(self, w: W) -> io::Result<()> {
+        // the slots were all allocated when the task was created.
+        let slots = self.paracord.inner.slots(self.range.start);
+        write_entries(w, PARTITION_VERSION, self.range, slots)
+    }
+}
+
+/// The decoded entries of one partition.
+struct Partition {
+    range: Range,
+    bytes: Vec<u8>,
+    lens: Vec<u32>,
+}
+
+fn decode_partition<R: Read>(r: R) -> Result<Partition, SnapshotError> {
+    let mut bytes = Vec::new();
+    let mut lens = Vec::new();
+    let range = decode(r, |s| {
+        match s {
+            Some(s) => {
+                bytes.extend_from_slice(s.as_bytes());
+                lens.push(s.len() as u32);
+            }
+            None => lens.push(TOMBSTONE),
+        }
+        Ok(())
+    })?;
+    Ok(Partition { range, bytes, lens })
+}
+
+impl<S> ParaCord<S> {
+    /// Write a snapshot of this [`ParaCord`] instance. See the [`snapshot`](crate::snapshot)
+    /// module for details on the format.
+    ///
+    /// Strings interned concurrently with the snapshot might not be included.
+    ///
+    /// The writer is not buffered, so consider wrapping it in a [`BufWriter`](std::io::BufWriter).
+    pub fn write_snapshot<W: Write>(&self, w: W) -> io::Result<()> {
+        let count = self.inner.slots(0).count();
+        let range = Range {
+            start: 0,
+            count,
+            index: 0,
+            parts: 1,
+            total: count,
+        };
+        write_entries(w, VERSION, range, self.inner.slots(0))
+    }
+
+    /// Split a snapshot of this [`ParaCord`] instance into `n` partitions of roughly equal size,
+    /// which can be written in parallel. Load them with [`ParaCord::read_partitioned`].
+    ///
+    /// Strings interned concurrently with the snapshot might not be included.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is zero, or more than `u32::MAX`.
+    pub fn export_partitioned(&self, n: usize) -> Vec<PartitionWriterTask<'_, S>> {
+        assert!(n > 0, "must export at least one partition");
+        let parts = u32::try_from(n).expect("too many partitions");
+
+        let total = self.inner.slots(0).count();
+        let per = total / n;
+        let extra = total % n;
+
+        let mut start = 0;
+        (0..parts)
+            .map(|index| {
+                let count = per + ((index as usize) < extra) as usize;
+                let task = PartitionWriterTask {
+                    paracord: self,
+                    range: Range {
+                        start,
+                        count,
+                        index,
+                        parts,
+                        total,
+                    },
+                };
+                start += count;
+                task
+            })
+            .collect()
+    }
+}
+
+impl<S: BuildHasher + Default> ParaCord<S> {
+    /// Load a [`ParaCord`] instance from a snapshot written by [`ParaCord::write_snapshot`].
+    ///
+    /// Every key has the same value as it did in the instance the snapshot was taken from.
+    ///
+    /// The reader is not buffered, so consider wrapping it in a [`BufReader`](std::io::BufReader).
+    pub fn read_snapshot<R: Read>(r: R) -> Result<Self, SnapshotError> {
+        let mut this = Self::with_hasher(S::default());
+        let mut next = 0;
+        let range = decode(r, |s| {
+            let key = match s {
+                Some(s) => this.inner.push_mut(s.as_bytes()),
+                None => this.inner.push_tombstone_mut(),
+            };
+            if key != Key::from_index(next) {
+                return Err(SnapshotError::Corrupt);
+            }
+            next += 1;
+            Ok(())
+        })?;
+        // a partition is only a complete snapshot if it is the only one.
+        if range.start != 0 || range.count != range.total {
+            return Err(SnapshotError::Corrupt);
+        }
+
+        Ok(this)
+    }
+
+    /// Load a [`ParaCord`] instance from every partition written by [`ParaCord::export_partitioned`].
+    ///
+    /// Each partition is read and verified on its own thread. The partitions may be given in any order.
+    ///
+    /// Returns [`SnapshotError::Corrupt`] if any partition is missing or repeated, or if the
+    /// partitions came from different exports.
+    ///
+    /// The readers are not buffered, so consider wrapping them in a [`BufReader`](std::io::BufReader).
+    pub fn read_partitioned<R: Read + Send>(
+        readers: impl IntoIterator<Item = R>,
+    ) -> Result<Self, SnapshotError> {
+        let mut partitions = std::thread::scope(|s| {
+            let handles: Vec<_> = readers
+                .into_iter()
+                .map(|r| s.spawn(move || decode_partition(r)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+        partitions.sort_unstable_by_key(|p| p.range.index);
+
+        // every partition records how many there are, and how many entries they hold together.
+        let Some(first) = partitions.first() else {
+            return Ok(Self::with_hasher(S::default()));
+        };
+        let (parts, total) = (first.range.parts, first.range.total);
+        if partitions.len() != parts as usize {
+            return Err(SnapshotError::Corrupt);
+        }
+
+        let mut this = Self::with_hasher(S::default());
+        let mut next = 0;
+        for (index, p) in (0..).zip(partitions) {
+            if p.range.index != index
+                || p.range.parts != parts
+                || p.range.total != total
+                || p.range.start != next
+            {
+                return Err(SnapshotError::Corrupt);
+            }
+
+            let mut bytes = &p.bytes[..];
+            for len in p.lens {
+                let key = if len == TOMBSTONE {
+                    this.inner.push_tombstone_mut()
+                } else {
+                    let (s, rest) = bytes.split_at(len as usize);
+                    bytes = rest;
+                    this.inner.push_mut(s)
+                };
+                if key != Key::from_index(next) {
+                    return Err(SnapshotError::Corrupt);
+                }
+                next += 1;
+            }
+        }
+        if next != total {
+            return Err(SnapshotError::Corrupt);
+        }
+
+        Ok(this)
+    }
+}
+
+/// An interner layered on top of a read-only snapshot.
+///
+/// The snapshot is borrowed, not copied, so it can be memory mapped from a file. Strings found in the
+/// snapshot resolve to the keys they had when the snapshot was taken, while new strings are interned
+/// into an in-memory delta, with keys that continue after the snapshot's range.
+/// This allows a shared dictionary to be shipped as a file and extended locally.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::snapshot::LayeredParaCord;
+/// use paracord::ParaCord;
+///
+/// let paracord = ParaCord::default();
+/// let foo = paracord.get_or_intern("foo");
+///
+/// // could be a memory mapped file
+/// let mut buf = vec![];
+/// paracord.write_snapshot(&mut buf).unwrap();
+///
+/// let layered: LayeredParaCord = LayeredParaCord::open(&buf).unwrap();
+/// assert_eq!(layered.get_or_intern("foo"), foo);
+///
+/// let bar = layered.get_or_intern("bar");
+/// assert_eq!(layered.resolve(bar), "bar");
+/// assert_eq!(layered.len(), 2);
+/// ```
+pub struct LayeredParaCord<'a, S = foldhash::fast::RandomState> {
+    base: Vec<Option<&'a str>>,
+    base_len: usize,
+    /// Indices into `base`.
+    index: HashTable<u32>,
+    delta: ParaCord<S>,
+}
+
+impl<S> std::fmt::Debug for LayeredParaCord<'_, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, S: BuildHasher + Default> LayeredParaCord<'a, S> {
+    /// Open a snapshot written by [`ParaCord::write_snapshot`] as the base of a new interner.
+    ///
+    /// The snapshot is verified, and indexed without copying the strings.
+    pub fn open(bytes: &'a [u8]) -> Result<Self, SnapshotError> {
+        Self::open_with_hasher(bytes, S::default())
+    }
+}
+
+impl<'a, S: BuildHasher> LayeredParaCord<'a, S> {
+    /// Open a snapshot written by [`ParaCord::write_snapshot`] as the base of a new interner,
+    /// with the given hasher state.
+    pub fn open_with_hasher(bytes: &'a [u8], hasher: S) -> Result<Self, SnapshotError> {
+        let base = decode_borrowed(bytes)?;
+        if u32::try_from(base.len()).map_or(true, |len| len == u32::MAX) {
+            return Err(SnapshotError::Corrupt);
+        }
+
+        let mut index = HashTable::with_capacity(base.len());
+        let mut base_len = 0;
+        for (i, s) in base.iter().enumerate() {
+            let Some(s) = s else { continue };
+            let hash = hasher.hash_one(s);
+            match index.entry(
+                hash,
+                |&j| base[j as usize] == Some(*s),
+                |&j| hasher.hash_one(base[j as usize].unwrap_or_default()),
+            ) {
+                hashbrown::hash_table::Entry::Occupied(_) => return Err(SnapshotError::Corrupt),
+                hashbrown::hash_table::Entry::Vacant(entry) => entry.insert(i as u32),
+            };
+            base_len += 1;
+        }
+
+        Ok(Self {
+            base,
+            base_len,
+            index,
+            delta: ParaCord::with_hasher(hasher),
+        })
+    }
+
+    fn get_base(&self, s: &str) -> Option<Key> {
+        let hash = self.delta.hasher().hash_one(s);
+        let i = self
+            .index
+            .find(hash, |&i| self.base[i as usize] == Some(s))?;
+        Some(Key::from_index(*i as usize))
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<Key> {
+        self.get_base(s)
+            .or_else(|| self.delta.get(s).map(|k| self.delta_to_key(k)))
+    }
+
+    /// Try and get the [`Key`] associated with the given string.
+    /// Allocates a new key in the delta if not found.
+    pub fn get_or_intern(&self, s: &str) -> Key {
+        match self.get_base(s) {
+            Some(key) => key,
+            None => self.delta_to_key(self.delta.get_or_intern(s)),
+        }
+    }
+}
+
+impl<'a, S> LayeredParaCord<'a, S> {
+    fn delta_to_key(&self, key: Key) -> Key {
+        Key::from_index(self.base.len() + key.into_repr() as usize)
+    }
+
+    /// Try and resolve the string associated with this [`Key`].
+    ///
+    /// Returns [`None`] if the key was not allocated by the snapshot or this instance.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<&str> {
+        let i = key.into_repr() as usize;
+        match i.checked_sub(self.base.len()) {
+            None => self.base[i],
+            Some(i) => self.delta.try_resolve(Key::from_index(i)),
+        }
+    }
+
+    /// Resolve the string associated with this [`Key`].
+    ///
+    /// # Panics
+    ///
+    /// If the key was not allocated by the snapshot or this instance.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> &str {
+        self.try_resolve(key)
+            .expect("key should be allocated by this paracord instance")
+    }
+
+    /// Determine how many strings are interned, including the snapshot's.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.base_len + self.delta.len()
+    }
+
+    /// Determine if no strings are interned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determine how many strings have been interned on top of the snapshot.
+    #[must_use]
+    pub fn delta_len(&self) -> usize {
+        self.delta.len()
+    }
+
+    /// Get an iterator over every ([`Key`], `&str`) pair, starting with the snapshot's.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &str)> {
+        let base = self
+            .base
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| Some((Key::from_index(i), (*s)?)));
+        let delta = self.delta.iter().map(|(k, s)| (self.delta_to_key(k), s));
+        base.chain(delta)
+    }
+}
+
+/// Decode a full snapshot, borrowing the strings from `bytes`.
+fn decode_borrowed(bytes: &[u8]) -> Result<Vec<Option<&str>>, SnapshotError> {
+    fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8], SnapshotError> {
+        if bytes.len() < n {
+            return Err(SnapshotError::Truncated);
+        }
+        let (head, rest) = bytes.split_at(n);
+        *bytes = rest;
+        Ok(head)
+    }
+    fn take_u32(bytes: &mut &[u8]) -> Result<u32, SnapshotError> {
+        Ok(u32::from_le_bytes(take(bytes, 4)?.try_into().unwrap()))
+    }
+    fn take_u64(bytes: &mut &[u8]) -> Result<u64, SnapshotError> {
+        Ok(u64::from_le_bytes(take(bytes, 8)?.try_into().unwrap()))
+    }
+
+    let mut rest = bytes;
+    if take(&mut rest, 8)? != MAGIC {
+        return Err(SnapshotError::BadMagic);
+    }
+    match take_u32(&mut rest)? {
+        VERSION => {}
+        PARTITION_VERSION => return Err(SnapshotError::Corrupt),
+        version => return Err(SnapshotError::UnsupportedVersion(version)),
+    }
+    let count = take_u64(&mut rest)?;
+
+    // don't trust the count for the allocation size.
+    let mut entries = Vec::with_capacity((count as usize).min(rest.len() / 4));
+    for _ in 0..count {
+        match take_u32(&mut rest)? {
+            TOMBSTONE => entries.push(None),
+            len => {
+                let s = take(&mut rest, len as usize)?;
+                let s = core::str::from_utf8(s).map_err(|_| SnapshotError::Corrupt)?;
+                entries.push(Some(s));
+            }
+        }
+    }
+
+    let actual = crc32fast::hash(&bytes[..bytes.len() - rest.len()]);
+    let expected = take_u32(&mut rest)?;
+    if expected != actual {
+        return Err(SnapshotError::ChecksumMismatch { expected, actual });
+    }
+
+    Ok(entries)
+}
@@ -0,0 +1,58 @@
+use std::borrow::Cow;
+
+/// A string that can be interned with [`ParaCord::intern`](crate::ParaCord::intern).
+///
+/// Owned strings are adopted by the interner if they are new, so they are not copied.
+/// Borrowed strings are copied into the interner's arena.
+pub trait InternSource: AsRef<str> + Sized {
+    /// Take ownership of the string, so it can be stored without copying.
+    ///
+    /// Borrowed sources return themselves, and are copied instead.
+    fn try_into_owned(self) -> Result<Box<str>, Self>;
+}
+
+impl InternSource for &str {
+    #[inline]
+    fn try_into_owned(self) -> Result<Box<str>, Self> {
+        Err(self)
+    }
+}
+
+impl InternSource for &String {
+    #[inline]
+    fn try_into_owned(self) -> Result<Box<str>, Self> {
+        Err(self)
+    }
+}
+
+impl InternSource for &Box<str> {
+    #[inline]
+    fn try_into_owned(self) -> Result<Box<str>, Self> {
+        Err(self)
+    }
+}
+
+impl InternSource for String {
+    /// Strings with spare capacity are shrunk to fit.
+    #[inline]
+    fn try_into_owned(self) -> Result<Box<str>, Self> {
+        Ok(self.into_boxed_str())
+    }
+}
+
+impl InternSource for Box<str> {
+    #[inline]
+    fn try_into_owned(self) -> Result<Box<str>, Self> {
+        Ok(self)
+    }
+}
+
+impl InternSource for Cow<'_, str> {
+    #[inline]
+    fn try_into_owned(self) -> Result<Box<str>, Self> {
+        match self {
+            Cow::Owned(s) => Ok(s.into_boxed_str()),
+            Cow::Borrowed(s) => Err(Cow::Borrowed(s)),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! Interning strings as a group, which is only published to the instance on commit.
+//!
+//! See [`ParaCord::stage`].
+
+use core::fmt;
+use std::hash::BuildHasher;
+
+use hashbrown::HashTable;
+
+use crate::{Key, ParaCord};
+
+/// A group of interned strings that are not visible to the [`ParaCord`] until they are
+/// committed. Created by [`ParaCord::stage`].
+///
+/// Strings that are already in the instance use the existing key. New strings are held
+/// by the stage, and are discarded if the stage is dropped without calling
+/// [`StagedIntern::commit`].
+pub struct StagedIntern<'a, S> {
+    paracord: &'a ParaCord<S>,
+    strings: Vec<Box<str>>,
+    /// Indices into `strings`, hashed with the instance's hasher.
+    table: HashTable<usize>,
+}
+
+/// A key returned by [`StagedIntern`], for either an existing string or a staged string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StagedKey(Repr);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Repr {
+    Existing(Key),
+    Staged(usize),
+}
+
+impl StagedKey {
+    /// Get the key, if the string was already in the instance when it was staged.
+    #[inline]
+    #[must_use]
+    pub fn existing(self) -> Option<Key> {
+        match self.0 {
+            Repr::Existing(key) => Some(key),
+            Repr::Staged(_) => None,
+        }
+    }
+}
+
+/// The keys of a committed [`StagedIntern`]. Returned by [`StagedIntern::commit`].
+pub struct Committed {
+    keys: Vec<Key>,
+}
+
+impl Committed {
+    /// Get the key for a [`StagedKey`] from the committed stage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the staged key came from a different stage.
+    #[track_caller]
+    #[must_use]
+    pub fn key(&self, key: StagedKey) -> Key {
+        match key.0 {
+            Repr::Existing(key) => key,
+            Repr::Staged(i) => *self
+                .keys
+                .get(i)
+                .expect("staged key should be allocated by this stage"),
+        }
+    }
+
+    /// Get the keys of the strings that were staged, in the order they were staged.
+    #[must_use]
+    pub fn keys(&self) -> &[Key] {
+        &self.keys
+    }
+}
+
+impl fmt::Debug for Committed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.keys).finish()
+    }
+}
+
+impl<S> fmt::Debug for StagedIntern<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.strings).finish()
+    }
+}
+
+impl<S> ParaCord<S> {
+    /// Start staging strings, which are only added to this instance when committed.
+    ///
+    /// This lets a workflow that is coupled to a database transaction intern strings as it goes,
+    /// without leaking entries for rolled back transactions into a shared interner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use paracord::ParaCord;
+    /// let paracord = ParaCord::default();
+    /// let foo = paracord.get_or_intern("foo");
+    ///
+    /// let mut stage = paracord.stage();
+    /// let staged_foo = stage.get_or_intern("foo");
+    /// let staged_bar = stage.get_or_intern("bar");
+    /// assert_eq!(stage.resolve(staged_bar), "bar");
+    /// assert_eq!(paracord.get("bar"), None);
+    ///
+    /// let committed = stage.commit();
+    /// assert_eq!(committed.key(staged_foo), foo);
+    /// assert_eq!(paracord.resolve(committed.key(staged_bar)), "bar");
+    ///
+    /// let mut stage = paracord.stage();
+    /// stage.get_or_intern("baz");
+    /// drop(stage);
+    /// assert_eq!(paracord.get("baz"), None);
+    /// ```
+    pub fn stage(&self) -> StagedIntern<'_, S> {
+        StagedIntern {
+            paracord: self,
+            strings: Vec::new(),
+            table: HashTable::new(),
+        }
+    }
+}
+
+impl<'a, S> StagedIntern<'a, S> {
+    /// Get the instance the strings will be committed to.
+    #[must_use]
+    pub fn paracord(&self) -> &'a ParaCord<S> {
+        self.paracord
+    }
+
+    /// Determine how many new strings are staged.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Determine if no new strings are staged.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Resolve the string associated with this key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the staged key came from a different stage, or was not allocated by the instance.
+    #[track_caller]
+    #[must_use]
+    pub fn resolve(&self, key: StagedKey) -> &str {
+        match key.0 {
+            Repr::Existing(key) => self.paracord.resolve(key),
+            Repr::Staged(i) => self
+                .strings
+                .get(i)
+                .expect("staged key should be allocated by this stage"),
+        }
+    }
+
+    /// Get an iterator over the new strings that are staged, in the order they were staged.
+    pub fn iter(&self) -> impl Iterator<Item = (StagedKey, &str)> {
+        self.strings
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (StagedKey(Repr::Staged(i)), &**s))
+    }
+
+    /// Discard the staged strings. This is the same as dropping the stage.
+    pub fn rollback(self) {}
+}
+
+impl<S: BuildHasher> StagedIntern<'_, S> {
+    /// Try and get the [`StagedKey`] associated with the given string, from the instance
+    /// or from this stage. Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<StagedKey> {
+        if let Some(key) = self.paracord.get(s) {
+            return Some(StagedKey(Repr::Existing(key)));
+        }
+
+        let hash = self.paracord.hasher().hash_one(s.as_bytes());
+        let i = self.table.find(hash, |&i| *self.strings[i] == *s)?;
+        Some(StagedKey(Repr::Staged(*i)))
+    }
+
+    /// Try and get the [`StagedKey`] associated with the given string.
+    /// Stages the string if it's not in the instance or this stage.
+    pub fn get_or_intern(&mut self, s: &str) -> StagedKey {
+        if let Some(key) = self.paracord.get(s) {
+            return StagedKey(Repr::Existing(key));
+        }
+
+        let Self {
+            paracord,
+            strings,
+            table,
+        } = self;
+        let hasher = paracord.hasher();
+        let hash = hasher.hash_one(s.as_bytes());
+        let entry = table.entry(
+            hash,
+            |&i| *strings[i] == *s,
+            |&i| hasher.hash_one(strings[i].as_bytes()),
+        );
+        let i = *entry
+            .or_insert_with(|| {
+                strings.push(s.into());
+                strings.len() - 1
+            })
+            .get();
+        StagedKey(Repr::Staged(i))
+    }
+
+    /// Intern every staged string into the instance, making them visible to other users.
+    ///
+    /// Strings that were interned by someone else in the meantime use the existing key.
+    pub fn commit(self) -> Committed {
+        let keys = self
+            .strings
+            .into_iter()
+            .map(|s| self.paracord.intern(s))
+            .collect();
+        Committed { keys }
+    }
+}
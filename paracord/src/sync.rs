@@ -0,0 +1,289 @@
+//! Keeping [`ParaCord`] instances converged across processes.
+//!
+//! A follower sends a [`DictDigest`] of its dictionary to the leader, which replies with a
+//! [`Delta`] of every entry allocated since. Applying the delta gives the follower the same keys
+//! as the leader, without resending the full dictionary.
+//!
+//! ```
+//! use paracord::ParaCord;
+//!
+//! let leader = ParaCord::default();
+//! let mut follower = ParaCord::default();
+//!
+//! let foo = leader.get_or_intern("foo");
+//!
+//! // follower -> leader
+//! let mut buf = vec![];
+//! follower.digest().encode(&mut buf);
+//! let digest = paracord::sync::DictDigest::decode(&mut &buf[..]).unwrap();
+//!
+//! // leader -> follower
+//! let mut buf = vec![];
+//! leader.delta_since(&digest).unwrap().encode(&mut buf);
+//! let delta = paracord::sync::Delta::decode(&mut &buf[..]).unwrap();
+//!
+//! follower.apply_delta(&delta).unwrap();
+//! assert_eq!(follower.get("foo"), Some(foo));
+//! assert_eq!(follower.digest(), leader.digest());
+//! ```
+//!
+//! # Format
+//!
+//! Integers are written as varints, in the same format as [`wire`](crate::wire).
+//!
+//! A digest is the number of keys followed by the hash as 8 little endian bytes.
+//! A delta is the digest it applies to, the number of entries, and then every entry:
+//! `0` for a removed key, otherwise the length of the string plus one followed by the string.
+
+use std::collections::HashSet;
+use std::hash::BuildHasher;
+
+use crate::wire::{read_u8, read_varint, write_varint, WireError};
+use crate::ParaCord;
+
+/// Error returned when two instances cannot be synced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SyncError {
+    /// The instances do not share the keys described by the digest.
+    Diverged,
+    /// The delta contains a string that is already interned.
+    DuplicateString,
+}
+
+impl std::fmt::Display for SyncError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncError::Diverged => f.write_str("paracord instances have diverged"),
+            SyncError::DuplicateString => {
+                f.write_str("delta contains a string that is already interned")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SyncError {}
+
+/// A summary of every key allocated in an instance: the number of keys, and a hash of every
+/// string in key order.
+///
+/// The hash is stable across processes and platforms, so digests can be compared between
+/// instances that use different hashers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DictDigest {
+    len: u64,
+    hash: u64,
+}
+
+impl Default for DictDigest {
+    fn default() -> Self {
+        Self {
+            len: 0,
+            hash: FNV_OFFSET,
+        }
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl DictDigest {
+    /// The number of keys, including removed keys.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Determine if no keys have been allocated.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The rolling hash of every string.
+    #[must_use]
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.hash = (self.hash ^ b as u64).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn push(&mut self, s: Option<&[u8]>) {
+        match s {
+            Some(s) => {
+                self.write(&[1]);
+                self.write(&(s.len() as u64).to_le_bytes());
+                self.write(s);
+            }
+            None => self.write(&[0]),
+        }
+        self.len += 1;
+    }
+
+    /// Write the digest to the buffer.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(out, self.len);
+        out.extend_from_slice(&self.hash.to_le_bytes());
+    }
+
+    /// Read a digest from the front of the input, advancing it.
+    pub fn decode(input: &mut &[u8]) -> Result<Self, WireError> {
+        let len = read_varint(input)?;
+        let mut hash = [0; 8];
+        for b in &mut hash {
+            *b = read_u8(input)?;
+        }
+        Ok(Self {
+            len,
+            hash: u64::from_le_bytes(hash),
+        })
+    }
+}
+
+/// The entries allocated in an instance since a [`DictDigest`].
+///
+/// Created with [`ParaCord::delta_since`], and applied with [`ParaCord::apply_delta`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Delta {
+    base: DictDigest,
+    entries: Vec<Option<String>>,
+}
+
+impl Delta {
+    /// The digest of the instance this delta applies to.
+    #[must_use]
+    pub fn base(&self) -> DictDigest {
+        self.base
+    }
+
+    /// The number of entries in the delta, including removed keys.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Determine if the delta has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Write the delta to the buffer.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        self.base.encode(out);
+        write_varint(out, self.entries.len() as u64);
+        for s in &self.entries {
+            match s {
+                Some(s) => {
+                    write_varint(out, s.len() as u64 + 1);
+                    out.extend_from_slice(s.as_bytes());
+                }
+                None => write_varint(out, 0),
+            }
+        }
+    }
+
+    /// Read a delta from the front of the input, advancing it.
+    pub fn decode(input: &mut &[u8]) -> Result<Self, WireError> {
+        let base = DictDigest::decode(input)?;
+        let count = read_varint(input)?;
+        // every entry takes at least one byte.
+        if count > input.len() as u64 {
+            return Err(WireError::Truncated);
+        }
+
+        let mut entries = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let entry = match read_varint(input)? {
+                0 => None,
+                len => {
+                    let len = usize::try_from(len - 1).map_err(|_| WireError::Corrupt)?;
+                    if len > input.len() {
+                        return Err(WireError::Truncated);
+                    }
+                    let (s, rest) = input.split_at(len);
+                    *input = rest;
+                    let s = std::str::from_utf8(s).map_err(|_| WireError::Corrupt)?;
+                    Some(s.to_owned())
+                }
+            };
+            entries.push(entry);
+        }
+        Ok(Self { base, entries })
+    }
+}
+
+impl<S> ParaCord<S> {
+    /// Get the [`DictDigest`] of every key allocated in this instance.
+    ///
+    /// This hashes every string, so it takes time proportional to the size of the dictionary.
+    /// Keys that are still being allocated concurrently might not be included.
+    #[must_use]
+    pub fn digest(&self) -> DictDigest {
+        let mut digest = DictDigest::default();
+        self.inner.slots(0).for_each(|s| digest.push(s));
+        digest
+    }
+
+    /// Get the [`Delta`] of every key allocated since the given digest was taken.
+    ///
+    /// The digest is usually taken from another instance that follows this one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SyncError::Diverged`] if the keys in the digest do not match the first keys
+    /// of this instance.
+    pub fn delta_since(&self, digest: &DictDigest) -> Result<Delta, SyncError> {
+        let mut slots = self.inner.slots(0);
+
+        let mut base = DictDigest::default();
+        for s in slots.by_ref().take(digest.len as usize) {
+            base.push(s);
+        }
+        if base != *digest {
+            return Err(SyncError::Diverged);
+        }
+
+        let entries = slots
+            // Safety: we insert only strings, so it's valid utf8
+            .map(|s| s.map(|b| unsafe { core::str::from_utf8_unchecked(b) }.to_owned()))
+            .collect();
+        Ok(Delta { base, entries })
+    }
+}
+
+impl<S: BuildHasher> ParaCord<S> {
+    /// Allocate every entry in the delta, so that this instance has the same keys as the instance
+    /// the delta was created from.
+    ///
+    /// Nothing is allocated if an error is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SyncError::Diverged`] if the delta was not created from this instance's digest,
+    /// or [`SyncError::DuplicateString`] if any string in the delta is already interned.
+    pub fn apply_delta(&mut self, delta: &Delta) -> Result<(), SyncError> {
+        if self.digest() != delta.base {
+            return Err(SyncError::Diverged);
+        }
+
+        let mut seen = HashSet::new();
+        for s in delta.entries.iter().flatten() {
+            if !seen.insert(s.as_str()) || self.get(s).is_some() {
+                return Err(SyncError::DuplicateString);
+            }
+        }
+
+        for s in &delta.entries {
+            match s {
+                Some(s) => self.inner.push_mut(s.as_bytes()),
+                None => self.inner.push_tombstone_mut(),
+            };
+        }
+        Ok(())
+    }
+}
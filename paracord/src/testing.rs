@@ -0,0 +1,229 @@
+//! Utilities for testing code that embeds a [`ParaCord`].
+//!
+//! ```
+//! use paracord::testing::{stress, StressConfig};
+//!
+//! let report = stress(StressConfig::new().threads(4).ops_per_thread(1000).rounds(2));
+//! assert_eq!(report.clears, 2);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use crate::{Key, ParaCord};
+
+/// Configuration for [`stress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StressConfig {
+    threads: usize,
+    ops_per_thread: usize,
+    distinct: usize,
+    rounds: usize,
+    seed: u64,
+}
+
+impl Default for StressConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StressConfig {
+    /// 4 threads doing 10000 operations each, over 1000 distinct strings, for 4 rounds.
+    pub const fn new() -> Self {
+        Self {
+            threads: 4,
+            ops_per_thread: 10000,
+            distinct: 1000,
+            rounds: 4,
+            seed: 0x5eed,
+        }
+    }
+
+    /// How many threads operate on the interner concurrently.
+    pub const fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// How many operations each thread performs per round.
+    pub const fn ops_per_thread(mut self, ops: usize) -> Self {
+        self.ops_per_thread = ops;
+        self
+    }
+
+    /// How many distinct strings the threads choose from. Fewer strings means more contention.
+    pub const fn distinct(mut self, distinct: usize) -> Self {
+        self.distinct = distinct;
+        self
+    }
+
+    /// How many rounds to run. The interner is cleared before every round.
+    pub const fn rounds(mut self, rounds: usize) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    /// The seed for the random choice of operations, so failures can be reproduced.
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// A summary of the operations performed by [`stress`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StressReport {
+    /// How many calls to [`ParaCord::get_or_intern`] were made.
+    pub interned: usize,
+    /// How many calls to [`ParaCord::get`] and [`ParaCord::resolve`] were made.
+    pub lookups: usize,
+    /// How many full iterations of the interner were made.
+    pub iterations: usize,
+    /// How many times the interner was cleared.
+    pub clears: usize,
+}
+
+impl StressReport {
+    fn merge(&mut self, other: StressReport) {
+        self.interned += other.interned;
+        self.lookups += other.lookups;
+        self.iterations += other.iterations;
+        self.clears += other.clears;
+    }
+}
+
+/// Run [`stress_with`] on a new [`ParaCord`] instance.
+pub fn stress(config: StressConfig) -> StressReport {
+    stress_with(&mut ParaCord::default(), config)
+}
+
+/// Run random interleavings of interning, lookups and iteration on the interner from many threads,
+/// clearing it between rounds, and check that the interner stays consistent.
+///
+/// Checked invariants include:
+/// * every thread gets the same key for the same string,
+/// * every key resolves to the string it was interned with,
+/// * a string is found by [`ParaCord::get`] once it has been interned,
+/// * iteration only yields consistent pairs, and yields every string once all threads are done.
+///
+/// # Panics
+///
+/// If any invariant does not hold.
+pub fn stress_with<S: BuildHasher + Sync>(
+    paracord: &mut ParaCord<S>,
+    config: StressConfig,
+) -> StressReport {
+    let distinct = config.distinct.max(1);
+    let mut report = StressReport::default();
+
+    for round in 0..config.rounds {
+        paracord.clear();
+        assert!(paracord.is_empty(), "interner should be empty after clear");
+        report.clears += 1;
+
+        let shared = &*paracord;
+        let results: Vec<(StressReport, HashMap<usize, Key>)> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..config.threads)
+                .map(|thread| {
+                    let seed = config.seed ^ ((round * config.threads + thread) as u64 + 1);
+                    s.spawn(move || worker(shared, config.ops_per_thread, distinct, seed))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect()
+        });
+
+        let mut keys = HashMap::new();
+        for (r, seen) in results {
+            report.merge(r);
+            for (n, key) in seen {
+                let prev = *keys.entry(n).or_insert(key);
+                assert_eq!(prev, key, "threads got different keys for {:?}", string(n));
+            }
+        }
+
+        assert_eq!(paracord.len(), keys.len(), "unexpected number of strings");
+        for (n, key) in keys {
+            assert_eq!(paracord.resolve(key), string(n));
+        }
+        assert_eq!(paracord.iter().count(), paracord.len());
+    }
+
+    report
+}
+
+/// Perform random operations, returning the keys this thread has seen.
+fn worker<S: BuildHasher>(
+    paracord: &ParaCord<S>,
+    ops: usize,
+    distinct: usize,
+    mut seed: u64,
+) -> (StressReport, HashMap<usize, Key>) {
+    let mut report = StressReport::default();
+    let mut seen = HashMap::new();
+
+    for _ in 0..ops {
+        // xorshift64
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        let n = (seed >> 8) as usize % distinct;
+        let s = string(n);
+
+        match seed % 16 {
+            0..=7 => {
+                report.interned += 1;
+                let key = paracord.get_or_intern(&s);
+                assert_eq!(
+                    paracord.resolve(key),
+                    s,
+                    "key does not resolve to its string"
+                );
+                let prev = *seen.entry(n).or_insert(key);
+                assert_eq!(prev, key, "got different keys for {s:?}");
+            }
+            8..=14 => {
+                report.lookups += 1;
+                let key = paracord.get(&s);
+                if let Some(&prev) = seen.get(&n) {
+                    assert_eq!(key, Some(prev), "interned string {s:?} was not found");
+                }
+                if let Some(key) = key {
+                    assert_eq!(
+                        paracord.resolve(key),
+                        s,
+                        "key does not resolve to its string"
+                    );
+                    seen.insert(n, key);
+                }
+            }
+            _ => {
+                report.iterations += 1;
+                let mut count = 0;
+                for (key, s) in paracord {
+                    assert_eq!(
+                        paracord.get(s),
+                        Some(key),
+                        "iterated pair is not consistent"
+                    );
+                    count += 1;
+                }
+                assert!(
+                    count >= seen.len(),
+                    "iteration missed strings seen by this thread"
+                );
+            }
+        }
+    }
+
+    (report, seen)
+}
+
+/// The `n`th string, with varying lengths.
+fn string(n: usize) -> String {
+    format!("{n}:{}", "x".repeat(n % 23))
+}
@@ -0,0 +1,217 @@
+//! Interning of structured values, rather than slices.
+//!
+//! See [`ParaCord`].
+
+use std::hash::BuildHasher;
+
+use crate::{buffer, slice, Key};
+
+/// A value that can be interned in a [`ParaCord`].
+///
+/// Values are identified by their canonical byte encoding: two values are the same
+/// if and only if they produce the same canonical bytes.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::value::InternValue;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl InternValue for Point {
+///     fn write_canonical(&self, out: &mut Vec<u8>) {
+///         out.extend_from_slice(&self.x.to_le_bytes());
+///         out.extend_from_slice(&self.y.to_le_bytes());
+///     }
+///
+///     fn from_canonical(bytes: &[u8]) -> Self {
+///         let (x, y) = bytes.split_at(4);
+///         Point {
+///             x: i32::from_le_bytes(x.try_into().unwrap()),
+///             y: i32::from_le_bytes(y.try_into().unwrap()),
+///         }
+///     }
+/// }
+///
+/// let paracord = paracord::value::ParaCord::default();
+/// let p = paracord.get_or_intern(&Point { x: 1, y: 2 });
+/// assert_eq!(paracord.get(&Point { x: 1, y: 2 }), Some(p));
+/// assert_eq!(paracord.resolve(p), Point { x: 1, y: 2 });
+/// ```
+pub trait InternValue: Sized {
+    /// Write the canonical bytes of this value to `out`.
+    fn write_canonical(&self, out: &mut Vec<u8>);
+
+    /// Reconstruct a value from the bytes written by [`InternValue::write_canonical`].
+    fn from_canonical(bytes: &[u8]) -> Self;
+}
+
+macro_rules! int_values {
+    ($($t:ty),*) => {
+        $(
+            impl InternValue for $t {
+                fn write_canonical(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn from_canonical(bytes: &[u8]) -> Self {
+                    <$t>::from_le_bytes(bytes.try_into().expect("canonical bytes should have the correct length"))
+                }
+            }
+        )*
+    };
+}
+
+int_values!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl InternValue for bool {
+    fn write_canonical(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn from_canonical(bytes: &[u8]) -> Self {
+        bytes == [1]
+    }
+}
+
+impl InternValue for char {
+    fn write_canonical(&self, out: &mut Vec<u8>) {
+        u32::from(*self).write_canonical(out);
+    }
+
+    fn from_canonical(bytes: &[u8]) -> Self {
+        char::from_u32(u32::from_canonical(bytes)).expect("canonical bytes should be a valid char")
+    }
+}
+
+impl InternValue for String {
+    fn write_canonical(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn from_canonical(bytes: &[u8]) -> Self {
+        String::from_utf8(bytes.to_vec()).expect("canonical bytes should be valid utf8")
+    }
+}
+
+impl InternValue for Vec<u8> {
+    fn write_canonical(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    fn from_canonical(bytes: &[u8]) -> Self {
+        bytes.to_vec()
+    }
+}
+
+/// An interner for structured values that implement [`InternValue`].
+///
+/// Only the canonical bytes of each value are stored, and [`ParaCord::resolve`] reconstructs
+/// the value from them.
+///
+/// # Examples
+///
+/// ```
+/// use paracord::value::ParaCord;
+///
+/// let paracord = ParaCord::default();
+///
+/// let a = paracord.get_or_intern(&1u64);
+/// let b = paracord.get_or_intern(&2u64);
+/// assert_ne!(a, b);
+/// assert_eq!(paracord.get_or_intern(&1u64), a);
+/// assert_eq!(paracord.resolve(b), 2);
+/// ```
+pub struct ParaCord<V, S = foldhash::fast::RandomState> {
+    bytes: slice::ParaCord<u8, S>,
+    _values: std::marker::PhantomData<fn(&V) -> V>,
+}
+
+impl<V: InternValue + std::fmt::Debug, S> std::fmt::Debug for ParaCord<V, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.bytes.iter().map(|(k, b)| (k, V::from_canonical(b))))
+            .finish()
+    }
+}
+
+impl<V> Default for ParaCord<V> {
+    fn default() -> Self {
+        Self::with_hasher(foldhash::fast::RandomState::default())
+    }
+}
+
+impl<V, S: BuildHasher> ParaCord<V, S> {
+    /// Create a new `ParaCord` instance with the given hasher state.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            bytes: slice::ParaCord::with_hasher(hasher),
+            _values: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<V: InternValue, S: BuildHasher> ParaCord<V, S> {
+    /// Try and get the [`Key`] associated with the given value.
+    /// Returns [`None`] if not found.
+    #[must_use]
+    pub fn get(&self, v: &V) -> Option<Key> {
+        let mut buf = buffer::take();
+        v.write_canonical(&mut buf);
+        let key = self.bytes.get(&buf);
+        buffer::give(buf);
+        key
+    }
+
+    /// Try and get the [`Key`] associated with the given value.
+    /// Allocates a new key if not found.
+    pub fn get_or_intern(&self, v: &V) -> Key {
+        let mut buf = buffer::take();
+        v.write_canonical(&mut buf);
+        let key = self.bytes.get_or_intern(&buf);
+        buffer::give(buf);
+        key
+    }
+}
+
+impl<V: InternValue, S> ParaCord<V, S> {
+    /// Try and resolve the value associated with this [`Key`].
+    ///
+    /// This can only return `None` if given a key that was allocated from
+    /// a different [`ParaCord`] instance, but it might return an arbitrary value as well.
+    #[must_use]
+    pub fn try_resolve(&self, key: Key) -> Option<V> {
+        self.bytes.try_resolve(key).map(V::from_canonical)
+    }
+
+    /// Resolve the value associated with this [`Key`].
+    ///
+    /// # Panics
+    /// This can panic if given a key that was allocated from
+    /// a different [`ParaCord`] instance, but it might return an arbitrary value
+    /// as well.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, key: Key) -> V {
+        self.try_resolve(key)
+            .expect("key should be allocated by this paracord instance")
+    }
+}
+
+impl<V, S> ParaCord<V, S> {
+    /// Determine how many values have been allocated
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Determine if no values have been allocated
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
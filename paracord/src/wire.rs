@@ -0,0 +1,201 @@
+//! Compact encodings of key sets, for sending keys between services that share a dictionary.
+//!
+//! Keys are encoded as a set: they are sorted and deduplicated, and each key is stored as the gap
+//! from the previous one. Gaps are either written as varints, or bitpacked at a fixed width,
+//! whichever is smaller. Dense key sets typically take one byte or less per key, instead of 4.
+//!
+//! ```
+//! use paracord::ParaCord;
+//!
+//! let paracord = ParaCord::default();
+//! let keys: Vec<_> = (0..1000).map(|i| paracord.get_or_intern(&i.to_string())).collect();
+//!
+//! let mut buf = vec![];
+//! paracord::wire::encode_keys(&keys, &mut buf);
+//! assert!(buf.len() < 200);
+//!
+//! let decoded = paracord::wire::decode_keys(&mut &buf[..]).unwrap();
+//! assert_eq!(decoded, keys);
+//! ```
+//!
+//! # Format
+//!
+//! | field    | type     | notes                                                             |
+//! |----------|----------|-------------------------------------------------------------------|
+//! | count    | varint   | number of keys                                                    |
+//! | encoding | `u8`     | `0` for varint gaps, `1` for bitpacked gaps                       |
+//! | width    | `u8`     | only for bitpacked gaps: the number of bits per gap, at most 32   |
+//! | gaps     |          | `count` gaps, either as varints or packed least significant bit first |
+//!
+//! Varints are LEB128: 7 bits per byte, least significant first, with the high bit set on every byte
+//! but the last. The first gap is the index of the first key, and every other gap is the difference
+//! between a key and the previous key, minus one.
+
+use crate::Key;
+
+const VARINT: u8 = 0;
+const BITPACKED: u8 = 1;
+
+/// Error returned when decoding keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WireError {
+    /// The input ended before all keys were decoded.
+    Truncated,
+    /// The input uses an unknown encoding.
+    UnknownEncoding(u8),
+    /// The input contains invalid data, eg a key that is out of range.
+    Corrupt,
+}
+
+impl std::fmt::Display for WireError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WireError::Truncated => f.write_str("encoded keys are truncated"),
+            WireError::UnknownEncoding(e) => write!(f, "unknown key encoding {e}"),
+            WireError::Corrupt => f.write_str("encoded keys contain invalid data"),
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+/// Encode the set of keys, appending the encoded bytes to `out`.
+///
+/// The keys are sorted and deduplicated first, so [`decode_keys`] returns them in ascending order.
+pub fn encode_keys(keys: &[Key], out: &mut Vec<u8>) {
+    let mut keys: Vec<u32> = keys.iter().map(|k| k.into_repr()).collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    let gaps = keys.iter().scan(None, |prev, &k| {
+        let gap = match *prev {
+            Some(prev) => k - prev - 1,
+            None => k,
+        };
+        *prev = Some(k);
+        Some(gap)
+    });
+
+    let width = gaps
+        .clone()
+        .fold(0, |w, gap| w.max(32 - gap.leading_zeros()));
+    let varint_len: usize = gaps.clone().map(varint_len).sum();
+    let bitpacked_len = 1 + (keys.len() * width as usize).div_ceil(8);
+
+    write_varint(out, keys.len() as u64);
+    if bitpacked_len < varint_len {
+        out.extend_from_slice(&[BITPACKED, width as u8]);
+
+        let mut acc = 0u64;
+        let mut bits = 0;
+        for gap in gaps {
+            acc |= (gap as u64) << bits;
+            bits += width;
+            while bits >= 8 {
+                out.push(acc as u8);
+                acc >>= 8;
+                bits -= 8;
+            }
+        }
+        if bits > 0 {
+            out.push(acc as u8);
+        }
+    } else {
+        out.push(VARINT);
+        for gap in gaps {
+            write_varint(out, gap as u64);
+        }
+    }
+}
+
+/// Decode a set of keys written by [`encode_keys`], advancing `input` past the encoded bytes.
+pub fn decode_keys(input: &mut &[u8]) -> Result<Vec<Key>, WireError> {
+    let count = read_varint(input)?;
+    // every key is distinct, so there are fewer keys than key indices.
+    if count >= u64::from(u32::MAX) {
+        return Err(WireError::Corrupt);
+    }
+    let count = count as usize;
+    let encoding = read_u8(input)?;
+
+    // don't trust the count for the allocation size.
+    let mut keys = Vec::with_capacity(count.min(input.len() * 8));
+    let mut next = 0u64;
+    let mut push = |gap: u64| {
+        let k = next + gap;
+        if k >= u64::from(u32::MAX) {
+            return Err(WireError::Corrupt);
+        }
+        keys.push(Key::from_index(k as usize));
+        next = k + 1;
+        Ok(())
+    };
+
+    match encoding {
+        VARINT => {
+            for _ in 0..count {
+                push(read_varint(input)?)?;
+            }
+        }
+        BITPACKED => {
+            let width = read_u8(input)? as u32;
+            if width > 32 {
+                return Err(WireError::Corrupt);
+            }
+            let len = (count as u64 * width as u64).div_ceil(8);
+            if (input.len() as u64) < len {
+                return Err(WireError::Truncated);
+            }
+            let (packed, rest) = input.split_at(len as usize);
+            *input = rest;
+
+            let mask = (1u64 << width) - 1;
+            let mut bytes = packed.iter();
+            let mut acc = 0u64;
+            let mut bits = 0;
+            for _ in 0..count {
+                while bits < width {
+                    acc |= (*bytes.next().ok_or(WireError::Truncated)? as u64) << bits;
+                    bits += 8;
+                }
+                push(acc & mask)?;
+                acc >>= width;
+                bits -= width;
+            }
+        }
+        e => return Err(WireError::UnknownEncoding(e)),
+    }
+
+    Ok(keys)
+}
+
+fn varint_len(v: u32) -> usize {
+    (32 - v.leading_zeros()).max(1).div_ceil(7) as usize
+}
+
+pub(crate) fn write_varint(out: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        out.push(v as u8 | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+pub(crate) fn read_varint(input: &mut &[u8]) -> Result<u64, WireError> {
+    let mut v = 0u64;
+    for shift in (0..64).step_by(7) {
+        let b = read_u8(input)?;
+        v |= ((b & 0x7f) as u64) << shift;
+        if b & 0x80 == 0 {
+            return Ok(v);
+        }
+    }
+    Err(WireError::Corrupt)
+}
+
+pub(crate) fn read_u8(input: &mut &[u8]) -> Result<u8, WireError> {
+    let (&b, rest) = input.split_first().ok_or(WireError::Truncated)?;
+    *input = rest;
+    Ok(b)
+}